@@ -2,14 +2,17 @@ use anyhow::Result;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use crate::resource::index::{BuildIndexParams, IndexCache, RootConfig};
+use crate::resource::index::{BuildIndexParams, IndexCache, ResourceItem, RootConfig};
 use crate::scan;
-use crate::util::{extract_placeholders, placeholder_equal};
+use crate::util::{
+    extract_placeholders, find_key_ignoring_namespace_case, is_cancelled, is_js_ts_file,
+    lang_from_extension, matches_key_pattern, path_to_string, placeholder_equal,
+};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct DiagnoseParams {
     pub project_root: String,
     pub roots: Vec<RootConfig>,
@@ -24,6 +27,113 @@ pub struct DiagnoseParams {
     pub open_buffers: Vec<OpenBuffer>,
     #[serde(default)]
     pub cancel_token_path: Option<String>,
+    /// Restrict the result to these issue kinds. Checks that cannot produce any of
+    /// them are skipped entirely rather than filtered afterwards.
+    #[serde(default)]
+    pub only_kinds: Option<Vec<String>>,
+    /// Drop issues less severe than this (severity numbers above it). Checks whose
+    /// issues would all be dropped are skipped.
+    #[serde(default)]
+    pub min_severity: Option<u32>,
+    /// Look for strings authored more than once, e.g. primary values that differ
+    /// only by trailing punctuation (`punctuation_variant`).
+    #[serde(default)]
+    pub detect_duplicates: bool,
+    /// Don't report `unused` for keys whose resource file was modified within this
+    /// many milliseconds, so keys added in the current change aren't flagged yet.
+    #[serde(default)]
+    pub unused_grace_ms: Option<u64>,
+    /// Match namespaces case-insensitively when a key is not found as written,
+    /// reporting `namespace_case_mismatch` instead of `missing`.
+    #[serde(default)]
+    pub case_insensitive_namespaces: bool,
+    /// Flag primary values far shorter than their translations
+    /// (`suspicious_primary_stub`), which usually means a placeholder string.
+    #[serde(default)]
+    pub detect_primary_stubs: bool,
+    /// Flag values that spell a character as an HTML entity (`&amp;`) where another
+    /// language uses the literal character (`html_entity_in_value`).
+    #[serde(default)]
+    pub detect_html_entities: bool,
+    /// Whether unresolved keys that fell back to `fallback_namespace` are errors
+    /// like any other missing key. With `Some(false)` they are reported as info,
+    /// for setups where the fallback namespace is only a catch-all. Unset is strict.
+    #[serde(default)]
+    pub strict_fallback: Option<bool>,
+    /// Markers such as `TODO` or `[MACHINE]` that must not ship; any value containing
+    /// one is reported as `translation_todo`. Empty disables the check.
+    #[serde(default)]
+    pub todo_markers: Vec<String>,
+    /// Hooks that establish a namespace scope; see `scan::ExtractParams::hook_names`.
+    #[serde(default)]
+    pub hook_names: Option<Vec<String>>,
+    /// See `scan::ExtractParams::t_object_names`.
+    #[serde(default)]
+    pub t_object_names: Option<Vec<String>>,
+    /// See `scan::ExtractParams::ns_separator`.
+    #[serde(default, deserialize_with = "scan::deserialize_separator")]
+    pub ns_separator: Option<String>,
+    /// See `scan::ExtractParams::key_separator`.
+    #[serde(default, deserialize_with = "scan::deserialize_separator")]
+    pub key_separator: Option<String>,
+}
+
+impl DiagnoseParams {
+    fn wants_kind(&self, kind: &str) -> bool {
+        let requested = match &self.only_kinds {
+            Some(kinds) => kinds.iter().any(|k| k == kind),
+            None => true,
+        };
+        requested
+            && self
+                .min_severity
+                .is_none_or(|min| kind_severity(kind) <= min)
+    }
+}
+
+/// Severity each issue kind is reported with (1 = most severe).
+fn kind_severity(kind: &str) -> u32 {
+    match kind {
+        "resource_root_missing" | "unused_skipped" => 1,
+        "unused"
+        | "drift_missing"
+        | "drift_extra"
+        | "html_entity_in_value"
+        | "punctuation_variant"
+        | "suspicious_primary_stub" => 3,
+        _ => 2,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MissingMatrixParams {
+    pub cache_key: String,
+    pub primary_lang: String,
+    pub languages: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MissingMatrixRow {
+    pub key: String,
+    pub missing_in: Vec<String>,
+    /// Primary value, the source text for translators.
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UntranslatedParams {
+    /// Project configuration, as for `doctor/diagnose`.
+    #[serde(flatten)]
+    pub config: DiagnoseParams,
+    /// Language to list untranslated keys for.
+    pub lang: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UntranslatedKey {
+    pub key: String,
+    /// Primary value, the source text for translators.
+    pub primary_value: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -49,46 +159,51 @@ pub struct DoctorIssue {
     pub col: Option<u32>,
 }
 
-fn is_js_ts_file(path: &std::path::Path) -> bool {
-    matches!(
-        path.extension().and_then(|e| e.to_str()),
-        Some("js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" | "mts" | "cts")
-    )
+/// Value with trailing punctuation and whitespace removed, for grouping variants
+/// such as "Welcome" / "Welcome!".
+fn strip_trailing_punctuation(value: &str) -> &str {
+    value.trim_end_matches(|c: char| {
+        c.is_whitespace()
+            || matches!(
+                c,
+                '.' | '!' | '?' | ',' | ':' | ';' | '…' | '。' | '！' | '？' | '、'
+            )
+    })
 }
 
-fn lang_from_extension(path: &std::path::Path) -> &str {
-    match path.extension().and_then(|e| e.to_str()) {
-        Some("tsx") => "tsx",
-        Some("jsx") => "jsx",
-        Some("ts" | "mts" | "cts") => "typescript",
-        _ => "javascript",
-    }
-}
+/// HTML entities commonly pasted into translation values, with the character each
+/// stands for.
+const HTML_ENTITIES: &[(&str, char)] = &[
+    ("&amp;", '&'),
+    ("&lt;", '<'),
+    ("&gt;", '>'),
+    ("&quot;", '"'),
+    ("&#34;", '"'),
+    ("&apos;", '\''),
+    ("&#39;", '\''),
+    ("&nbsp;", '\u{a0}'),
+];
 
 fn should_ignore_key(key: &str, ignore_patterns: &[String]) -> bool {
-    ignore_patterns.iter().any(|pattern| {
-        if pattern.is_empty() {
-            return false;
-        }
-        let anchored_start = pattern.starts_with('^');
-        let anchored_end = pattern.ends_with('$');
-        let start = if anchored_start { 1 } else { 0 };
-        let end = if anchored_end {
-            pattern.len().saturating_sub(1)
-        } else {
-            pattern.len()
-        };
-        let needle = &pattern[start..end];
-        if anchored_start && anchored_end {
-            key == needle
-        } else if anchored_start {
-            key.starts_with(needle)
-        } else if anchored_end {
-            key.ends_with(needle)
-        } else {
-            key.contains(needle)
-        }
-    })
+    matches_key_pattern(key, ignore_patterns)
+}
+
+/// Suffixes i18next appends for plural forms: `t("item", { count })` reads
+/// `item_one`, `item_other`, ...
+const PLURAL_SUFFIXES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+/// Base key of a plural form: `common:item` for `common:item_one`.
+fn plural_base(key: &str) -> Option<&str> {
+    let segment_start = key.rfind(['.', ':']).map_or(0, |i| i + 1);
+    let (base, suffix) = key.rsplit_once('_')?;
+    (base.len() > segment_start && PLURAL_SUFFIXES.contains(&suffix)).then_some(base)
+}
+
+/// Whether `keys` holds a plural form of `base`, e.g. `item_one` for `item`.
+fn has_plural_form(keys: &HashMap<String, ResourceItem>, base: &str) -> bool {
+    PLURAL_SUFFIXES
+        .iter()
+        .any(|suffix| keys.contains_key(&format!("{}_{}", base, suffix)))
 }
 
 /// Result of scanning a single file
@@ -98,13 +213,6 @@ struct FileResult {
     scan_failed: bool,
 }
 
-fn is_cancelled(token_path: Option<&str>) -> bool {
-    match token_path {
-        Some(path) if !path.is_empty() => Path::new(path).exists(),
-        _ => false,
-    }
-}
-
 fn make_result(issues: Vec<DoctorIssue>, used_keys_set: HashSet<String>, cancelled: bool) -> Value {
     let used_keys_map: HashMap<String, bool> =
         used_keys_set.into_iter().map(|k| (k, true)).collect();
@@ -120,7 +228,7 @@ fn process_file(
     params: &DiagnoseParams,
     index_data: &crate::resource::index::IndexResult,
 ) -> FileResult {
-    let file_str = file_path.to_string_lossy().to_string();
+    let file_str = path_to_string(file_path);
     if params.open_buf_paths.iter().any(|p| p == &file_str) {
         return FileResult {
             keys: Vec::new(),
@@ -156,18 +264,25 @@ fn process_source(
         lang: lang.to_string(),
         fallback_namespace: params.fallback_namespace.clone(),
         range: None,
+        hook_names: params.hook_names.clone(),
+        t_object_names: params.t_object_names.clone(),
+        ns_separator: params.ns_separator.clone(),
+        key_separator: params.key_separator.clone(),
+        ..Default::default()
     });
 
     if let Err(err) = &extracted {
-        issues.push(DoctorIssue {
-            kind: "scan_error".to_string(),
-            message: format!("Failed to analyze source: {}", err),
-            severity: 2,
-            file: file.map(|p| p.to_string()),
-            key: None,
-            lnum: None,
-            col: None,
-        });
+        if params.wants_kind("scan_error") {
+            issues.push(DoctorIssue {
+                kind: "scan_error".to_string(),
+                message: format!("Failed to analyze source: {}", err),
+                severity: kind_severity("scan_error"),
+                file: file.map(|p| p.to_string()),
+                key: None,
+                lnum: None,
+                col: None,
+            });
+        }
         return FileResult {
             keys,
             issues,
@@ -182,6 +297,30 @@ fn process_source(
                     if should_ignore_key(key, &params.ignore_patterns) {
                         continue;
                     }
+                    let case_key = index_data
+                        .index
+                        .get(&params.primary_lang)
+                        .filter(|_| params.case_insensitive_namespaces)
+                        .and_then(|m| find_key_ignoring_namespace_case(m, key))
+                        .cloned();
+                    if let Some(actual) = case_key
+                        .as_ref()
+                        .filter(|_| params.wants_kind("namespace_case_mismatch"))
+                    {
+                        issues.push(DoctorIssue {
+                            kind: "namespace_case_mismatch".to_string(),
+                            message: format!(
+                                "Key '{}' matches resource key '{}' only with different namespace casing",
+                                key, actual
+                            ),
+                            severity: kind_severity("namespace_case_mismatch"),
+                            file: file.map(|p| p.to_string()),
+                            key: Some(key.to_string()),
+                            lnum: item.get("lnum").and_then(|v| v.as_u64()).map(|v| v as u32),
+                            col: item.get("col").and_then(|v| v.as_u64()).map(|v| v as u32),
+                        });
+                    }
+                    let key = case_key.as_deref().unwrap_or(key);
                     keys.push(key.to_string());
 
                     let primary_value = index_data
@@ -190,6 +329,54 @@ fn process_source(
                         .and_then(|m| m.get(key))
                         .and_then(|e| e.value.as_deref());
 
+                    // i18next context: `t("friend", { context: "male" })` reads
+                    // `friend_male`, falling back to `friend`.
+                    // Either form may be plural: `friend_male_one`.
+                    let context_keys: Vec<String> = item
+                        .get("context")
+                        .and_then(|v| v.as_array())
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|v| v.as_str())
+                        .map(|context| format!("{}_{}", key, context))
+                        .filter(|context_key| {
+                            index_data.index.get(&params.primary_lang).is_some_and(|m| {
+                                m.contains_key(context_key) || has_plural_form(m, context_key)
+                            })
+                        })
+                        .collect();
+                    keys.extend(context_keys.iter().cloned());
+                    if primary_value.is_none() && !context_keys.is_empty() {
+                        continue;
+                    }
+                    // Plural forms (`item_one`) stand in for a base key that has no
+                    // value of its own.
+                    let has_variant = || {
+                        index_data
+                            .index
+                            .get(&params.primary_lang)
+                            .is_some_and(|m| has_plural_form(m, key))
+                    };
+                    if primary_value.is_none() && has_variant() {
+                        continue;
+                    }
+
+                    // `returnObjects: true` may name a subtree; any leaf under it counts.
+                    let returns_object = item
+                        .get("return_objects")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    if returns_object && primary_value.is_none() {
+                        let prefix = format!("{}.", key);
+                        let has_subtree = index_data
+                            .index
+                            .get(&params.primary_lang)
+                            .is_some_and(|m| m.keys().any(|k| k.starts_with(&prefix)));
+                        if has_subtree {
+                            continue;
+                        }
+                    }
+
                     let raw = item.get("raw").and_then(|v| v.as_str()).unwrap_or("");
                     let key_path = key.split_once(':').map(|(_, path)| path).unwrap_or(key);
                     let is_missing = match primary_value {
@@ -202,23 +389,76 @@ fn process_source(
                         }
                     };
 
+                    // A value with placeholders rendered without any options argument
+                    // shows the raw `{{name}}` markers to users.
+                    let has_options = item
+                        .get("has_options")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    if let Some(pv) = primary_value.filter(|_| {
+                        !is_missing
+                            && !has_options
+                            && params.wants_kind("interpolation_without_args")
+                    }) {
+                        let placeholders = extract_placeholders(pv);
+                        if !placeholders.is_empty() {
+                            issues.push(DoctorIssue {
+                                kind: "interpolation_without_args".to_string(),
+                                message: format!(
+                                    "Key '{}' interpolates {} but the call passes no options",
+                                    key,
+                                    placeholders.join(", ")
+                                ),
+                                severity: kind_severity("interpolation_without_args"),
+                                file: file.map(|p| p.to_string()),
+                                key: Some(key.to_string()),
+                                lnum: item.get("lnum").and_then(|v| v.as_u64()).map(|v| v as u32),
+                                col: item.get("col").and_then(|v| v.as_u64()).map(|v| v as u32),
+                            });
+                        }
+                    }
+
                     if is_missing {
+                        if !params.wants_kind("missing") {
+                            continue;
+                        }
+                        let used_fallback = item
+                            .get("fallback")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false);
+                        // An inline `defaultValue` still renders something sensible.
+                        let default_value = item.get("default_value").and_then(|v| v.as_str());
+                        let lenient_fallback =
+                            used_fallback && params.strict_fallback == Some(false);
+                        let severity = if default_value.is_some() || lenient_fallback {
+                            3
+                        } else {
+                            kind_severity("missing")
+                        };
+                        if params.min_severity.is_some_and(|min| severity > min) {
+                            continue;
+                        }
                         let lnum = item.get("lnum").and_then(|v| v.as_u64()).map(|v| v as u32);
                         let col = item.get("col").and_then(|v| v.as_u64()).map(|v| v as u32);
 
+                        let mut message = format!(
+                            "Key '{}' is missing in primary language '{}'",
+                            key, params.primary_lang
+                        );
+                        if let Some(default_value) = default_value {
+                            message.push_str(&format!(" (inline default '{}')", default_value));
+                        }
                         issues.push(DoctorIssue {
                             kind: "missing".to_string(),
-                            message: format!(
-                                "Key '{}' is missing in primary language '{}'",
-                                key, params.primary_lang
-                            ),
-                            severity: 2,
+                            message,
+                            severity,
                             file: file.map(|p| p.to_string()),
                             key: Some(key.to_string()),
                             lnum,
                             col,
                         });
-                    } else if let Some(pv) = primary_value {
+                    } else if let Some(pv) = primary_value.filter(|_| params.wants_kind("mismatch"))
+                    {
                         let base_ph = extract_placeholders(pv);
                         for lang in &params.languages {
                             if lang == &params.primary_lang {
@@ -239,7 +479,7 @@ fn process_source(
                                             "Placeholder mismatch for '{}' between '{}' and '{}'",
                                             key, params.primary_lang, lang
                                         ),
-                                        severity: 2,
+                                        severity: kind_severity("mismatch"),
                                         file: file.map(|p| p.to_string()),
                                         key: Some(key.to_string()),
                                         lnum: None,
@@ -261,60 +501,24 @@ fn process_source(
     }
 }
 
-pub fn diagnose(params: DiagnoseParams, notify: &dyn Fn(&str, Value)) -> Result<Value> {
-    let cancel_token_path = params.cancel_token_path.clone();
-    let is_cancelled_now = || is_cancelled(cancel_token_path.as_deref());
-
-    let mut issues: Vec<DoctorIssue> = Vec::new();
-    let mut used_keys_set: HashSet<String> = HashSet::new();
-    let mut has_scan_failures = false;
-
-    if is_cancelled_now() {
-        return Ok(make_result(issues, used_keys_set, true));
-    }
-
-    if params.roots.is_empty() {
-        issues.push(DoctorIssue {
-            kind: "resource_root_missing".to_string(),
-            message: "No resource roots found. Expected locales/, public/locales/, or messages/ directory.".to_string(),
-            severity: 1,
-            file: None,
-            key: None,
-            lnum: None,
-            col: None,
-        });
-        return Ok(make_result(issues, used_keys_set, false));
-    }
-
-    // Build resource index
-    let cache = IndexCache::new();
-    let index_result = crate::resource::index::build_index(
-        BuildIndexParams {
-            roots: params.roots.clone(),
-        },
-        &cache,
-    )?;
-    let index_data: crate::resource::index::IndexResult = serde_json::from_value(index_result)?;
-
-    if is_cancelled_now() {
-        return Ok(make_result(issues, used_keys_set, true));
-    }
-
-    for error in &index_data.errors {
-        issues.push(DoctorIssue {
-            kind: "resource_error".to_string(),
-            message: format!("Failed to parse {}: {}", error.file, error.error),
-            severity: 2,
-            file: Some(error.file.clone()),
-            key: None,
-            lnum: None,
-            col: None,
-        });
-    }
+/// Scan open buffers and every source file under the project root, collecting the
+/// keys used in code and the per-call issues. The flag is true when cancelled, in
+/// which case the result is partial.
+fn scan_project_sources(
+    params: &DiagnoseParams,
+    index_data: &crate::resource::index::IndexResult,
+    notify: &dyn Fn(&str, Value),
+    is_cancelled_now: &dyn Fn() -> bool,
+) -> (FileResult, bool) {
+    let mut scanned = FileResult {
+        keys: Vec::new(),
+        issues: Vec::new(),
+        scan_failed: false,
+    };
 
     for open_buf in &params.open_buffers {
         if is_cancelled_now() {
-            return Ok(make_result(issues, used_keys_set, true));
+            return (scanned, true);
         }
         if open_buf.source.is_empty() {
             continue;
@@ -330,16 +534,12 @@ pub fn diagnose(params: DiagnoseParams, notify: &dyn Fn(&str, Value)) -> Result<
             &open_buf.source,
             lang,
             open_buf.path.as_deref(),
-            &params,
-            &index_data,
+            params,
+            index_data,
         );
-        if result.scan_failed {
-            has_scan_failures = true;
-        }
-        for key in result.keys {
-            used_keys_set.insert(key);
-        }
-        issues.extend(result.issues);
+        scanned.scan_failed |= result.scan_failed;
+        scanned.keys.extend(result.keys);
+        scanned.issues.extend(result.issues);
     }
 
     // Collect source files
@@ -364,7 +564,7 @@ pub fn diagnose(params: DiagnoseParams, notify: &dyn Fn(&str, Value)) -> Result<
                     "file_total": 0
                 }),
             );
-            return Ok(make_result(issues, used_keys_set, true));
+            return (scanned, true);
         }
         if discovered_entries > 0 && discovered_entries % 500 == 0 {
             notify(
@@ -408,22 +608,18 @@ pub fn diagnose(params: DiagnoseParams, notify: &dyn Fn(&str, Value)) -> Result<
                     "file_total": total_files
                 }),
             );
-            return Ok(make_result(issues, used_keys_set, true));
+            return (scanned, true);
         }
 
         let results: Vec<FileResult> = chunk
             .par_iter()
-            .map(|file_path| process_file(file_path, &params, &index_data))
+            .map(|file_path| process_file(file_path, params, index_data))
             .collect();
 
         for result in results {
-            if result.scan_failed {
-                has_scan_failures = true;
-            }
-            for key in result.keys {
-                used_keys_set.insert(key);
-            }
-            issues.extend(result.issues);
+            scanned.scan_failed |= result.scan_failed;
+            scanned.keys.extend(result.keys);
+            scanned.issues.extend(result.issues);
         }
 
         processed += chunk.len();
@@ -437,43 +633,192 @@ pub fn diagnose(params: DiagnoseParams, notify: &dyn Fn(&str, Value)) -> Result<
         );
     }
 
+    (scanned, false)
+}
+
+pub fn diagnose(params: DiagnoseParams, notify: &dyn Fn(&str, Value)) -> Result<Value> {
+    let cancel_token_path = params.cancel_token_path.clone();
+    let is_cancelled_now = || is_cancelled(cancel_token_path.as_deref());
+
+    let mut issues: Vec<DoctorIssue> = Vec::new();
+    let mut used_keys_set: HashSet<String> = HashSet::new();
+    let mut has_scan_failures = false;
+
+    if is_cancelled_now() {
+        return Ok(make_result(issues, used_keys_set, true));
+    }
+
+    if params.roots.is_empty() {
+        if !params.wants_kind("resource_root_missing") {
+            return Ok(make_result(issues, used_keys_set, false));
+        }
+        issues.push(DoctorIssue {
+            kind: "resource_root_missing".to_string(),
+            message: "No resource roots found. Expected locales/, public/locales/, or messages/ directory.".to_string(),
+            severity: kind_severity("resource_root_missing"),
+            file: None,
+            key: None,
+            lnum: None,
+            col: None,
+        });
+        return Ok(make_result(issues, used_keys_set, false));
+    }
+
+    // Build resource index
+    let cache = IndexCache::new();
+    let index_result = crate::resource::index::build_index(
+        BuildIndexParams {
+            roots: params.roots.clone(),
+        },
+        &cache,
+    )?;
+    let index_data: crate::resource::index::IndexResult = serde_json::from_value(index_result)?;
+
+    if is_cancelled_now() {
+        return Ok(make_result(issues, used_keys_set, true));
+    }
+
+    for error in index_data
+        .errors
+        .iter()
+        .filter(|_| params.wants_kind("resource_error"))
+    {
+        issues.push(DoctorIssue {
+            kind: "resource_error".to_string(),
+            message: format!("Failed to parse {}: {}", error.file, error.error),
+            severity: kind_severity("resource_error"),
+            file: Some(error.file.clone()),
+            key: None,
+            lnum: None,
+            col: None,
+        });
+    }
+
+    // Source scanning only feeds the per-call checks and unused detection.
+    let needs_scan = [
+        "missing",
+        "mismatch",
+        "interpolation_without_args",
+        "unused",
+        "scan_error",
+        "namespace_file_missing",
+    ]
+    .iter()
+    .any(|kind| params.wants_kind(kind));
+
+    if needs_scan {
+        let (scanned, cancelled) =
+            scan_project_sources(&params, &index_data, notify, &is_cancelled_now);
+        has_scan_failures = scanned.scan_failed;
+        used_keys_set.extend(scanned.keys);
+        issues.extend(scanned.issues);
+        if cancelled {
+            return Ok(make_result(issues, used_keys_set, true));
+        }
+    }
+
     // Check for unused keys
-    if has_scan_failures {
+    if !params.wants_kind("unused") {
+        // Not requested: skip the walk over every primary key.
+    } else if has_scan_failures {
         issues.push(DoctorIssue {
             kind: "unused_skipped".to_string(),
             message:
                 "Skipped unused key detection because one or more source files failed to analyze."
                     .to_string(),
-            severity: 1,
+            severity: kind_severity("unused_skipped"),
             file: None,
             key: None,
             lnum: None,
             col: None,
         });
     } else if let Some(primary_index) = index_data.index.get(&params.primary_lang) {
+        // Index mtimes are nanoseconds since the epoch.
+        let grace_cutoff = params.unused_grace_ms.map(|grace_ms| {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos() as u64;
+            now.saturating_sub(grace_ms.saturating_mul(1_000_000))
+        });
+        let in_grace = |file: Option<&str>| {
+            grace_cutoff.is_some_and(|cutoff| {
+                file.and_then(|f| index_data.files.get(f))
+                    .is_some_and(|mtime| *mtime >= cutoff)
+            })
+        };
         for (key, entry) in primary_index {
             if is_cancelled_now() {
                 return Ok(make_result(issues, used_keys_set, true));
             }
-            if should_ignore_key(key, &params.ignore_patterns) {
+            if should_ignore_key(key, &params.ignore_patterns) || in_grace(entry.file.as_deref()) {
                 continue;
             }
-            if !used_keys_set.contains(key) {
+            // A plural form is used through its base key. Context forms are only
+            // used when the call's context is known, which adds them explicitly.
+            let used = used_keys_set.contains(key)
+                || plural_base(key).is_some_and(|base| used_keys_set.contains(base));
+            if !used {
                 issues.push(DoctorIssue {
                     kind: "unused".to_string(),
                     message: format!("Key '{}' exists in resources but is not used in code", key),
-                    severity: 3,
+                    severity: kind_severity("unused"),
                     file: entry.file.clone(),
                     key: Some(key.clone()),
-                    lnum: None,
+                    lnum: entry.lnum,
                     col: None,
                 });
             }
         }
     }
 
+    // Namespaces used in code with no primary-language keys at all: the resource
+    // file is missing (or misnamed) rather than individual keys.
+    let mut namespace_coverage: BTreeMap<&str, usize> = BTreeMap::new();
+    if params.wants_kind("namespace_file_missing") && !has_scan_failures {
+        for key in &used_keys_set {
+            if let Some((ns, _)) = key.split_once(':') {
+                namespace_coverage.insert(ns, 0);
+            }
+        }
+        for key in index_data
+            .index
+            .get(&params.primary_lang)
+            .into_iter()
+            .flat_map(|m| m.keys())
+        {
+            if let Some(count) = key
+                .split_once(':')
+                .and_then(|(ns, _)| namespace_coverage.get_mut(ns))
+            {
+                *count += 1;
+            }
+        }
+        for (ns, _) in namespace_coverage.iter().filter(|(_, count)| **count == 0) {
+            issues.push(DoctorIssue {
+                kind: "namespace_file_missing".to_string(),
+                message: format!(
+                    "Namespace '{}' is used in code but has no keys in primary language '{}'",
+                    ns, params.primary_lang
+                ),
+                severity: kind_severity("namespace_file_missing"),
+                file: None,
+                key: None,
+                lnum: None,
+                col: None,
+            });
+        }
+    }
+    let namespace_coverage = serde_json::to_value(&namespace_coverage)?;
+
     // Check for drift
-    if let Some(primary_index) = index_data.index.get(&params.primary_lang) {
+    let wants_drift_missing = params.wants_kind("drift_missing");
+    let wants_drift_extra = params.wants_kind("drift_extra");
+    if let Some(primary_index) = index_data
+        .index
+        .get(&params.primary_lang)
+        .filter(|_| wants_drift_missing || wants_drift_extra)
+    {
         for lang in &params.languages {
             if is_cancelled_now() {
                 return Ok(make_result(issues, used_keys_set, true));
@@ -483,7 +828,7 @@ pub fn diagnose(params: DiagnoseParams, notify: &dyn Fn(&str, Value)) -> Result<
             }
             let other_index = index_data.index.get(lang.as_str());
 
-            for key in primary_index.keys() {
+            for key in primary_index.keys().filter(|_| wants_drift_missing) {
                 if is_cancelled_now() {
                     return Ok(make_result(issues, used_keys_set, true));
                 }
@@ -503,7 +848,7 @@ pub fn diagnose(params: DiagnoseParams, notify: &dyn Fn(&str, Value)) -> Result<
                             "Key '{}' exists in '{}' but is missing in '{}'",
                             key, params.primary_lang, lang
                         ),
-                        severity: 3,
+                        severity: kind_severity("drift_missing"),
                         file: None,
                         key: Some(key.clone()),
                         lnum: None,
@@ -512,7 +857,7 @@ pub fn diagnose(params: DiagnoseParams, notify: &dyn Fn(&str, Value)) -> Result<
                 }
             }
 
-            if let Some(other) = other_index {
+            if let Some(other) = other_index.filter(|_| wants_drift_extra) {
                 for key in other.keys() {
                     if is_cancelled_now() {
                         return Ok(make_result(issues, used_keys_set, true));
@@ -527,7 +872,7 @@ pub fn diagnose(params: DiagnoseParams, notify: &dyn Fn(&str, Value)) -> Result<
                                 "Key '{}' exists in '{}' but not in primary '{}'",
                                 key, lang, params.primary_lang
                             ),
-                            severity: 3,
+                            severity: kind_severity("drift_extra"),
                             file: None,
                             key: Some(key.clone()),
                             lnum: None,
@@ -539,5 +884,343 @@ pub fn diagnose(params: DiagnoseParams, notify: &dyn Fn(&str, Value)) -> Result<
         }
     }
 
-    Ok(make_result(issues, used_keys_set, false))
+    // Keys every non-primary language has but primary lacks: more likely forgotten in
+    // primary than stray extras, so they are reported above `drift_extra`.
+    let other_indexes: Option<Vec<_>> = params
+        .languages
+        .iter()
+        .filter(|lang| *lang != &params.primary_lang)
+        .map(|lang| index_data.index.get(lang.as_str()))
+        .collect();
+    if let Some((first, rest)) = other_indexes
+        .as_deref()
+        .and_then(|others| others.split_first())
+        .filter(|_| params.wants_kind("missing_from_primary_only"))
+    {
+        let primary_index = index_data.index.get(&params.primary_lang);
+        let mut keys: Vec<&String> = first
+            .keys()
+            .filter(|key| !primary_index.is_some_and(|m| m.contains_key(*key)))
+            .filter(|key| rest.iter().all(|other| other.contains_key(*key)))
+            .filter(|key| !should_ignore_key(key, &params.ignore_patterns))
+            .collect();
+        keys.sort();
+        for key in keys {
+            issues.push(DoctorIssue {
+                kind: "missing_from_primary_only".to_string(),
+                message: format!(
+                    "Key '{}' exists in every other language but not in primary '{}'",
+                    key, params.primary_lang
+                ),
+                severity: kind_severity("missing_from_primary_only"),
+                file: None,
+                key: Some(key.clone()),
+                lnum: None,
+                col: None,
+            });
+        }
+    }
+
+    // Primary values that only differ by trailing punctuation are usually the same
+    // string authored twice.
+    if params.detect_duplicates && params.wants_kind("punctuation_variant") {
+        let mut groups: BTreeMap<&str, Vec<(&String, &str)>> = BTreeMap::new();
+        for (key, item) in index_data
+            .index
+            .get(&params.primary_lang)
+            .into_iter()
+            .flatten()
+        {
+            let Some(value) = item.value.as_deref() else {
+                continue;
+            };
+            let stem = strip_trailing_punctuation(value);
+            if stem.is_empty() || should_ignore_key(key, &params.ignore_patterns) {
+                continue;
+            }
+            groups.entry(stem).or_default().push((key, value));
+        }
+        for mut group in groups.into_values() {
+            if group.iter().all(|(_, value)| *value == group[0].1) {
+                continue;
+            }
+            group.sort();
+            let keys: Vec<&str> = group.iter().map(|(key, _)| key.as_str()).collect();
+            issues.push(DoctorIssue {
+                kind: "punctuation_variant".to_string(),
+                message: format!(
+                    "Keys {} have values that differ only by trailing punctuation",
+                    keys.join(", ")
+                ),
+                severity: kind_severity("punctuation_variant"),
+                file: None,
+                key: Some(keys[0].to_string()),
+                lnum: None,
+                col: None,
+            });
+        }
+    }
+
+    // A primary value at most a quarter as long as the median translation (of at
+    // least `STUB_MIN_MEDIAN` chars) is likely a stub, e.g. "x" next to full sentences.
+    const STUB_MIN_MEDIAN: usize = 8;
+    if params.detect_primary_stubs && params.wants_kind("suspicious_primary_stub") {
+        let mut primary_entries: Vec<(&String, &str)> = index_data
+            .index
+            .get(&params.primary_lang)
+            .into_iter()
+            .flatten()
+            .filter_map(|(key, item)| item.value.as_deref().map(|value| (key, value)))
+            .filter(|(key, _)| !should_ignore_key(key, &params.ignore_patterns))
+            .collect();
+        primary_entries.sort();
+        for (key, value) in primary_entries {
+            let mut lengths: Vec<usize> = params
+                .languages
+                .iter()
+                .filter(|lang| **lang != params.primary_lang)
+                .filter_map(|lang| index_data.index.get(lang.as_str())?.get(key))
+                .filter_map(|item| item.value.as_deref())
+                .filter(|v| !v.is_empty())
+                .map(|v| v.chars().count())
+                .collect();
+            if lengths.is_empty() {
+                continue;
+            }
+            lengths.sort_unstable();
+            let median = lengths[lengths.len() / 2];
+            let primary_len = value.chars().count();
+            if median < STUB_MIN_MEDIAN || primary_len * 4 > median {
+                continue;
+            }
+            issues.push(DoctorIssue {
+                kind: "suspicious_primary_stub".to_string(),
+                message: format!(
+                    "Primary value of '{}' has {} chars while translations have a median of {}",
+                    key, primary_len, median
+                ),
+                severity: kind_severity("suspicious_primary_stub"),
+                file: None,
+                key: Some(key.clone()),
+                lnum: None,
+                col: None,
+            });
+        }
+    }
+
+    // `Tom &amp; Jerry` in one language and `Tom & Jerry` in another: the entity was
+    // most likely copied from HTML by mistake.
+    if params.detect_html_entities && params.wants_kind("html_entity_in_value") {
+        let mut langs: Vec<&String> = vec![&params.primary_lang];
+        langs.extend(
+            params
+                .languages
+                .iter()
+                .filter(|lang| **lang != params.primary_lang),
+        );
+        let value_of = |lang: &str, key: &str| {
+            index_data
+                .index
+                .get(lang)
+                .and_then(|m| m.get(key))
+                .and_then(|item| item.value.as_deref())
+        };
+        for lang in &langs {
+            let Some(lang_map) = index_data.index.get(lang.as_str()) else {
+                continue;
+            };
+            let mut entries: Vec<(&String, &crate::resource::index::ResourceItem)> =
+                lang_map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            for (key, item) in entries {
+                let Some(value) = item.value.as_deref() else {
+                    continue;
+                };
+                if should_ignore_key(key, &params.ignore_patterns) {
+                    continue;
+                }
+                let Some((entity, other_lang)) = HTML_ENTITIES
+                    .iter()
+                    .filter(|(entity, _)| value.contains(entity))
+                    .find_map(|(entity, literal)| {
+                        langs
+                            .iter()
+                            .filter(|other| *other != lang)
+                            .find(|other| {
+                                value_of(other, key)
+                                    .is_some_and(|v| v.contains(*literal) && !v.contains(entity))
+                            })
+                            .map(|other| (entity, other))
+                    })
+                else {
+                    continue;
+                };
+                issues.push(DoctorIssue {
+                    kind: "html_entity_in_value".to_string(),
+                    message: format!(
+                        "Value of '{}' in '{}' contains '{}' while '{}' uses the literal character",
+                        key, lang, entity, other_lang
+                    ),
+                    severity: kind_severity("html_entity_in_value"),
+                    file: item.file.clone(),
+                    key: Some(key.clone()),
+                    lnum: None,
+                    col: None,
+                });
+            }
+        }
+    }
+
+    if !params.todo_markers.is_empty() && params.wants_kind("translation_todo") {
+        let mut langs: Vec<&String> = vec![&params.primary_lang];
+        langs.extend(
+            params
+                .languages
+                .iter()
+                .filter(|lang| **lang != params.primary_lang),
+        );
+        for lang in langs {
+            let Some(lang_map) = index_data.index.get(lang.as_str()) else {
+                continue;
+            };
+            let mut entries: Vec<(&String, &crate::resource::index::ResourceItem)> =
+                lang_map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            for (key, item) in entries {
+                let Some(marker) = item.value.as_deref().and_then(|value| {
+                    params
+                        .todo_markers
+                        .iter()
+                        .find(|marker| !marker.is_empty() && value.contains(marker.as_str()))
+                }) else {
+                    continue;
+                };
+                if should_ignore_key(key, &params.ignore_patterns) {
+                    continue;
+                }
+                issues.push(DoctorIssue {
+                    kind: "translation_todo".to_string(),
+                    message: format!(
+                        "Value of '{}' in '{}' contains marker '{}'",
+                        key, lang, marker
+                    ),
+                    severity: kind_severity("translation_todo"),
+                    file: item.file.clone(),
+                    key: Some(key.clone()),
+                    lnum: item.lnum,
+                    col: None,
+                });
+            }
+        }
+    }
+
+    let mut result = make_result(issues, used_keys_set, false);
+    result["namespace_coverage"] = namespace_coverage;
+    Ok(result)
+}
+
+/// Keys used in code that `lang` lacks or has empty, sorted, with their primary
+/// values. Meant for per-language translation handoff.
+pub fn untranslated_for_language(
+    params: UntranslatedParams,
+    notify: &dyn Fn(&str, Value),
+) -> Result<Value> {
+    let cancel_token_path = params.config.cancel_token_path.clone();
+    let is_cancelled_now = || is_cancelled(cancel_token_path.as_deref());
+
+    let cache = IndexCache::new();
+    let index_result = crate::resource::index::build_index(
+        BuildIndexParams {
+            roots: params.config.roots.clone(),
+        },
+        &cache,
+    )?;
+    let index_data: crate::resource::index::IndexResult = serde_json::from_value(index_result)?;
+
+    // Only the used keys are needed, not the per-call issues.
+    let config = DiagnoseParams {
+        only_kinds: Some(Vec::new()),
+        ..params.config
+    };
+    let (scanned, cancelled) =
+        scan_project_sources(&config, &index_data, notify, &is_cancelled_now);
+    if cancelled {
+        return Ok(serde_json::json!({ "keys": [], "cancelled": true }));
+    }
+
+    let value_of = |lang: &str, key: &str| {
+        index_data
+            .index
+            .get(lang)
+            .and_then(|m| m.get(key))
+            .and_then(|item| item.value.clone())
+            .filter(|value| !value.is_empty())
+    };
+    let used: BTreeSet<String> = scanned.keys.into_iter().collect();
+    let keys: Vec<UntranslatedKey> = used
+        .into_iter()
+        .filter(|key| !should_ignore_key(key, &config.ignore_patterns))
+        .filter(|key| value_of(&params.lang, key).is_none())
+        .map(|key| UntranslatedKey {
+            primary_value: value_of(&config.primary_lang, &key),
+            key,
+        })
+        .collect();
+
+    Ok(serde_json::json!({
+        "keys": keys,
+        "scan_failed": scanned.scan_failed,
+        "cancelled": false
+    }))
+}
+
+/// Every key that at least one of `languages` (or the primary language) lacks or has
+/// empty, with the languages lacking it, sorted by key. Meant for translation handoff.
+pub fn missing_matrix(params: MissingMatrixParams, cache: &IndexCache) -> Result<Value> {
+    let Some(index_data) = cache.get(&params.cache_key) else {
+        return Ok(serde_json::json!({ "success": false, "needs_rebuild": true }));
+    };
+
+    let mut langs: Vec<&String> = vec![&params.primary_lang];
+    langs.extend(
+        params
+            .languages
+            .iter()
+            .filter(|lang| **lang != params.primary_lang),
+    );
+    let has_value = |lang: &str, key: &str| {
+        index_data
+            .index
+            .get(lang)
+            .and_then(|m| m.get(key))
+            .and_then(|item| item.value.as_deref())
+            .is_some_and(|value| !value.is_empty())
+    };
+
+    let keys: BTreeSet<&String> = langs
+        .iter()
+        .filter_map(|lang| index_data.index.get(lang.as_str()))
+        .flat_map(|m| m.keys())
+        .collect();
+    let rows: Vec<MissingMatrixRow> = keys
+        .into_iter()
+        .filter_map(|key| {
+            let missing_in: Vec<String> = langs
+                .iter()
+                .filter(|lang| !has_value(lang, key))
+                .map(|lang| lang.to_string())
+                .collect();
+            (!missing_in.is_empty()).then(|| MissingMatrixRow {
+                key: key.clone(),
+                missing_in,
+                value: index_data
+                    .index
+                    .get(&params.primary_lang)
+                    .and_then(|m| m.get(key))
+                    .and_then(|item| item.value.clone()),
+            })
+        })
+        .collect();
+
+    Ok(serde_json::json!({ "rows": rows }))
 }
@@ -2,8 +2,9 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashSet;
-use swc_common::{SourceMap, Spanned};
+use swc_common::{SourceMap, SourceMapper, Span, Spanned};
 use swc_ecma_ast::*;
+use unicode_width::UnicodeWidthChar;
 
 /// Convert a Wtf8Atom (string literal value) to a Rust String
 fn wtf8_to_string(atom: &swc_atoms::Wtf8Atom) -> String {
@@ -21,6 +22,23 @@ pub struct ExtractParams {
     pub min_length: usize,
     #[serde(default = "default_exclude_components")]
     pub exclude_components: Vec<String>,
+    /// Attributes carrying a translation key. Children of any element with one of
+    /// them are skipped, like those of `exclude_components`.
+    #[serde(default = "default_key_attributes")]
+    pub key_attributes: Vec<String>,
+    /// When set, only text whose nearest enclosing element is one of these is
+    /// reported, e.g. `["p", "h1", "Heading"]`.
+    #[serde(default)]
+    pub include_elements: Option<Vec<String>>,
+    /// Report an element whose children mix text with elements/expressions as one
+    /// `jsx_mixed` item carrying a `Trans`-ready template, instead of one item per
+    /// text node.
+    #[serde(default)]
+    pub group_mixed_content: bool,
+    /// Keep line breaks in the reported text of a `jsx_text` item, collapsing only
+    /// spaces and tabs within each line, e.g. for `<pre>` content.
+    #[serde(default)]
+    pub preserve_newlines: bool,
 }
 
 fn default_min_length() -> usize {
@@ -31,6 +49,26 @@ fn default_exclude_components() -> Vec<String> {
     vec!["Trans".to_string(), "Translation".to_string()]
 }
 
+fn default_key_attributes() -> Vec<String> {
+    vec!["i18nKey".to_string()]
+}
+
+impl Default for ExtractParams {
+    fn default() -> Self {
+        Self {
+            source: String::new(),
+            lang: String::new(),
+            range: None,
+            min_length: default_min_length(),
+            exclude_components: default_exclude_components(),
+            key_attributes: default_key_attributes(),
+            include_elements: None,
+            group_mixed_content: false,
+            preserve_newlines: false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct HardcodedItem {
     pub lnum: u32,
@@ -38,7 +76,18 @@ pub struct HardcodedItem {
     pub end_lnum: u32,
     pub end_col: u32,
     pub text: String,
-    pub kind: String, // "jsx_text" or "jsx_literal"
+    pub kind: String, // "jsx_text", "jsx_literal" or "jsx_mixed"
+    /// Source text before whitespace normalization
+    pub raw_text: String,
+    /// Per-line spans covering only the non-whitespace part of the text
+    pub segments: Vec<TextSegment>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TextSegment {
+    pub lnum: u32,
+    pub col: u32,
+    pub end_col: u32,
 }
 
 fn span_to_loc(cm: &SourceMap, span: swc_common::Span) -> (u32, u32, u32, u32) {
@@ -57,6 +106,126 @@ fn normalize_whitespace(text: &str) -> String {
     result.trim().to_string()
 }
 
+/// Normalize each line separately and join the non-blank ones with `\n`.
+fn normalize_whitespace_per_line(text: &str) -> String {
+    text.lines()
+        .map(normalize_whitespace)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Split raw text starting at (`lnum`, `col`) into per-line segments, trimming
+/// surrounding whitespace on each line and dropping whitespace-only lines.
+fn text_segments(raw: &str, lnum: u32, col: u32) -> Vec<TextSegment> {
+    let mut segments = Vec::new();
+    for (i, line) in raw.split('\n').enumerate() {
+        let line_start = if i == 0 { col } else { 0 };
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let leading = display_width(&line[..line.len() - line.trim_start().len()]);
+        let start = line_start + leading;
+        segments.push(TextSegment {
+            lnum: lnum + i as u32,
+            col: start,
+            end_col: start + display_width(trimmed),
+        });
+    }
+    segments
+}
+
+/// Width of `text` in the display columns `span_to_loc` reports: tabs are 4 wide,
+/// other control characters 0, and the rest their Unicode width (2 for CJK).
+fn display_width(text: &str) -> u32 {
+    text.chars()
+        .map(|c| match c {
+            '\t' => 4,
+            c if c < ' ' => 0,
+            c if c.is_ascii() => 1,
+            c => c.width().unwrap_or(0) as u32,
+        })
+        .sum()
+}
+
+/// JSX drops whitespace-only text that spans lines, so it is not a child at all.
+fn is_ignorable_jsx_text(text: &JSXText) -> bool {
+    text.value.trim().is_empty() && text.value.contains('\n')
+}
+
+/// Children that survive JSX whitespace rules, in order. Their positions are the
+/// indices `Trans` uses for `<N>` markers.
+fn meaningful_children(children: &[JSXElementChild]) -> Vec<&JSXElementChild> {
+    children
+        .iter()
+        .filter(|child| match child {
+            JSXElementChild::JSXText(text) => !is_ignorable_jsx_text(text),
+            JSXElementChild::JSXExprContainer(container) => {
+                !matches!(container.expr, JSXExpr::JSXEmptyExpr(_))
+            }
+            _ => true,
+        })
+        .collect()
+}
+
+/// Whether children interleave non-blank text with elements or expressions.
+fn is_mixed_content(children: &[&JSXElementChild]) -> bool {
+    let has_text = children.iter().any(|child| match child {
+        JSXElementChild::JSXText(text) => !text.value.trim().is_empty(),
+        _ => false,
+    });
+    let has_other = children
+        .iter()
+        .any(|child| !matches!(child, JSXElementChild::JSXText(_)));
+    has_text && has_other
+}
+
+/// Build a `Trans`-style template: text verbatim (whitespace collapsed), child
+/// elements as `<N>...</N>` and identifiers as `{{name}}`.
+fn trans_template(children: &[&JSXElementChild]) -> String {
+    let mut out = String::new();
+    for (index, child) in children.iter().enumerate() {
+        match child {
+            JSXElementChild::JSXText(text) => {
+                let mut last_space = false;
+                for ch in text.value.chars() {
+                    if ch.is_whitespace() {
+                        if !last_space {
+                            out.push(' ');
+                        }
+                        last_space = true;
+                    } else {
+                        out.push(ch);
+                        last_space = false;
+                    }
+                }
+            }
+            JSXElementChild::JSXExprContainer(container) => match &container.expr {
+                JSXExpr::Expr(expr) => match eval_literal(expr) {
+                    Some(literal) => out.push_str(&literal),
+                    None => match expr.as_ref() {
+                        Expr::Ident(ident) => out.push_str(&format!("{{{{{}}}}}", ident.sym)),
+                        _ => out.push_str(&format!("{{{{{}}}}}", index)),
+                    },
+                },
+                JSXExpr::JSXEmptyExpr(_) => {}
+            },
+            JSXElementChild::JSXElement(element) => {
+                let inner = trans_template(&meaningful_children(&element.children));
+                out.push_str(&format!("<{index}>{inner}</{index}>"));
+            }
+            JSXElementChild::JSXFragment(fragment) => {
+                out.push_str(&trans_template(&meaningful_children(&fragment.children)));
+            }
+            JSXElementChild::JSXSpreadChild(_) => {
+                out.push_str(&format!("{{{{{}}}}}", index));
+            }
+        }
+    }
+    out
+}
+
 /// Evaluate a literal expression (string, template without substitutions)
 fn eval_literal(expr: &Expr) -> Option<String> {
     match expr {
@@ -88,9 +257,12 @@ fn is_inside_t_call(ancestors: &[AncestorInfo]) -> bool {
     false
 }
 
-/// Check if we're inside an excluded component
+/// Check if we're inside an excluded component or an element with a key attribute
 fn is_inside_excluded(ancestors: &[AncestorInfo], exclude_set: &HashSet<String>) -> bool {
     for ancestor in ancestors.iter().rev() {
+        if matches!(ancestor.kind, AncestorKind::KeyedJSXElement) {
+            return true;
+        }
         if let AncestorKind::JSXElement(name) = &ancestor.kind {
             if exclude_set.contains(name.as_str()) {
                 // Also check short name
@@ -108,10 +280,33 @@ fn is_inside_excluded(ancestors: &[AncestorInfo], exclude_set: &HashSet<String>)
     false
 }
 
+/// Check if the nearest enclosing element is missing from `include_set`, when set
+fn is_outside_included(ancestors: &[AncestorInfo], include_set: Option<&HashSet<String>>) -> bool {
+    let Some(include_set) = include_set else {
+        return false;
+    };
+    let nearest = ancestors
+        .iter()
+        .rev()
+        .find_map(|ancestor| match &ancestor.kind {
+            AncestorKind::JSXElement(name) => Some(name.as_str()),
+            _ => None,
+        });
+    match nearest {
+        Some(name) => {
+            let short = name.rsplit('.').next().unwrap_or(name);
+            !include_set.contains(name) && !include_set.contains(short)
+        }
+        None => true,
+    }
+}
+
 #[derive(Debug)]
 enum AncestorKind {
     CallExpr(String),
     JSXElement(String),
+    /// Element carrying one of the configured key attributes, e.g. `i18nKey`.
+    KeyedJSXElement,
 }
 
 #[derive(Debug)]
@@ -131,6 +326,10 @@ struct HardcodedVisitor<'a> {
     range: &'a Option<Range>,
     min_length: usize,
     exclude_set: &'a HashSet<String>,
+    key_attributes: &'a [String],
+    include_set: Option<&'a HashSet<String>>,
+    group_mixed_content: bool,
+    preserve_newlines: bool,
     items: Vec<HardcodedItem>,
     ancestors: Vec<AncestorInfo>,
 }
@@ -295,9 +494,20 @@ impl<'a> HardcodedVisitor<'a> {
 
     fn visit_jsx_element(&mut self, jsx: &JSXElement) {
         let component_name = get_jsx_element_name(&jsx.opening);
+        let has_key_attribute = jsx.opening.attrs.iter().any(|attr| match attr {
+            JSXAttrOrSpread::JSXAttr(JSXAttr {
+                name: JSXAttrName::Ident(ident),
+                ..
+            }) => self.key_attributes.iter().any(|a| a == ident.sym.as_ref()),
+            _ => false,
+        });
 
         self.ancestors.push(AncestorInfo {
-            kind: AncestorKind::JSXElement(component_name),
+            kind: if has_key_attribute {
+                AncestorKind::KeyedJSXElement
+            } else {
+                AncestorKind::JSXElement(component_name)
+            },
         });
 
         // Visit attributes
@@ -311,9 +521,15 @@ impl<'a> HardcodedVisitor<'a> {
             }
         }
 
-        // Visit children
-        for child in &jsx.children {
-            self.visit_jsx_child(child);
+        // Visit children, or report them as one unit when grouping mixed content
+        let children = meaningful_children(&jsx.children);
+        if !(self.group_mixed_content
+            && is_mixed_content(&children)
+            && self.check_mixed_content(&children))
+        {
+            for child in &jsx.children {
+                self.visit_jsx_child(child);
+            }
         }
 
         self.ancestors.pop();
@@ -349,7 +565,9 @@ impl<'a> HardcodedVisitor<'a> {
             return;
         }
 
-        if is_inside_excluded(&self.ancestors, self.exclude_set) {
+        if is_inside_excluded(&self.ancestors, self.exclude_set)
+            || is_outside_included(&self.ancestors, self.include_set)
+        {
             return;
         }
 
@@ -357,7 +575,11 @@ impl<'a> HardcodedVisitor<'a> {
             return;
         }
 
-        let normalized = normalize_whitespace(&text.value);
+        let normalized = if self.preserve_newlines {
+            normalize_whitespace_per_line(&text.value)
+        } else {
+            normalize_whitespace(&text.value)
+        };
         if normalized.len() >= self.min_length {
             self.items.push(HardcodedItem {
                 lnum: start_line,
@@ -366,10 +588,47 @@ impl<'a> HardcodedVisitor<'a> {
                 end_col,
                 text: normalized,
                 kind: "jsx_text".to_string(),
+                raw_text: text.raw.to_string(),
+                segments: text_segments(&text.raw, start_line, start_col),
             });
         }
     }
 
+    /// Report mixed children as a single `jsx_mixed` item. Returns false when the
+    /// group is not reportable, so the children are checked individually instead.
+    fn check_mixed_content(&mut self, children: &[&JSXElementChild]) -> bool {
+        let (Some(first), Some(last)) = (children.first(), children.last()) else {
+            return false;
+        };
+        let span = Span::new(first.span().lo, last.span().hi);
+        let (start_line, start_col, end_line, end_col) = span_to_loc(self.cm, span);
+
+        if !in_range(start_line, end_line, self.range)
+            || is_inside_excluded(&self.ancestors, self.exclude_set)
+            || is_outside_included(&self.ancestors, self.include_set)
+            || is_inside_t_call(&self.ancestors)
+        {
+            return false;
+        }
+
+        let template = trans_template(children).trim().to_string();
+        if template.len() < self.min_length {
+            return false;
+        }
+        let raw_text = self.cm.span_to_snippet(span).unwrap_or_default();
+        self.items.push(HardcodedItem {
+            lnum: start_line,
+            col: start_col,
+            end_lnum: end_line,
+            end_col,
+            text: template,
+            kind: "jsx_mixed".to_string(),
+            segments: text_segments(&raw_text, start_line, start_col),
+            raw_text,
+        });
+        true
+    }
+
     fn check_jsx_literal(&mut self, expr: &Expr, span: swc_common::Span) {
         let (start_line, _, end_line, _) = span_to_loc(self.cm, span);
 
@@ -377,7 +636,9 @@ impl<'a> HardcodedVisitor<'a> {
             return;
         }
 
-        if is_inside_excluded(&self.ancestors, self.exclude_set) {
+        if is_inside_excluded(&self.ancestors, self.exclude_set)
+            || is_outside_included(&self.ancestors, self.include_set)
+        {
             return;
         }
 
@@ -389,6 +650,7 @@ impl<'a> HardcodedVisitor<'a> {
             let trimmed = literal.trim().to_string();
             if trimmed.len() >= self.min_length {
                 let (lnum, col, end_lnum, end_col) = span_to_loc(self.cm, expr.span());
+                let raw_text = self.cm.span_to_snippet(expr.span()).unwrap_or_default();
                 self.items.push(HardcodedItem {
                     lnum,
                     col,
@@ -396,6 +658,8 @@ impl<'a> HardcodedVisitor<'a> {
                     end_col,
                     text: literal,
                     kind: "jsx_literal".to_string(),
+                    segments: text_segments(&raw_text, lnum, col),
+                    raw_text,
                 });
             }
         }
@@ -427,12 +691,19 @@ pub fn extract(params: ExtractParams) -> Result<Value> {
     let (module, cm) = crate::scan::parser::parse_module(&params.source, &params.lang)?;
 
     let exclude_set: HashSet<String> = params.exclude_components.into_iter().collect();
+    let include_set: Option<HashSet<String>> = params
+        .include_elements
+        .map(|names| names.into_iter().collect());
 
     let mut visitor = HardcodedVisitor {
         cm: &cm,
         range: &params.range,
         min_length: params.min_length,
         exclude_set: &exclude_set,
+        key_attributes: &params.key_attributes,
+        include_set: include_set.as_ref(),
+        group_mixed_content: params.group_mixed_content,
+        preserve_newlines: params.preserve_newlines,
         items: Vec::new(),
         ancestors: Vec::new(),
     };
@@ -0,0 +1,238 @@
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Whether a value uses ICU MessageFormat arguments (`{name}`, `{n, plural, ...}`)
+/// rather than i18next `{{name}}` interpolation.
+pub fn is_icu_message(text: &str) -> bool {
+    text.contains('{') && !text.contains("{{")
+}
+
+/// Render an ICU message with sample arguments. Only a minimal subset is
+/// supported: `{name}` substitution, `plural`/`selectordinal` (exact `=N` matches,
+/// then `one` for 1, then `other`) with `#`, and `select`. Arguments missing from
+/// `args` are left as written. Returns `None` for malformed messages.
+pub fn render(message: &str, args: &HashMap<String, Value>) -> Option<String> {
+    let chars: Vec<char> = message.chars().collect();
+    let mut pos = 0;
+    let out = render_message(&chars, &mut pos, args, None)?;
+    (pos == chars.len()).then_some(out)
+}
+
+fn arg_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        _ => value.to_string(),
+    }
+}
+
+/// Render until an unmatched `}` (left unconsumed) or the end of input. `plural`
+/// is the number `#` stands for inside a plural branch.
+fn render_message(
+    chars: &[char],
+    pos: &mut usize,
+    args: &HashMap<String, Value>,
+    plural: Option<f64>,
+) -> Option<String> {
+    let mut out = String::new();
+    while *pos < chars.len() {
+        match chars[*pos] {
+            '}' => break,
+            '{' => {
+                *pos += 1;
+                out.push_str(&render_argument(chars, pos, args)?);
+            }
+            '#' if plural.is_some() => {
+                out.push_str(&plural.map(format_number)?);
+                *pos += 1;
+            }
+            c => {
+                out.push(c);
+                *pos += 1;
+            }
+        }
+    }
+    Some(out)
+}
+
+fn format_number(n: f64) -> String {
+    if n.fract() == 0.0 {
+        format!("{}", n as i64)
+    } else {
+        n.to_string()
+    }
+}
+
+/// Read up to (not including) the next `,` or `}` and trim it.
+fn read_token(chars: &[char], pos: &mut usize) -> String {
+    let start = *pos;
+    while *pos < chars.len() && chars[*pos] != ',' && chars[*pos] != '}' {
+        *pos += 1;
+    }
+    chars[start..*pos]
+        .iter()
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+fn skip_whitespace(chars: &[char], pos: &mut usize) {
+    while *pos < chars.len() && chars[*pos].is_whitespace() {
+        *pos += 1;
+    }
+}
+
+/// Render an argument whose opening `{` has been consumed, consuming its `}`.
+fn render_argument(
+    chars: &[char],
+    pos: &mut usize,
+    args: &HashMap<String, Value>,
+) -> Option<String> {
+    let name = read_token(chars, pos);
+    let arg = args.get(&name);
+    // `read_token` stops at `,`, `}` or the end of input, which is malformed.
+    match chars.get(*pos)? {
+        '}' => {
+            *pos += 1;
+            return Some(arg.map_or_else(|| format!("{{{}}}", name), arg_text));
+        }
+        _ => *pos += 1,
+    }
+
+    // `{name, type` ...
+    let kind = read_token(chars, pos);
+    match chars.get(*pos)? {
+        '}' => {
+            // `{n, number}` and friends: show the raw argument.
+            *pos += 1;
+            return Some(arg.map_or_else(|| format!("{{{}, {}}}", name, kind), arg_text));
+        }
+        _ => *pos += 1,
+    }
+
+    let number = arg.and_then(|v| match v {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    });
+    let is_plural = matches!(kind.as_str(), "plural" | "selectordinal");
+    let wanted: Vec<String> = match (kind.as_str(), arg) {
+        _ if is_plural => match number {
+            Some(n) => {
+                let mut wanted = vec![format!("={}", format_number(n))];
+                if n == 1.0 {
+                    wanted.push("one".to_string());
+                }
+                wanted.push("other".to_string());
+                wanted
+            }
+            None => vec!["other".to_string()],
+        },
+        ("select", Some(value)) => vec![arg_text(value), "other".to_string()],
+        ("select", None) => vec!["other".to_string()],
+        _ => return None,
+    };
+
+    // Options: `selector {message}` pairs until the closing `}`.
+    let mut branches: Vec<(String, String)> = Vec::new();
+    loop {
+        skip_whitespace(chars, pos);
+        match chars.get(*pos) {
+            Some('}') => {
+                *pos += 1;
+                break;
+            }
+            None => return None,
+            _ => {}
+        }
+        if is_plural && chars[*pos..].starts_with(&['o', 'f', 'f', 's', 'e', 't', ':']) {
+            // `offset:N` is accepted but not applied.
+            while *pos < chars.len() && !chars[*pos].is_whitespace() {
+                *pos += 1;
+            }
+            continue;
+        }
+        let start = *pos;
+        while *pos < chars.len() && !chars[*pos].is_whitespace() && chars[*pos] != '{' {
+            *pos += 1;
+        }
+        let selector: String = chars[start..*pos].iter().collect();
+        skip_whitespace(chars, pos);
+        if chars.get(*pos) != Some(&'{') {
+            return None;
+        }
+        *pos += 1;
+        let body = render_message(chars, pos, args, if is_plural { number } else { None })?;
+        if chars.get(*pos) != Some(&'}') {
+            return None;
+        }
+        *pos += 1;
+        branches.push((selector, body));
+    }
+
+    wanted.iter().find_map(|w| {
+        branches
+            .iter()
+            .find(|(selector, _)| selector == w)
+            .map(|(_, body)| body.clone())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(pairs: &[(&str, Value)]) -> HashMap<String, Value> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn renders_substitution_and_select() {
+        let message =
+            "{name} replied to {gender, select, female {her} male {his} other {their}} post";
+        let rendered = render(
+            message,
+            &args(&[
+                ("name", Value::from("Ana")),
+                ("gender", Value::from("female")),
+            ]),
+        );
+        assert_eq!(rendered.as_deref(), Some("Ana replied to her post"));
+    }
+
+    #[test]
+    fn exact_plural_match_wins_over_category() {
+        let message = "{n, plural, =0 {none} one {# item} other {# items}}";
+        assert_eq!(
+            render(message, &args(&[("n", Value::from(0))])).as_deref(),
+            Some("none")
+        );
+        assert_eq!(
+            render(message, &args(&[("n", Value::from(5))])).as_deref(),
+            Some("5 items")
+        );
+    }
+
+    #[test]
+    fn malformed_message_has_no_preview() {
+        assert_eq!(render("{n, plural, one {x}", &HashMap::new()), None);
+    }
+
+    #[test]
+    fn unclosed_braces_have_no_preview() {
+        let with_args = args(&[("name", Value::from("Ana")), ("n", Value::from(1))]);
+        for message in [
+            "{name",
+            "Hello {name",
+            "{n, plural, one {x}",
+            "{",
+            "{n,",
+            "{n, plural",
+        ] {
+            assert_eq!(render(message, &with_args), None, "{}", message);
+            assert_eq!(render(message, &HashMap::new()), None, "{}", message);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 pub mod doctor;
 pub mod hardcoded;
+pub mod icu;
 pub mod resolve;
 pub mod resource;
 pub mod rpc;
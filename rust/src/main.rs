@@ -1,5 +1,7 @@
 mod doctor;
 mod hardcoded;
+mod icu;
+mod pool;
 mod resolve;
 mod resource;
 mod rpc;
@@ -7,26 +9,54 @@ mod scan;
 mod util;
 
 use anyhow::Result;
+use pool::WorkerPool;
 use resource::index::IndexCache;
 use rpc::{
-    INTERNAL_ERROR, INVALID_PARAMS, INVALID_REQUEST, METHOD_NOT_FOUND, Notification, Response,
-    Transport,
+    INTERNAL_ERROR, INVALID_PARAMS, INVALID_REQUEST, METHOD_NOT_FOUND, Notification, Output,
+    Request, Response, Transport,
 };
+use serde::Deserialize;
 use serde_json::{Value, json};
 use std::process;
+use std::sync::Arc;
 
 struct Server {
     transport: Transport,
-    index_cache: IndexCache,
+    handler: Arc<Handler>,
     initialized: bool,
+    /// Set by `initialize` with `workers > 1`; requests are handled sequentially
+    /// on the server thread otherwise.
+    pool: Option<WorkerPool>,
+}
+
+/// Request handling state shared by the server thread and the worker pool. The
+/// caches guard their entries with their own mutexes.
+struct Handler {
+    output: Output,
+    index_cache: IndexCache,
+    project_scan_cache: scan::ProjectScanCache,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct InitializeParams {
+    /// Number of worker threads; more than one enables concurrent handling.
+    #[serde(default)]
+    workers: Option<usize>,
 }
 
 impl Server {
     fn new() -> Self {
-        Self {
-            transport: Transport::new(),
+        let transport = Transport::new();
+        let handler = Arc::new(Handler {
+            output: transport.output(),
             index_cache: IndexCache::new(),
+            project_scan_cache: scan::ProjectScanCache::new(),
+        });
+        Self {
+            transport,
+            handler,
             initialized: false,
+            pool: None,
         }
     }
 
@@ -66,46 +96,78 @@ impl Server {
                 continue;
             }
 
-            let id = request.id.clone();
-            // A panic inside a handler must not take down the long-running
-            // server. Catch it and downgrade it to a JSON-RPC error so the
-            // editor's i18n features keep working without a restart.
-            let response = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                self.dispatch(&request.method, request.params, id.clone())
-            }))
-            .unwrap_or_else(|_| {
-                Response::error(
-                    id.clone(),
-                    INTERNAL_ERROR,
-                    "internal error: request handler panicked".to_string(),
-                )
-            });
-            if let Err(e) = self.transport.send_response(&response) {
-                eprintln!("i18n-status-core: send error: {}", e);
+            if request.method == "initialize" {
+                let response = self.initialize(request.params, request.id);
+                let _ = self.transport.send_response(&response);
+                continue;
+            }
+
+            match &self.pool {
+                Some(pool) => pool.submit(request),
+                None => self.handler.respond(request),
             }
         }
 
         Ok(())
     }
 
-    fn dispatch(&mut self, method: &str, params: Value, id: Option<Value>) -> Response {
-        match method {
-            "initialize" => {
-                self.initialized = true;
-                Response::success(
-                    id,
-                    json!({
-                        "name": "i18n-status-core",
-                        "version": env!("CARGO_PKG_VERSION")
-                    }),
-                )
+    fn initialize(&mut self, params: Value, id: Option<Value>) -> Response {
+        let params: InitializeParams = if params.is_null() {
+            InitializeParams::default()
+        } else {
+            match serde_json::from_value(params) {
+                Ok(p) => p,
+                Err(e) => return Response::error(id, INVALID_PARAMS, e.to_string()),
             }
+        };
+        self.initialized = true;
+        if let Some(workers) = params.workers.filter(|&workers| workers > 1) {
+            if self.pool.is_none() {
+                let handler = Arc::clone(&self.handler);
+                self.pool = Some(WorkerPool::new(workers, move |request| {
+                    handler.respond(request)
+                }));
+            }
+        }
+        Response::success(
+            id,
+            json!({
+                "name": "i18n-status-core",
+                "version": env!("CARGO_PKG_VERSION")
+            }),
+        )
+    }
+}
+
+impl Handler {
+    /// Dispatch a request and send its response.
+    fn respond(&self, request: Request) {
+        let id = request.id.clone();
+        // A panic inside a handler must not take down the long-running
+        // server. Catch it and downgrade it to a JSON-RPC error so the
+        // editor's i18n features keep working without a restart.
+        let response = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            self.dispatch(&request.method, request.params, id.clone())
+        }))
+        .unwrap_or_else(|_| {
+            Response::error(
+                id.clone(),
+                INTERNAL_ERROR,
+                "internal error: request handler panicked".to_string(),
+            )
+        });
+        if let Err(e) = self.output.send_response(&response) {
+            eprintln!("i18n-status-core: send error: {}", e);
+        }
+    }
 
+    fn dispatch(&self, method: &str, params: Value, id: Option<Value>) -> Response {
+        match method {
             "shutdown" => {
                 eprintln!("i18n-status-core: shutdown requested");
                 let resp = Response::success(id, json!(null));
                 // Send response then exit
-                let _ = self.transport.send_response(&resp);
+                let _ = self.output.send_response(&resp);
                 process::exit(0);
             }
 
@@ -133,6 +195,77 @@ impl Server {
                 Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
             },
 
+            "scan/suggestNamespace" => match serde_json::from_value(params) {
+                Ok(p) => match scan::suggest_namespace(p, &self.index_cache) {
+                    Ok(result) => Response::success(id, result),
+                    Err(e) => Response::error(id, INTERNAL_ERROR, e.to_string()),
+                },
+                Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
+            },
+
+            "scan/namespacesInFile" => match serde_json::from_value(params) {
+                Ok(p) => match scan::namespaces_in_file(p) {
+                    Ok(result) => Response::success(id, result),
+                    Err(e) => Response::error(id, INTERNAL_ERROR, e.to_string()),
+                },
+                Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
+            },
+
+            "scan/keyAt" => match serde_json::from_value(params) {
+                Ok(p) => match scan::key_at(p) {
+                    Ok(result) => Response::success(id, result),
+                    Err(e) => Response::error(id, INTERNAL_ERROR, e.to_string()),
+                },
+                Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
+            },
+
+            "scan/nodeAt" => match serde_json::from_value(params) {
+                Ok(p) => match scan::node_at(p) {
+                    Ok(result) => Response::success(id, result),
+                    Err(e) => Response::error(id, INTERNAL_ERROR, e.to_string()),
+                },
+                Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
+            },
+
+            "scan/indexProject" => match serde_json::from_value(params) {
+                Ok(p) => {
+                    let output = &self.output;
+                    let notify = |method: &str, params: Value| {
+                        let notification = Notification::new(method, params);
+                        let _ = output.send_notification(&notification);
+                    };
+                    match scan::index_project(p, &self.project_scan_cache, &notify) {
+                        Ok(result) => Response::success(id, result),
+                        Err(e) => Response::error(id, INTERNAL_ERROR, e.to_string()),
+                    }
+                }
+                Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
+            },
+
+            "scan/referencesForKey" => match serde_json::from_value(params) {
+                Ok(p) => match scan::references_for_key(p, &self.project_scan_cache) {
+                    Ok(result) => Response::success(id, result),
+                    Err(e) => Response::error(id, INTERNAL_ERROR, e.to_string()),
+                },
+                Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
+            },
+
+            "scan/componentCensus" => match serde_json::from_value(params) {
+                Ok(p) => match scan::component_census(p, &self.project_scan_cache) {
+                    Ok(result) => Response::success(id, result),
+                    Err(e) => Response::error(id, INTERNAL_ERROR, e.to_string()),
+                },
+                Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
+            },
+
+            "scan/dynamicKeys" => match serde_json::from_value(params) {
+                Ok(p) => match scan::dynamic_keys(p) {
+                    Ok(result) => Response::success(id, result),
+                    Err(e) => Response::error(id, INTERNAL_ERROR, e.to_string()),
+                },
+                Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
+            },
+
             "resolve/compute" => match serde_json::from_value(params) {
                 Ok(p) => match resolve::compute(p) {
                     Ok(result) => Response::success(id, result),
@@ -141,6 +274,14 @@ impl Server {
                 Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
             },
 
+            "resolve/summary" => match serde_json::from_value(params) {
+                Ok(p) => match resolve::summary(p) {
+                    Ok(result) => Response::success(id, result),
+                    Err(e) => Response::error(id, INTERNAL_ERROR, e.to_string()),
+                },
+                Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
+            },
+
             "resource/buildIndex" => match serde_json::from_value(params) {
                 Ok(p) => match resource::index::build_index(p, &self.index_cache) {
                     Ok(result) => Response::success(id, result),
@@ -157,6 +298,14 @@ impl Server {
                 Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
             },
 
+            "resource/checkRoots" => match serde_json::from_value(params) {
+                Ok(p) => match resource::discovery::check_roots(p) {
+                    Ok(result) => Response::success(id, result),
+                    Err(e) => Response::error(id, INTERNAL_ERROR, e.to_string()),
+                },
+                Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
+            },
+
             "resource/applyChanges" => match serde_json::from_value(params) {
                 Ok(p) => match resource::index::apply_changes(p, &self.index_cache) {
                     Ok(result) => Response::success(id, result),
@@ -165,12 +314,44 @@ impl Server {
                 Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
             },
 
+            "resource/rebuildNamespace" => match serde_json::from_value(params) {
+                Ok(p) => match resource::index::rebuild_namespace(p, &self.index_cache) {
+                    Ok(result) => Response::success(id, result),
+                    Err(e) => Response::error(id, INTERNAL_ERROR, e.to_string()),
+                },
+                Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
+            },
+
+            "resource/valueOutliers" => match serde_json::from_value(params) {
+                Ok(p) => match resource::index::value_outliers(p, &self.index_cache) {
+                    Ok(result) => Response::success(id, result),
+                    Err(e) => Response::error(id, INTERNAL_ERROR, e.to_string()),
+                },
+                Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
+            },
+
+            "resource/keysUsingPlaceholder" => match serde_json::from_value(params) {
+                Ok(p) => match resource::index::keys_using_placeholder(p, &self.index_cache) {
+                    Ok(result) => Response::success(id, result),
+                    Err(e) => Response::error(id, INTERNAL_ERROR, e.to_string()),
+                },
+                Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
+            },
+
+            "doctor/missingMatrix" => match serde_json::from_value(params) {
+                Ok(p) => match doctor::missing_matrix(p, &self.index_cache) {
+                    Ok(result) => Response::success(id, result),
+                    Err(e) => Response::error(id, INTERNAL_ERROR, e.to_string()),
+                },
+                Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
+            },
+
             "doctor/diagnose" => match serde_json::from_value(params) {
                 Ok(p) => {
-                    let transport = &self.transport;
+                    let output = &self.output;
                     let notify = |method: &str, params: Value| {
                         let notification = Notification::new(method, params);
-                        let _ = transport.send_notification(&notification);
+                        let _ = output.send_notification(&notification);
                     };
                     match doctor::diagnose(p, &notify) {
                         Ok(result) => Response::success(id, result),
@@ -180,6 +361,21 @@ impl Server {
                 Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
             },
 
+            "doctor/untranslatedForLanguage" => match serde_json::from_value(params) {
+                Ok(p) => {
+                    let output = &self.output;
+                    let notify = |method: &str, params: Value| {
+                        let notification = Notification::new(method, params);
+                        let _ = output.send_notification(&notification);
+                    };
+                    match doctor::untranslated_for_language(p, &notify) {
+                        Ok(result) => Response::success(id, result),
+                        Err(e) => Response::error(id, INTERNAL_ERROR, e.to_string()),
+                    }
+                }
+                Err(e) => Response::error(id, INVALID_PARAMS, e.to_string()),
+            },
+
             "hardcoded/extract" => match serde_json::from_value(params) {
                 Ok(p) => match hardcoded::extract(p) {
                     Ok(result) => Response::success(id, result),
@@ -0,0 +1,114 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use crate::rpc::Request;
+use crate::util::SERVER_STACK_SIZE;
+
+/// Fixed set of threads handling requests concurrently, so a slow request does not
+/// hold up independent quick ones. Each request is passed to the shared handler,
+/// which is responsible for sending its response (tagged by the request id).
+pub struct WorkerPool {
+    sender: Option<Sender<Request>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl WorkerPool {
+    pub fn new<F>(size: usize, handle: F) -> Self
+    where
+        F: Fn(Request) + Send + Sync + 'static,
+    {
+        let (sender, receiver) = mpsc::channel::<Request>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let handle = Arc::new(handle);
+        let workers = (0..size.max(1))
+            .map(|index| {
+                let receiver = Arc::clone(&receiver);
+                let handle = Arc::clone(&handle);
+                // Same large stack as the server thread; see `SERVER_STACK_SIZE`.
+                thread::Builder::new()
+                    .name(format!("i18n-status-worker-{}", index))
+                    .stack_size(SERVER_STACK_SIZE)
+                    .spawn(move || {
+                        while let Some(request) = next_request(&receiver) {
+                            handle(request);
+                        }
+                    })
+                    .expect("failed to spawn worker thread")
+            })
+            .collect();
+        Self {
+            sender: Some(sender),
+            workers,
+        }
+    }
+
+    pub fn submit(&self, request: Request) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(request);
+        }
+    }
+}
+
+fn next_request(receiver: &Mutex<Receiver<Request>>) -> Option<Request> {
+    let receiver = match receiver.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    receiver.recv().ok()
+}
+
+impl Drop for WorkerPool {
+    /// Let the workers finish queued requests, then join them.
+    fn drop(&mut self) {
+        self.sender.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+    use std::time::Duration;
+
+    fn request(id: u64, method: &str) -> Request {
+        Request {
+            jsonrpc: "2.0".to_string(),
+            id: Some(json!(id)),
+            method: method.to_string(),
+            params: Value::Null,
+        }
+    }
+
+    #[test]
+    fn quick_request_completes_while_slow_one_is_in_flight() {
+        let (done_tx, done_rx) = mpsc::channel::<Option<Value>>();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let release_rx = Mutex::new(release_rx);
+        let done_tx = Mutex::new(done_tx);
+
+        let pool = WorkerPool::new(2, move |request| {
+            if request.method == "test/slow" {
+                let _ = release_rx.lock().unwrap().recv();
+            }
+            let _ = done_tx.lock().unwrap().send(request.id);
+        });
+
+        pool.submit(request(1, "test/slow"));
+        pool.submit(request(2, "scan/keyAt"));
+
+        let first = done_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("quick request should finish while the slow one is blocked");
+        assert_eq!(first, Some(json!(2)));
+
+        release_tx.send(()).unwrap();
+        let second = done_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("slow request should finish once released");
+        assert_eq!(second, Some(json!(1)));
+    }
+}
@@ -1,11 +1,15 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use crate::util::extract_placeholders;
+use crate::icu;
+use crate::util::{
+    extract_placeholders, extract_placeholders_multiset, find_key_ignoring_namespace_case,
+    matches_key_pattern,
+};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct ComputeParams {
     pub items: Vec<ScanItemInput>,
     pub primary_lang: String,
@@ -14,6 +18,71 @@ pub struct ComputeParams {
     pub index: HashMap<String, HashMap<String, ResourceItemInput>>,
     #[serde(default)]
     pub current_lang: Option<String>,
+    /// Key patterns whose values must be identical across languages (brand names,
+    /// legal strings). A differing translation is reported as a violation.
+    #[serde(default)]
+    pub must_match: Vec<String>,
+    /// Glyphs used for the `status` field. `reason` stays stable regardless.
+    #[serde(default)]
+    pub symbols: Option<StatusSymbols>,
+    /// Languages that get a full entry in `hover.values`. Others still count towards
+    /// missing/mismatch/localized aggregates. The primary language is always included.
+    /// `None` includes every language.
+    #[serde(default)]
+    pub display_langs: Option<Vec<String>>,
+    /// Sample arguments for rendering ICU values. When set, each ICU value in the
+    /// hover gets a `preview` rendered with these arguments.
+    #[serde(default)]
+    pub icu_preview: Option<HashMap<String, Value>>,
+    /// Separator between namespace and key path in canonical keys. Defaults to `:`.
+    #[serde(default)]
+    pub ns_separator: Option<String>,
+    /// Also emit `hover_markdown`, the hover rendered server-side.
+    #[serde(default)]
+    pub render_markdown: bool,
+    /// Match namespaces case-insensitively when a key is not found as written, and
+    /// flag the hover with a `namespace_case_mismatch` warning.
+    #[serde(default)]
+    pub case_insensitive_namespaces: bool,
+    /// Languages that must be complete, e.g. for a release gate. A missing value in
+    /// one of them is reported as `required_missing` with the missing-primary glyph
+    /// instead of the soft `fallback` status.
+    #[serde(default)]
+    pub require_complete: Vec<String>,
+    /// Attach `namespace_coverage` to each hover: how much of the item's namespace
+    /// is translated per language.
+    #[serde(default)]
+    pub namespace_stats: bool,
+    /// Compare how many times each placeholder is used, not just which ones are,
+    /// so a repeated or dropped `{{name}}` is a `placeholder_mismatch`.
+    #[serde(default)]
+    pub strict_placeholders: bool,
+    /// Languages to try, in order, when a language lacks a key, e.g.
+    /// `{ "en-US": ["en"] }`. A value found this way counts as present.
+    #[serde(default)]
+    pub fallback_chains: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct StatusSymbols {
+    pub synced: String,
+    pub localized: String,
+    pub missing_primary: String,
+    pub fallback: String,
+    pub mismatch: String,
+}
+
+impl Default for StatusSymbols {
+    fn default() -> Self {
+        Self {
+            synced: "=".to_string(),
+            localized: "\u{2260}".to_string(),       // ≠
+            missing_primary: "\u{00d7}".to_string(), // ×
+            fallback: "?".to_string(),
+            mismatch: "!".to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -21,7 +90,6 @@ pub struct ScanItemInput {
     pub key: String,
     pub raw: String,
     pub namespace: String,
-    #[allow(dead_code)]
     #[serde(default)]
     pub fallback: bool,
 }
@@ -41,6 +109,8 @@ pub struct ResolvedItem {
     pub text: String,
     pub status: String,
     pub hover: HoverInfo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hover_markdown: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -57,12 +127,81 @@ pub struct HoverInfo {
     pub focus_lang: String,
     pub lang_order: Vec<String>,
     pub values: HashMap<String, HoverValue>,
+    /// The key was resolved via the fallback namespace rather than an explicit one.
+    pub used_fallback_namespace: bool,
+    /// Set when a fallback-resolved key also exists under a more specific namespace,
+    /// which usually means the namespace was forgotten at the call site.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    /// Non-status problem with the key, e.g. `namespace_case_mismatch`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub missing_langs: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub localized_langs: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mismatch_langs: Option<Vec<String>>,
+    /// Per-language completeness of the key's namespace, with `namespace_stats`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace_coverage: Option<BTreeMap<String, NamespaceCoverage>>,
+}
+
+/// Primary-language keys of a namespace that have a value in one language.
+#[derive(Debug, Clone, Serialize)]
+pub struct NamespaceCoverage {
+    pub translated: usize,
+    pub total: usize,
+    pub ratio: f64,
+}
+
+/// Coverage of every namespace for each non-primary language, computed once from the
+/// index: namespace -> lang -> coverage.
+fn namespace_coverage(
+    params: &ComputeParams,
+    ns_separator: &str,
+) -> HashMap<String, BTreeMap<String, NamespaceCoverage>> {
+    let has_value = |lang: &str, key: &str| {
+        params
+            .index
+            .get(lang)
+            .and_then(|m| m.get(key))
+            .is_some_and(|entry| entry.value.as_deref().is_some_and(|v| !v.is_empty()))
+    };
+    let mut keys_by_namespace: HashMap<&str, Vec<&str>> = HashMap::new();
+    for key in params
+        .index
+        .get(&params.primary_lang)
+        .into_iter()
+        .flat_map(|m| m.keys())
+    {
+        if !has_value(&params.primary_lang, key) {
+            continue;
+        }
+        if let Some((ns, _)) = key.split_once(ns_separator) {
+            keys_by_namespace.entry(ns).or_default().push(key);
+        }
+    }
+    keys_by_namespace
+        .into_iter()
+        .map(|(ns, keys)| {
+            let per_lang = params
+                .languages
+                .iter()
+                .filter(|lang| **lang != params.primary_lang)
+                .map(|lang| {
+                    let translated = keys.iter().filter(|key| has_value(lang, key)).count();
+                    let coverage = NamespaceCoverage {
+                        translated,
+                        total: keys.len(),
+                        ratio: translated as f64 / keys.len() as f64,
+                    };
+                    (lang.clone(), coverage)
+                })
+                .collect();
+            (ns.to_string(), per_lang)
+        })
+        .collect()
 }
 
 #[derive(Debug, Serialize)]
@@ -70,10 +209,124 @@ pub struct HoverValue {
     pub value: Option<String>,
     pub file: Option<String>,
     pub missing: bool,
+    /// `value` rendered with the `icu_preview` sample arguments.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview: Option<String>,
+    /// `value` spans several lines, so inline previews should truncate it.
+    pub multiline: bool,
+    /// `value` contains an HTML/JSX-like tag, so previews should escape it.
+    pub has_markup: bool,
+    /// Language from `fallback_chains` that supplied `value`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback_lang: Option<String>,
+}
+
+/// Entry of `key` in `lang`, or else in the first language of its fallback chain
+/// that has the key, with the name of that fallback language.
+fn lookup_entry<'a>(
+    params: &'a ComputeParams,
+    lang: &str,
+    key: &str,
+    raw: &str,
+    ns_separator: &str,
+) -> (Option<&'a ResourceItemInput>, Option<&'a str>) {
+    let entry = |lang: &str| params.index.get(lang).and_then(|m| m.get(key));
+    let own = entry(lang);
+    if !is_missing(own.and_then(|e| e.value.as_deref()), key, raw, ns_separator) {
+        return (own, None);
+    }
+    params
+        .fallback_chains
+        .get(lang)
+        .into_iter()
+        .flatten()
+        .find_map(|fallback| {
+            let found = entry(fallback)?;
+            (!is_missing(found.value.as_deref(), key, raw, ns_separator))
+                .then_some((Some(found), Some(fallback.as_str())))
+        })
+        .unwrap_or((own, None))
+}
+
+fn is_multiline(value: Option<&str>) -> bool {
+    value.is_some_and(|v| v.contains('\n'))
+}
+
+/// A `<tag`, `</tag` or `<tag/>` opening with an ASCII letter and closed by `>`.
+fn has_markup(value: Option<&str>) -> bool {
+    let Some(value) = value else {
+        return false;
+    };
+    value.match_indices('<').any(|(i, _)| {
+        let rest = &value[i + 1..];
+        let name = rest.strip_prefix('/').unwrap_or(rest);
+        name.starts_with(|c: char| c.is_ascii_alphabetic()) && name.contains('>')
+    })
+}
+
+/// Canonical keys in other namespaces of `lang_index` sharing the key path of a
+/// fallback-resolved `key`.
+fn other_namespace_keys(
+    lang_index: Option<&HashMap<String, ResourceItemInput>>,
+    key: &str,
+    ns_separator: &str,
+) -> Vec<String> {
+    let Some((ns, path)) = key.split_once(ns_separator) else {
+        return Vec::new();
+    };
+    let mut keys: Vec<String> = lang_index
+        .into_iter()
+        .flat_map(|m| m.keys())
+        .filter(|candidate| {
+            candidate
+                .split_once(ns_separator)
+                .is_some_and(|(other_ns, other_path)| other_ns != ns && other_path == path)
+        })
+        .cloned()
+        .collect();
+    keys.sort();
+    keys
+}
+
+/// Render an ICU value with the sample arguments, if any were given.
+fn icu_preview(value: Option<&str>, args: Option<&HashMap<String, Value>>) -> Option<String> {
+    let value = value.filter(|v| icu::is_icu_message(v))?;
+    icu::render(value, args?)
+}
+
+/// Markdown rendering of a hover: key and status, then one line per language in
+/// `lang_order` with its value (or a missing marker) and source file.
+fn render_hover_markdown(hover: &HoverInfo) -> String {
+    let mut out = format!("**`{}`**", hover.key);
+    if let Some(status) = &hover.status {
+        out.push_str(&format!(" `{}`", status));
+    }
+    if let Some(reason) = &hover.reason {
+        out.push_str(&format!(" {}", reason));
+    }
+    out.push_str("\n\n");
+    for lang in &hover.lang_order {
+        let Some(entry) = hover.values.get(lang) else {
+            continue;
+        };
+        let value = match (&entry.value, entry.missing) {
+            (Some(value), false) => value.clone(),
+            _ => "_(missing)_".to_string(),
+        };
+        out.push_str(&format!("- **{}**: {}", lang, value));
+        if let Some(file) = &entry.file {
+            out.push_str(&format!(" — `{}`", file));
+        }
+        out.push('\n');
+    }
+    if let Some(hint) = &hover.hint {
+        out.push_str(&format!("\n{}\n", hint));
+    }
+    out
 }
 
 /// Check if a value is considered "missing"
-fn is_missing(value: Option<&str>, key: &str, raw: &str) -> bool {
+fn is_missing(value: Option<&str>, key: &str, raw: &str, ns_separator: &str) -> bool {
     match value {
         None => true,
         Some(v) => {
@@ -86,8 +339,8 @@ fn is_missing(value: Option<&str>, key: &str, raw: &str) -> bool {
             if v == key {
                 return true;
             }
-            // Check against key path (after namespace colon)
-            if let Some(key_path) = key.split_once(':').map(|(_, path)| path) {
+            // Check against key path (after the namespace separator)
+            if let Some(key_path) = key.split_once(ns_separator).map(|(_, path)| path) {
                 if v == key_path {
                     return true;
                 }
@@ -98,10 +351,47 @@ fn is_missing(value: Option<&str>, key: &str, raw: &str) -> bool {
 }
 
 pub fn compute(params: ComputeParams) -> Result<Value> {
+    let resolved = resolve_items(&params);
+    Ok(serde_json::json!({ "resolved": resolved }))
+}
+
+/// Number of items per status, for the statusline.
+#[derive(Debug, Default, Serialize)]
+pub struct StatusSummary {
+    pub synced: usize,
+    pub localized: usize,
+    pub missing: usize,
+    pub fallback: usize,
+    pub mismatch: usize,
+}
+
+/// Aggregate counts of `compute`, without per-item detail. Counted by reason, so
+/// custom `symbols` don't affect the result.
+pub fn summary(params: ComputeParams) -> Result<Value> {
+    let mut summary = StatusSummary::default();
+    for item in resolve_items(&params) {
+        match item.hover.reason.as_deref() {
+            None => summary.synced += 1,
+            Some("missing_primary" | "required_missing") => summary.missing += 1,
+            Some("fallback") => summary.fallback += 1,
+            Some("placeholder_mismatch" | "must_match_violation") => summary.mismatch += 1,
+            Some(_) => summary.localized += 1,
+        }
+    }
+    Ok(serde_json::to_value(summary)?)
+}
+
+fn resolve_items(params: &ComputeParams) -> Vec<ResolvedItem> {
     let mut resolved = Vec::new();
 
+    let symbols = params.symbols.clone().unwrap_or_default();
     let primary = &params.primary_lang;
     let display_lang = params.current_lang.as_deref().unwrap_or(primary);
+    let ns_separator = params
+        .ns_separator
+        .as_deref()
+        .filter(|sep| !sep.is_empty())
+        .unwrap_or(":");
 
     // Build lang_order: primary first, then others
     let mut lang_order = vec![primary.clone()];
@@ -116,24 +406,52 @@ pub fn compute(params: ComputeParams) -> Result<Value> {
         .iter()
         .filter(|l| l.as_str() != primary)
         .collect();
+    let coverage_by_namespace = if params.namespace_stats {
+        namespace_coverage(params, ns_separator)
+    } else {
+        HashMap::new()
+    };
+    let wants_value = |lang: &str| match &params.display_langs {
+        Some(langs) => lang == primary.as_str() || langs.iter().any(|l| l == lang),
+        None => true,
+    };
 
     for item in &params.items {
         let key = &item.key;
 
         // Get primary value
-        let primary_entry = params.index.get(primary).and_then(|m| m.get(key.as_str()));
+        // With case-insensitive namespaces, `Common:title` reads `common:title`.
+        let case_key = if params.case_insensitive_namespaces
+            && !params.index.values().any(|m| m.contains_key(key))
+        {
+            params
+                .index
+                .get(primary)
+                .and_then(|m| find_key_ignoring_namespace_case(m, key))
+                .or_else(|| {
+                    params
+                        .index
+                        .values()
+                        .find_map(|m| find_key_ignoring_namespace_case(m, key))
+                })
+                .cloned()
+        } else {
+            None
+        };
+        let lookup_key = case_key.as_deref().unwrap_or(key);
+
+        let (primary_entry, primary_fallback) =
+            lookup_entry(params, primary, lookup_key, &item.raw, ns_separator);
         let primary_value = primary_entry.and_then(|e| e.value.as_deref());
 
         // Get display value
-        let display_entry = params
-            .index
-            .get(display_lang)
-            .and_then(|m| m.get(key.as_str()));
+        let (display_entry, _) =
+            lookup_entry(params, display_lang, lookup_key, &item.raw, ns_separator);
         let display_value = display_entry
             .and_then(|e| e.value.as_deref())
             .or(primary_value);
 
-        let missing_primary = is_missing(primary_value, key, &item.raw);
+        let missing_primary = is_missing(primary_value, key, &item.raw, ns_separator);
 
         // Build hover values and compute status
         let mut values = HashMap::new();
@@ -143,11 +461,17 @@ pub fn compute(params: ComputeParams) -> Result<Value> {
                 value: primary_value.map(|s| s.to_string()),
                 file: primary_entry.and_then(|e| e.file.clone()),
                 missing: missing_primary,
+                preview: icu_preview(primary_value, params.icu_preview.as_ref()),
+                multiline: is_multiline(primary_value),
+                has_markup: has_markup(primary_value),
+                fallback_lang: primary_fallback.map(|l| l.to_string()),
             },
         );
 
         let mut any_missing = false;
+        let mut any_required_missing = false;
         let mut any_localized = false;
+        let mut any_case_only = false;
         let mut missing_langs = Vec::new();
         let mut localized_langs = Vec::new();
         let mut mismatch_langs = Vec::new();
@@ -156,29 +480,37 @@ pub fn compute(params: ComputeParams) -> Result<Value> {
         let mut compare_values: Vec<(&str, &str)> = Vec::new(); // (lang, value)
 
         for lang in &compare_langs {
-            let entry = params
-                .index
-                .get(lang.as_str())
-                .and_then(|m| m.get(key.as_str()));
+            let (entry, fallback_lang) =
+                lookup_entry(params, lang, lookup_key, &item.raw, ns_separator);
             let value = entry.and_then(|e| e.value.as_deref());
-            let missing = is_missing(value, key, &item.raw);
+            let missing = is_missing(value, key, &item.raw, ns_separator);
 
-            values.insert(
-                lang.to_string(),
-                HoverValue {
-                    value: value.map(|s| s.to_string()),
-                    file: entry.and_then(|e| e.file.clone()),
-                    missing,
-                },
-            );
+            if wants_value(lang) {
+                values.insert(
+                    lang.to_string(),
+                    HoverValue {
+                        value: value.map(|s| s.to_string()),
+                        file: entry.and_then(|e| e.file.clone()),
+                        missing,
+                        preview: icu_preview(value, params.icu_preview.as_ref()),
+                        multiline: is_multiline(value),
+                        has_markup: has_markup(value),
+                        fallback_lang: fallback_lang.map(|l| l.to_string()),
+                    },
+                );
+            }
 
             if missing {
                 any_missing = true;
+                any_required_missing |= params.require_complete.iter().any(|l| l == *lang);
                 missing_langs.push(lang.to_string());
             } else if let Some(pv) = primary_value {
                 if let Some(v) = value {
                     if v != pv {
                         any_localized = true;
+                        // "Submit" vs "submit" is more likely an accidental edit
+                        // than a translation.
+                        any_case_only |= v.to_lowercase() == pv.to_lowercase();
                         localized_langs.push(lang.to_string());
                     }
                     compare_values.push((lang, v));
@@ -191,14 +523,22 @@ pub fn compute(params: ComputeParams) -> Result<Value> {
         let reason;
 
         if missing_primary {
-            status = "\u{00d7}"; // ×
+            status = symbols.missing_primary.as_str();
             reason = Some("missing_primary");
+        } else if any_required_missing {
+            status = symbols.missing_primary.as_str();
+            reason = Some("required_missing");
         } else {
             // Check placeholder mismatches
-            let base_placeholders = extract_placeholders(primary_value.unwrap_or(""));
+            let extract = if params.strict_placeholders {
+                extract_placeholders_multiset
+            } else {
+                extract_placeholders
+            };
+            let base_placeholders = extract(primary_value.unwrap_or(""));
             let mut has_mismatch = false;
             for (lang, value) in &compare_values {
-                let current_placeholders = extract_placeholders(value);
+                let current_placeholders = extract(value);
                 if !placeholder_equal_vecs(&base_placeholders, &current_placeholders) {
                     has_mismatch = true;
                     mismatch_langs.push(lang.to_string());
@@ -206,20 +546,38 @@ pub fn compute(params: ComputeParams) -> Result<Value> {
             }
 
             if has_mismatch {
-                status = "!";
+                status = symbols.mismatch.as_str();
                 reason = Some("placeholder_mismatch");
+            } else if any_localized && matches_key_pattern(key, &params.must_match) {
+                status = symbols.mismatch.as_str();
+                reason = Some("must_match_violation");
             } else if any_missing {
-                status = "?";
+                status = symbols.fallback.as_str();
                 reason = Some("fallback");
+            } else if any_case_only {
+                status = symbols.localized.as_str();
+                reason = Some("case_only_difference");
             } else if any_localized {
-                status = "\u{2260}"; // ≠
+                status = symbols.localized.as_str();
                 reason = Some("localized");
             } else {
-                status = "=";
+                status = symbols.synced.as_str();
                 reason = None;
             }
         };
 
+        let hint = if item.fallback {
+            let alternatives = other_namespace_keys(params.index.get(primary), key, ns_separator);
+            (!alternatives.is_empty()).then(|| {
+                format!(
+                    "Resolved via the fallback namespace, but the key also exists as {}",
+                    alternatives.join(", ")
+                )
+            })
+        } else {
+            None
+        };
+
         let hover = HoverInfo {
             key: key.clone(),
             namespace: Some(item.namespace.clone()),
@@ -230,6 +588,11 @@ pub fn compute(params: ComputeParams) -> Result<Value> {
             focus_lang: display_lang.to_string(),
             lang_order: lang_order.clone(),
             values,
+            used_fallback_namespace: item.fallback,
+            hint,
+            warning: case_key
+                .as_ref()
+                .map(|_| "namespace_case_mismatch".to_string()),
             missing_langs: if missing_langs.is_empty() {
                 None
             } else {
@@ -245,17 +608,25 @@ pub fn compute(params: ComputeParams) -> Result<Value> {
             } else {
                 Some(mismatch_langs)
             },
+            namespace_coverage: lookup_key
+                .split_once(ns_separator)
+                .and_then(|(ns, _)| coverage_by_namespace.get(ns))
+                .cloned(),
         };
 
+        let hover_markdown = params
+            .render_markdown
+            .then(|| render_hover_markdown(&hover));
         resolved.push(ResolvedItem {
             key: key.clone(),
             text: display_value.unwrap_or("").to_string(),
             status: status.to_string(),
             hover,
+            hover_markdown,
         });
     }
 
-    Ok(serde_json::json!({ "resolved": resolved }))
+    resolved
 }
 
 fn placeholder_equal_vecs(a: &[String], b: &[String]) -> bool {
@@ -3,11 +3,28 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::path::{Path, PathBuf};
 
+use super::index::RootConfig;
+use crate::util::path_to_string;
+
 #[derive(Debug, Deserialize)]
 pub struct ResolveRootsParams {
     pub start_dir: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CheckRootsParams {
+    pub roots: Vec<RootConfig>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RootCheck {
+    pub path: String,
+    pub kind: String,
+    pub exists: bool,
+    pub readable: bool,
+    pub file_count: usize,
+}
+
 #[derive(Debug, Serialize)]
 pub struct RootInfo {
     pub kind: String,
@@ -37,12 +54,12 @@ pub fn resolve_roots(params: ResolveRootsParams) -> Result<Value> {
     if let Some(path) = find_up(&start, "public/locales") {
         roots.push(RootInfo {
             kind: "i18next".to_string(),
-            path: path.to_string_lossy().to_string(),
+            path: path_to_string(&path),
         });
     } else if let Some(path) = find_up(&start, "locales") {
         roots.push(RootInfo {
             kind: "i18next".to_string(),
-            path: path.to_string_lossy().to_string(),
+            path: path_to_string(&path),
         });
     }
 
@@ -50,9 +67,62 @@ pub fn resolve_roots(params: ResolveRootsParams) -> Result<Value> {
     if let Some(path) = find_up(&start, "messages") {
         roots.push(RootInfo {
             kind: "next-intl".to_string(),
-            path: path.to_string_lossy().to_string(),
+            path: path_to_string(&path),
         });
     }
 
     Ok(serde_json::to_value(serde_json::json!({ "roots": roots }))?)
 }
+
+/// Count resource files directly under `dir` and one level below it, which covers
+/// both `{lang}.json` and `{lang}/{ns}.json` layouts (and `{lang}.po`,
+/// `{lang}/{ns}.ftl`) without walking the whole tree.
+fn count_resource_files(dir: &Path) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut count = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if let Ok(children) = std::fs::read_dir(&path) {
+                count += children
+                    .flatten()
+                    .filter(|child| is_readable_resource(&child.path()))
+                    .count();
+            }
+        } else if is_readable_resource(&path) {
+            count += 1;
+        }
+    }
+    count
+}
+
+fn is_readable_resource(path: &Path) -> bool {
+    path.is_file() && super::io::is_readable_file(path)
+}
+
+pub fn check_roots(params: CheckRootsParams) -> Result<Value> {
+    let checks: Vec<RootCheck> = params
+        .roots
+        .iter()
+        .map(|root| {
+            let path = Path::new(&root.path);
+            let exists = path.is_dir();
+            let readable = exists && std::fs::read_dir(path).is_ok();
+            RootCheck {
+                path: root.path.clone(),
+                kind: root.kind.clone(),
+                exists,
+                readable,
+                file_count: if readable {
+                    count_resource_files(path)
+                } else {
+                    0
+                },
+            }
+        })
+        .collect();
+
+    Ok(serde_json::json!({ "roots": checks }))
+}
@@ -0,0 +1,124 @@
+//! Reader for Fluent `.ftl` resources.
+//!
+//! Each message becomes a flat key: `id` for its value and `id.attr` for each
+//! attribute, with the line the entry starts on. Multiline values are joined with
+//! `\n` after dropping their indentation. Comments, terms (`-brand`) and junk are
+//! skipped, as Fluent itself recovers from junk without failing the resource.
+
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// The flat `{ key: value }` object of a resource and the line of each key.
+pub fn parse(source: &str) -> (Value, HashMap<String, u32>) {
+    let mut messages = Map::new();
+    let mut lines = HashMap::new();
+    // Key, first line and collected lines of the value or attribute being read.
+    let mut current: Option<(String, u32, Vec<String>)> = None;
+    // Inside a message (or term) whose indented lines continue it; `None` in junk.
+    let mut message_id: Option<String> = None;
+
+    let mut flush = |current: &mut Option<(String, u32, Vec<String>)>| {
+        if let Some((key, lnum, parts)) = current.take() {
+            let value = parts.join("\n").trim().to_string();
+            if !value.is_empty() && !key.starts_with('-') {
+                lines.insert(key.clone(), lnum);
+                messages.insert(key, Value::String(value));
+            }
+        }
+    };
+
+    for (index, line) in source.lines().enumerate() {
+        let lnum = index as u32;
+        if line.trim().is_empty() {
+            // Blank lines may separate lines of a multiline value.
+            continue;
+        }
+        // Indented lines continue the entry, as does the `}` closing a multiline
+        // placeable.
+        if line.starts_with([' ', '\t']) || (line.starts_with('}') && message_id.is_some()) {
+            let Some(id) = &message_id else {
+                continue;
+            };
+            let trimmed = line.trim();
+            if let Some((name, value)) = trimmed
+                .strip_prefix('.')
+                .and_then(|rest| rest.split_once('='))
+                .filter(|(name, _)| is_identifier(name.trim()))
+            {
+                flush(&mut current);
+                let key = format!("{}.{}", id, name.trim());
+                current = Some((key, lnum, vec![value.trim().to_string()]));
+            } else if let Some((_, _, parts)) = &mut current {
+                parts.push(trimmed.to_string());
+            }
+            continue;
+        }
+
+        flush(&mut current);
+        message_id = None;
+        if line.starts_with('#') {
+            continue;
+        }
+        let Some((id, value)) = line
+            .split_once('=')
+            .map(|(id, value)| (id.trim_end(), value))
+            .filter(|(id, _)| is_identifier(id.strip_prefix('-').unwrap_or(id)))
+        else {
+            // Junk: skipped up to the next entry.
+            continue;
+        };
+        message_id = Some(id.to_string());
+        current = Some((id.to_string(), lnum, vec![value.trim().to_string()]));
+    }
+    flush(&mut current);
+
+    (Value::Object(messages), lines)
+}
+
+/// `[a-zA-Z][a-zA-Z0-9_-]*`
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_messages_attributes_and_multiline_values() {
+        let source = "\
+# Comment
+-brand = Firefox
+welcome = Welcome, { $user }!
+login =
+    .title = Log in
+    .aria-label = Log in to { -brand }
+about =
+    First line
+    Second line
+this is junk
+    still junk
+count = { $n ->
+    [one] One item
+   *[other] { $n } items
+}
+";
+        let (value, lines) = parse(source);
+
+        assert_eq!(
+            value,
+            json!({
+                "welcome": "Welcome, { $user }!",
+                "login.title": "Log in",
+                "login.aria-label": "Log in to { -brand }",
+                "about": "First line\nSecond line",
+                "count": "{ $n ->\n[one] One item\n*[other] { $n } items\n}"
+            })
+        );
+        assert_eq!(lines.get("login.title"), Some(&4));
+        assert_eq!(lines.get("about"), Some(&6));
+    }
+}
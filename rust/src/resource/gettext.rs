@@ -0,0 +1,256 @@
+//! Reader for gettext `.po` catalogs.
+//!
+//! Entries become `{ namespace: { msgid: msgstr } }`, with `msgctxt` as the
+//! namespace (or `DEFAULT_NAMESPACE` without one), so a catalog is indexed like a
+//! next-intl root file. Plural entries map each `msgstr[n]` to `{msgid}_{category}`,
+//! the suffixes i18next uses, with categories ordered as gettext orders the forms.
+
+use anyhow::{Result, bail};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Namespace of entries without `msgctxt`; gettext's default text domain.
+const DEFAULT_NAMESPACE: &str = "messages";
+
+/// A parsed catalog: the namespace object, and the line of each entry's `msgstr`
+/// keyed by `{namespace}.{key}`.
+pub fn parse(source: &str, lang: &str) -> Result<(Value, HashMap<String, u32>)> {
+    let entries = parse_entries(source)?;
+    let nplurals = entries
+        .iter()
+        .find(|entry| entry.id.is_empty() && entry.context.is_none())
+        .and_then(|header| header.strs.first())
+        .and_then(|(_, header)| header_nplurals(header));
+
+    let mut root = Map::new();
+    let mut lines = HashMap::new();
+    for entry in &entries {
+        if entry.id.is_empty() {
+            continue;
+        }
+        let ns = entry.context.as_deref().unwrap_or(DEFAULT_NAMESPACE);
+        let table = root
+            .entry(ns.to_string())
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .expect("namespace entries are objects");
+        if entry.plural_id.is_some() {
+            let categories = plural_categories(lang, nplurals.unwrap_or(entry.strs.len()));
+            for (index, (lnum, value)) in entry.strs.iter().enumerate() {
+                let Some(category) = categories.get(index) else {
+                    continue;
+                };
+                let key = format!("{}_{}", entry.id, category);
+                lines.insert(format!("{}.{}", ns, key), *lnum);
+                table.insert(key, Value::String(value.clone()));
+            }
+        } else if let Some((lnum, value)) = entry.strs.first() {
+            lines.insert(format!("{}.{}", ns, entry.id), *lnum);
+            table.insert(entry.id.clone(), Value::String(value.clone()));
+        }
+    }
+    Ok((Value::Object(root), lines))
+}
+
+#[derive(Debug, Default)]
+struct Entry {
+    context: Option<String>,
+    id: String,
+    plural_id: Option<String>,
+    /// `msgstr` (or `msgstr[n]` in order) with the line it starts on.
+    strs: Vec<(u32, String)>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Context,
+    Id,
+    PluralId,
+    Str,
+}
+
+fn parse_entries(source: &str) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    let mut current = Entry::default();
+    let mut has_id = false;
+    let mut field: Option<Field> = None;
+
+    for (index, line) in source.lines().enumerate() {
+        let lnum = index as u32;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('"') {
+            let text = unquote(line, lnum)?;
+            match field {
+                Some(Field::Context) => current
+                    .context
+                    .get_or_insert_with(String::new)
+                    .push_str(&text),
+                Some(Field::Id) => current.id.push_str(&text),
+                Some(Field::PluralId) => current
+                    .plural_id
+                    .get_or_insert_with(String::new)
+                    .push_str(&text),
+                Some(Field::Str) => {
+                    if let Some((_, value)) = current.strs.last_mut() {
+                        value.push_str(&text);
+                    }
+                }
+                None => bail!("line {}: string without a keyword", lnum + 1),
+            }
+            continue;
+        }
+        let (keyword, rest) = line
+            .split_once(|c: char| c.is_whitespace())
+            .map(|(keyword, rest)| (keyword, rest.trim_start()))
+            .unwrap_or((line, ""));
+        let text = unquote(rest, lnum)?;
+        // A new msgctxt/msgid after a msgstr starts the next entry.
+        if matches!(keyword, "msgctxt" | "msgid") && field == Some(Field::Str) {
+            entries.push(std::mem::take(&mut current));
+            has_id = false;
+        }
+        match keyword {
+            "msgctxt" => {
+                current.context = Some(text);
+                field = Some(Field::Context);
+            }
+            "msgid" => {
+                current.id = text;
+                has_id = true;
+                field = Some(Field::Id);
+            }
+            "msgid_plural" if has_id => {
+                current.plural_id = Some(text);
+                field = Some(Field::PluralId);
+            }
+            _ if keyword == "msgstr" || keyword.starts_with("msgstr[") => {
+                if !has_id {
+                    bail!("line {}: msgstr without msgid", lnum + 1);
+                }
+                current.strs.push((lnum, text));
+                field = Some(Field::Str);
+            }
+            _ => bail!("line {}: unexpected '{}'", lnum + 1, keyword),
+        }
+    }
+    match field {
+        Some(Field::Str) => entries.push(current),
+        Some(_) => bail!("unexpected end of file: entry without msgstr"),
+        None => {}
+    }
+    Ok(entries)
+}
+
+/// Content of a `"..."` string with C escapes resolved.
+fn unquote(text: &str, lnum: u32) -> Result<String> {
+    let Some(inner) = text
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    else {
+        bail!("line {}: expected a quoted string", lnum + 1);
+    };
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some(other @ ('"' | '\\')) => out.push(other),
+            _ => bail!("line {}: invalid escape sequence", lnum + 1),
+        }
+    }
+    Ok(out)
+}
+
+/// `nplurals` of a header's `Plural-Forms: nplurals=N; plural=...;` line.
+fn header_nplurals(header: &str) -> Option<usize> {
+    let forms = header
+        .lines()
+        .find_map(|line| line.strip_prefix("Plural-Forms:"))?;
+    forms.split(';').find_map(|part| {
+        part.trim()
+            .strip_prefix("nplurals=")
+            .and_then(|n| n.trim().parse().ok())
+    })
+}
+
+/// CLDR categories of gettext's plural forms, in `msgstr[n]` order.
+fn plural_categories(lang: &str, nplurals: usize) -> &'static [&'static str] {
+    let base = lang.split(['-', '_']).next().unwrap_or(lang);
+    match nplurals {
+        0 | 1 => &["other"],
+        2 => &["one", "other"],
+        3 => match base {
+            "lv" => &["zero", "one", "other"],
+            "ru" | "uk" | "be" | "sr" | "hr" | "bs" | "pl" => &["one", "few", "many"],
+            _ => &["one", "few", "other"],
+        },
+        4 => &["one", "two", "few", "other"],
+        5 => &["one", "two", "few", "many", "other"],
+        _ => &["zero", "one", "two", "few", "many", "other"],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_context_plurals_and_continuations() {
+        let source = r#"
+msgid ""
+msgstr ""
+"Language: ru\n"
+"Plural-Forms: nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : 1);\n"
+
+# Greeting
+msgid "Hello"
+msgstr "Привет"
+
+msgctxt "menu"
+msgid "Open"
+msgstr ""
+"Откры"
+"ть"
+
+msgid "file"
+msgid_plural "files"
+msgstr[0] "файл"
+msgstr[1] "файла"
+msgstr[2] "файлов"
+"#;
+        let (value, lines) = parse(source, "ru").expect("po should parse");
+
+        assert_eq!(
+            value,
+            json!({
+                "messages": {
+                    "Hello": "Привет",
+                    "file_one": "файл",
+                    "file_few": "файла",
+                    "file_many": "файлов"
+                },
+                "menu": { "Open": "Открыть" }
+            })
+        );
+        assert_eq!(lines.get("messages.Hello"), Some(&8));
+        assert_eq!(lines.get("messages.file_many"), Some(&20));
+    }
+
+    #[test]
+    fn rejects_malformed_entries() {
+        let err = parse("msgstr \"x\"\n", "de").unwrap_err();
+        assert!(err.to_string().contains("line 1"), "{}", err);
+        assert!(parse("msgid \"a\"\nmsgstr \"unterminated\n", "de").is_err());
+        assert!(parse("msgid \"a\"\n", "de").is_err());
+    }
+}
@@ -1,22 +1,103 @@
 use anyhow::Result;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
-use std::sync::{Mutex, MutexGuard};
+use std::sync::{Arc, Mutex, MutexGuard};
 
-use super::io::{file_mtime, read_json_file};
-use crate::util::flatten_table;
+use super::io::{ResourceContent, file_mtime, is_resource_file, read_resource_file};
+use crate::util::{extract_placeholders, flatten_table, flatten_top_level, path_to_string};
 
 #[derive(Debug, Deserialize)]
 pub struct BuildIndexParams {
     pub roots: Vec<RootConfig>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct RootConfig {
     pub kind: String,
     pub path: String,
+    /// Namespaces whose files map top-level keys verbatim instead of flattening
+    /// nested objects, for catalogs whose keys contain literal dots (`"v1.2.3"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flat_namespaces: Option<Vec<String>>,
+    /// Languages stored outside `path`, mapped to their own `{ns}.json` directory.
+    /// These take precedence over a same-named directory under `path`. Ordered so
+    /// the serialized config (the cache key) is stable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang_paths: Option<BTreeMap<String, String>>,
+    /// Files of a `files` root, each assigned its language and namespace
+    /// explicitly instead of by directory layout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<ResourceFile>>,
+    /// Language directories merged into another language, e.g. `en-US` -> `en`.
+    /// Entries from the canonical language's own files win over aliased ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang_aliases: Option<BTreeMap<String, String>>,
+    /// Accept comments and trailing commas in `.json` files as well, not only in
+    /// `.jsonc`/`.json5` ones.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub json_comments: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ResourceFile {
+    pub path: String,
+    pub lang: String,
+    pub namespace: String,
+}
+
+/// Priority of entries from explicitly listed files. They win over every
+/// layout-derived entry, since the user assigned them by hand.
+const EXPLICIT_FILE_PRIORITY: u32 = 20;
+
+impl RootConfig {
+    /// The explicit assignment for `path`, when this root lists it.
+    fn explicit_file(&self, path: &Path) -> Option<&ResourceFile> {
+        self.files
+            .iter()
+            .flatten()
+            .find(|file| Path::new(&file.path) == path)
+    }
+
+    fn has_lang_path(&self, lang: &str) -> bool {
+        self.lang_paths
+            .as_ref()
+            .is_some_and(|paths| paths.contains_key(lang))
+    }
+
+    /// Language that entries of the `lang` directory or file are indexed under.
+    fn canonical_lang<'a>(&'a self, lang: &'a str) -> &'a str {
+        self.lang_aliases
+            .as_ref()
+            .and_then(|aliases| aliases.get(lang))
+            .map_or(lang, |canonical| canonical.as_str())
+    }
+
+    /// Extra priority for entries read under an aliased language, so the canonical
+    /// language's own files win.
+    fn alias_penalty(&self, lang: &str) -> u32 {
+        u32::from(self.canonical_lang(lang) != lang)
+    }
+
+    /// Priority of entries read from `{lang}/{ns}.json` under this root.
+    fn lang_dir_priority(&self, lang: &str) -> u32 {
+        let base = if self.kind == "next-intl" { 50 } else { 30 };
+        base + self.alias_penalty(lang)
+    }
+
+    fn flatten_namespace(&self, ns: &str, value: &Value) -> BTreeMap<String, String> {
+        let is_flat = self
+            .flat_namespaces
+            .as_ref()
+            .is_some_and(|list| list.iter().any(|n| n == ns));
+        if is_flat {
+            flatten_top_level(value)
+        } else {
+            flatten_table(value, "")
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -25,11 +106,44 @@ pub struct ApplyChangesParams {
     pub paths: Vec<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct RebuildNamespaceParams {
+    pub cache_key: String,
+    pub namespace: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ValueOutliersParams {
+    pub cache_key: String,
+    pub lang: String,
+    pub long_threshold: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KeysUsingPlaceholderParams {
+    pub cache_key: String,
+    /// Placeholder name without braces, e.g. `name` for `{{name}}` or `{name}`.
+    pub placeholder: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceItem {
     pub value: Option<String>,
     pub file: Option<String>,
     pub priority: u32,
+    /// Line of the key's leaf in `file`, when the file format records positions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lnum: Option<u32>,
+}
+
+/// A key whose value was added, changed, or removed by `apply_changes`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedKey {
+    pub key: String,
+    pub lang: String,
+    /// Line of the key's leaf in the updated file; absent for removed keys.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lnum: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,7 +153,33 @@ pub struct IndexError {
     pub error: String,
 }
 
+/// A non-fatal condition found while indexing, e.g. an intentionally empty catalog.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexWarning {
+    pub lang: String,
+    pub file: String,
+    pub kind: String,
+}
+
+/// Two files defining the same key of one language with different values.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValueConflict {
+    pub kind: String,
+    pub lang: String,
+    pub key: String,
+    /// The definition the index keeps.
+    pub kept: ConflictEntry,
+    /// The definition it shadows.
+    pub shadowed: ConflictEntry,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictEntry {
+    pub file: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct IndexResult {
     /// lang -> canonical_key -> ResourceItem
     pub index: HashMap<String, HashMap<String, ResourceItem>>,
@@ -47,22 +187,76 @@ pub struct IndexResult {
     pub files: HashMap<String, u64>,
     pub languages: Vec<String>,
     pub errors: Vec<IndexError>,
+    #[serde(default)]
+    pub warnings: Vec<IndexWarning>,
     pub namespaces: Vec<String>,
+    /// namespace -> paths of the roots that define it
+    #[serde(default)]
+    pub namespace_roots: HashMap<String, Vec<String>>,
+    /// Keys defined with different values by several files, from the last full
+    /// build. Namespace rebuilds and language directory changes refresh the ones
+    /// they touch; single-file updates do not re-check them.
+    #[serde(default)]
+    pub value_conflicts: Vec<ValueConflict>,
+    /// Parsed content of each file read by the build, reused by the next build of
+    /// the same roots for files whose mtime is unchanged.
+    #[serde(skip)]
+    parsed_files: ParsedFiles,
 }
 
-/// In-process cache for resource indices.
+/// file path -> parsed content (`None` for an empty file) at the recorded mtime.
+type ParsedFiles = HashMap<String, ParsedFile>;
+
+#[derive(Debug, Clone)]
+struct ParsedFile {
+    mtime: u64,
+    content: Option<Arc<ResourceContent>>,
+}
+
+/// Number of indices `IndexCache::new` keeps before evicting the least recently used.
+pub const DEFAULT_INDEX_CACHE_CAPACITY: usize = 8;
+
+/// In-process cache for resource indices, bounded by an LRU policy: each distinct
+/// root configuration gets its own entry, so a long session would otherwise keep
+/// every historical index alive.
 pub struct IndexCache {
-    entries: Mutex<HashMap<String, IndexResult>>,
+    entries: Mutex<CacheEntries>,
+    /// Held across each read-modify-write of an entry (`build_index`,
+    /// `apply_changes`, `rebuild_namespace`), so concurrent requests on the worker
+    /// pool cannot overwrite each other's updates. Plain reads only lock `entries`.
+    updates: Mutex<()>,
+    capacity: usize,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    /// Index and the tick of its last access.
+    map: HashMap<String, (IndexResult, u64)>,
+    tick: u64,
+}
+
+impl CacheEntries {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
 }
 
 impl IndexCache {
     pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_INDEX_CACHE_CAPACITY)
+    }
+
+    /// Cache holding at most `capacity` indices (at least one).
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            entries: Mutex::new(HashMap::new()),
+            entries: Mutex::new(CacheEntries::default()),
+            updates: Mutex::new(()),
+            capacity: capacity.max(1),
         }
     }
 
-    fn lock_entries(&self) -> MutexGuard<'_, HashMap<String, IndexResult>> {
+    fn lock_entries(&self) -> MutexGuard<'_, CacheEntries> {
         match self.entries.lock() {
             Ok(guard) => guard,
             Err(poisoned) => {
@@ -72,18 +266,44 @@ impl IndexCache {
                 // erroring on every call until the server restarts.
                 self.entries.clear_poison();
                 let mut entries = poisoned.into_inner();
-                entries.clear();
+                entries.map.clear();
                 entries
             }
         }
     }
 
-    fn get(&self, key: &str) -> Option<IndexResult> {
-        self.lock_entries().get(key).cloned()
+    fn lock_updates(&self) -> MutexGuard<'_, ()> {
+        self.updates.lock().unwrap_or_else(|poisoned| {
+            // The guarded data is `()`, so a panicking updater leaves nothing
+            // inconsistent behind.
+            self.updates.clear_poison();
+            poisoned.into_inner()
+        })
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<IndexResult> {
+        let mut entries = self.lock_entries();
+        let tick = entries.next_tick();
+        let (value, last_used) = entries.map.get_mut(key)?;
+        *last_used = tick;
+        Some(value.clone())
     }
 
     fn set(&self, key: String, value: IndexResult) {
-        self.lock_entries().insert(key, value);
+        let mut entries = self.lock_entries();
+        let tick = entries.next_tick();
+        entries.map.insert(key, (value, tick));
+        while entries.map.len() > self.capacity {
+            let Some(oldest) = entries
+                .map
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            entries.map.remove(&oldest);
+        }
     }
 }
 
@@ -93,17 +313,46 @@ impl Default for IndexCache {
     }
 }
 
+/// Leaf lines of a resource file, and the path prefix of the namespace being
+/// inserted (`"{ns}."` for root files holding every namespace, else empty).
+struct LeafLines<'a> {
+    lines: &'a HashMap<String, u32>,
+    prefix: &'a str,
+}
+
+impl<'a> LeafLines<'a> {
+    fn file(lines: &'a HashMap<String, u32>) -> Self {
+        Self { lines, prefix: "" }
+    }
+
+    fn namespace(lines: &'a HashMap<String, u32>, prefix: &'a str) -> Self {
+        Self { lines, prefix }
+    }
+
+    fn get(&self, key: &str) -> Option<u32> {
+        if self.prefix.is_empty() {
+            self.lines.get(key).copied()
+        } else {
+            self.lines.get(&format!("{}{}", self.prefix, key)).copied()
+        }
+    }
+}
+
 /// Insert items into the index. Only replaces if the new priority is lower (wins).
+/// Returns the keys another file already defined with a different value.
 fn insert_items(
     index: &mut HashMap<String, HashMap<String, ResourceItem>>,
     lang: &str,
     namespace: &str,
-    flat: &std::collections::BTreeMap<String, String>,
+    flat: &BTreeMap<String, String>,
+    lines: LeafLines<'_>,
     file_path: &str,
     priority: u32,
-) {
+) -> Vec<ValueConflict> {
+    let mut conflicts = Vec::new();
     let lang_map = index.entry(lang.to_string()).or_default();
     for (key, value) in flat {
+        let lnum = lines.get(key);
         let canonical_key = format!("{}:{}", namespace, key);
         let entry = lang_map.entry(canonical_key);
         match entry {
@@ -112,31 +361,275 @@ fn insert_items(
                     value: Some(value.clone()),
                     file: Some(file_path.to_string()),
                     priority,
+                    lnum,
                 });
             }
             std::collections::hash_map::Entry::Occupied(mut e) => {
-                if priority < e.get().priority {
+                let existing = ConflictEntry {
+                    file: e.get().file.clone().unwrap_or_default(),
+                    value: e.get().value.clone().unwrap_or_default(),
+                };
+                let incoming = ConflictEntry {
+                    file: file_path.to_string(),
+                    value: value.clone(),
+                };
+                let wins = priority < e.get().priority;
+                if wins {
                     e.insert(ResourceItem {
                         value: Some(value.clone()),
                         file: Some(file_path.to_string()),
                         priority,
+                        lnum,
                     });
                 }
+                if existing.value != incoming.value && existing.file != incoming.file {
+                    let (kept, shadowed) = if wins {
+                        (incoming, existing)
+                    } else {
+                        (existing, incoming)
+                    };
+                    conflicts.push(ValueConflict {
+                        kind: "value_conflict".to_string(),
+                        lang: lang.to_string(),
+                        key: e.key().clone(),
+                        kept,
+                        shadowed,
+                    });
+                }
+            }
+        }
+    }
+    conflicts
+}
+
+fn empty_file_warning(lang: &str, file: &str) -> IndexWarning {
+    IndexWarning {
+        lang: lang.to_string(),
+        file: file.to_string(),
+        kind: "empty_file".to_string(),
+    }
+}
+
+/// A resource file found by walking a root, read in parallel with the others and
+/// merged in walk order so the result does not depend on thread scheduling.
+struct FileTask {
+    path: PathBuf,
+    file_str: String,
+    /// Canonical language the file's entries are indexed under.
+    lang: String,
+    priority: u32,
+    target: FileTarget,
+}
+
+enum FileTarget {
+    /// `{ns}.json` inside a language directory.
+    Namespace(String),
+    /// File assigned to a namespace by a `files` root; its language and namespace
+    /// only count once it parses.
+    Explicit(String),
+    /// next-intl root `{lang}.json` or gettext `{lang}.po`: each top-level key is a
+    /// namespace.
+    Namespaces,
+}
+
+impl FileTask {
+    fn new(path: PathBuf, lang: &str, priority: u32, target: FileTarget) -> Self {
+        Self {
+            file_str: path_to_string(&path),
+            path,
+            lang: lang.to_string(),
+            priority,
+            target,
+        }
+    }
+}
+
+/// What reading a `FileTask` produced, before it is merged into the index.
+struct FileOutcome {
+    mtime: Option<u64>,
+    content: Result<Option<Arc<ResourceContent>>>,
+    /// Flattened entries per namespace, with the leaf path prefix of that namespace.
+    namespaces: Vec<(String, String, BTreeMap<String, String>)>,
+}
+
+/// Read and flatten a file, reusing the previous build's parse when its mtime is
+/// unchanged. Parse errors are not cached, so they are re-read. With `only_ns`,
+/// other namespaces of a file holding several are left out.
+fn read_file_task(
+    root: &RootConfig,
+    task: &FileTask,
+    previous: &ParsedFiles,
+    only_ns: Option<&str>,
+) -> FileOutcome {
+    let mtime = file_mtime(&task.path).ok();
+    let reused = previous
+        .get(&task.file_str)
+        .filter(|parsed| Some(parsed.mtime) == mtime);
+    let content = match reused {
+        Some(parsed) => Ok(parsed.content.clone()),
+        None => {
+            read_resource_file(&task.path, root.json_comments).map(|content| content.map(Arc::new))
+        }
+    };
+    let namespaces = match (&task.target, &content) {
+        (FileTarget::Namespace(ns) | FileTarget::Explicit(ns), Ok(Some(content))) => {
+            vec![(
+                ns.clone(),
+                String::new(),
+                root.flatten_namespace(ns, &content.value),
+            )]
+        }
+        (FileTarget::Namespaces, Ok(Some(content))) => match &content.value {
+            Value::Object(map) => map
+                .iter()
+                .filter(|(ns, _)| only_ns.is_none_or(|only| only == ns.as_str()))
+                .map(|(ns, ns_value)| {
+                    (
+                        ns.clone(),
+                        format!("{}.", ns),
+                        root.flatten_namespace(ns, ns_value),
+                    )
+                })
+                .collect(),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    FileOutcome {
+        mtime,
+        content,
+        namespaces,
+    }
+}
+
+/// Read `tasks` in parallel, then merge them into `out` in order, so priorities and
+/// conflicts resolve exactly as a sequential build would.
+fn index_files(
+    root: &RootConfig,
+    tasks: Vec<FileTask>,
+    previous: &ParsedFiles,
+    only_ns: Option<&str>,
+    out: &mut IndexResult,
+    languages: &mut BTreeSet<String>,
+    namespaces: &mut BTreeSet<String>,
+) {
+    let outcomes: Vec<FileOutcome> = tasks
+        .par_iter()
+        .map(|task| read_file_task(root, task, previous, only_ns))
+        .collect();
+
+    for (task, outcome) in tasks.into_iter().zip(outcomes) {
+        let FileTask {
+            file_str,
+            lang,
+            priority,
+            target,
+            ..
+        } = task;
+        if let Some(mtime) = outcome.mtime {
+            out.files.insert(file_str.clone(), mtime);
+        }
+        let content = match outcome.content {
+            Ok(content) => content,
+            Err(e) => {
+                out.errors.push(IndexError {
+                    lang,
+                    file: file_str,
+                    error: e.to_string(),
+                });
+                continue;
             }
+        };
+        if let Some(mtime) = outcome.mtime {
+            out.parsed_files.insert(
+                file_str.clone(),
+                ParsedFile {
+                    mtime,
+                    content: content.clone(),
+                },
+            );
+        }
+        if let FileTarget::Explicit(ns) = &target {
+            languages.insert(lang.clone());
+            namespaces.insert(ns.clone());
+        }
+        let Some(content) = content else {
+            out.warnings.push(empty_file_warning(&lang, &file_str));
+            continue;
+        };
+        if matches!(target, FileTarget::Namespaces) && !content.value.is_object() {
+            out.errors.push(IndexError {
+                lang,
+                file: file_str,
+                error: "expected top-level JSON object".to_string(),
+            });
+            continue;
+        }
+        for (ns, prefix, flat) in &outcome.namespaces {
+            if matches!(target, FileTarget::Namespaces) {
+                namespaces.insert(ns.clone());
+            }
+            let conflicts = insert_items(
+                &mut out.index,
+                &lang,
+                ns,
+                flat,
+                LeafLines::namespace(&content.lines, prefix),
+                &file_str,
+                priority,
+            );
+            out.value_conflicts.extend(conflicts);
+        }
+    }
+}
+
+/// Queue every `{ns}.json` directly inside a language directory. Returns whether
+/// the directory contained any JSON file.
+fn index_lang_dir(
+    root: &RootConfig,
+    dir: &Path,
+    lang: &str,
+    tasks: &mut Vec<FileTask>,
+    namespaces: &mut BTreeSet<String>,
+) -> bool {
+    let priority = root.lang_dir_priority(lang);
+    let lang = root.canonical_lang(lang);
+    let lang_entries = match std::fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(_) => return false,
+    };
+    let mut has_json_file = false;
+
+    for file_entry in lang_entries.flatten() {
+        let file_path = file_entry.path();
+        if !is_resource_file(&file_path) {
+            continue;
         }
+        has_json_file = true;
+        let ns = match file_path.file_stem().and_then(|n| n.to_str()) {
+            Some(n) => n.to_string(),
+            None => continue,
+        };
+        namespaces.insert(ns.clone());
+        tasks.push(FileTask::new(
+            file_path,
+            lang,
+            priority,
+            FileTarget::Namespace(ns),
+        ));
     }
+
+    has_json_file
 }
 
 /// Process an i18next root: locales/{lang}/{ns}.json
 fn process_i18next(
-    root: &Path,
-    index: &mut HashMap<String, HashMap<String, ResourceItem>>,
-    files: &mut HashMap<String, u64>,
+    root: &RootConfig,
+    tasks: &mut Vec<FileTask>,
     languages: &mut BTreeSet<String>,
     namespaces: &mut BTreeSet<String>,
-    errors: &mut Vec<IndexError>,
 ) {
-    let entries = match std::fs::read_dir(root) {
+    let entries = match std::fs::read_dir(&root.path) {
         Ok(e) => e,
         Err(_) => return,
     };
@@ -150,63 +643,23 @@ fn process_i18next(
             Some(n) => n.to_string(),
             None => continue,
         };
-        let mut has_json_file = false;
-
-        let lang_entries = match std::fs::read_dir(&path) {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        for file_entry in lang_entries.flatten() {
-            let file_path = file_entry.path();
-            if file_path.extension().and_then(|e| e.to_str()) != Some("json") {
-                continue;
-            }
-            has_json_file = true;
-            let ns = match file_path.file_stem().and_then(|n| n.to_str()) {
-                Some(n) => n.to_string(),
-                None => continue,
-            };
-            namespaces.insert(ns.clone());
-
-            let file_str = file_path.to_string_lossy().to_string();
-
-            // Record mtime
-            if let Ok(mtime) = file_mtime(&file_path) {
-                files.insert(file_str.clone(), mtime);
-            }
-
-            match read_json_file(&file_path) {
-                Ok(value) => {
-                    let flat = flatten_table(&value, "");
-                    insert_items(index, &lang, &ns, &flat, &file_str, 30);
-                }
-                Err(e) => {
-                    errors.push(IndexError {
-                        lang: lang.clone(),
-                        file: file_str,
-                        error: e.to_string(),
-                    });
-                }
-            }
+        if root.has_lang_path(&lang) {
+            continue;
         }
-
-        if has_json_file {
-            languages.insert(lang);
+        if index_lang_dir(root, &path, &lang, tasks, namespaces) {
+            languages.insert(root.canonical_lang(&lang).to_string());
         }
     }
 }
 
 /// Process a next-intl root: messages/{lang}/{ns}.json and messages/{lang}.json
 fn process_next_intl(
-    root: &Path,
-    index: &mut HashMap<String, HashMap<String, ResourceItem>>,
-    files: &mut HashMap<String, u64>,
+    root: &RootConfig,
+    tasks: &mut Vec<FileTask>,
     languages: &mut BTreeSet<String>,
     namespaces: &mut BTreeSet<String>,
-    errors: &mut Vec<IndexError>,
 ) {
-    let entries = match std::fs::read_dir(root) {
+    let entries = match std::fs::read_dir(&root.path) {
         Ok(e) => e,
         Err(_) => return,
     };
@@ -220,136 +673,185 @@ fn process_next_intl(
                 Some(n) => n.to_string(),
                 None => continue,
             };
-            let mut has_json_file = false;
-
-            let lang_entries = match std::fs::read_dir(&path) {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
-
-            for file_entry in lang_entries.flatten() {
-                let file_path = file_entry.path();
-                if file_path.extension().and_then(|e| e.to_str()) != Some("json") {
-                    continue;
-                }
-                has_json_file = true;
-                let ns = match file_path.file_stem().and_then(|n| n.to_str()) {
-                    Some(n) => n.to_string(),
-                    None => continue,
-                };
-                namespaces.insert(ns.clone());
-
-                let file_str = file_path.to_string_lossy().to_string();
-
-                if let Ok(mtime) = file_mtime(&file_path) {
-                    files.insert(file_str.clone(), mtime);
-                }
-
-                match read_json_file(&file_path) {
-                    Ok(value) => {
-                        let flat = flatten_table(&value, "");
-                        insert_items(index, &lang, &ns, &flat, &file_str, 50);
-                    }
-                    Err(e) => {
-                        errors.push(IndexError {
-                            lang: lang.clone(),
-                            file: file_str,
-                            error: e.to_string(),
-                        });
-                    }
-                }
+            if root.has_lang_path(&lang) {
+                continue;
             }
-
-            if has_json_file {
-                languages.insert(lang);
+            if index_lang_dir(root, &path, &lang, tasks, namespaces) {
+                languages.insert(root.canonical_lang(&lang).to_string());
             }
-        } else if path.extension().and_then(|e| e.to_str()) == Some("json") {
+        } else if is_resource_file(&path) {
             // Root-level {lang}.json: top-level keys are namespaces
             let lang = match path.file_stem().and_then(|n| n.to_str()) {
                 Some(n) => n.to_string(),
                 None => continue,
             };
+            if root.has_lang_path(&lang) {
+                continue;
+            }
+            let priority = 40 + root.alias_penalty(&lang);
+            let lang = root.canonical_lang(&lang).to_string();
             languages.insert(lang.clone());
+            tasks.push(FileTask::new(path, &lang, priority, FileTarget::Namespaces));
+        }
+    }
+}
 
-            let file_str = path.to_string_lossy().to_string();
+/// Process a `files` root: each listed file is read as the given language and
+/// namespace, regardless of where it lives.
+fn process_explicit_files(root: &RootConfig, tasks: &mut Vec<FileTask>) {
+    for file in root.files.iter().flatten() {
+        tasks.push(FileTask::new(
+            PathBuf::from(&file.path),
+            &file.lang,
+            EXPLICIT_FILE_PRIORITY,
+            FileTarget::Explicit(file.namespace.clone()),
+        ));
+    }
+}
 
-            if let Ok(mtime) = file_mtime(&path) {
-                files.insert(file_str.clone(), mtime);
-            }
+/// Process a gettext root: locales/{lang}.po, with each `msgctxt` as a namespace.
+fn process_gettext(root: &RootConfig, tasks: &mut Vec<FileTask>, languages: &mut BTreeSet<String>) {
+    let entries = match std::fs::read_dir(&root.path) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
 
-            match read_json_file(&path) {
-                Ok(Value::Object(map)) => {
-                    // Each top-level key is a namespace
-                    for (ns, ns_value) in &map {
-                        namespaces.insert(ns.clone());
-                        let flat = flatten_table(ns_value, "");
-                        insert_items(index, &lang, ns, &flat, &file_str, 40);
-                    }
-                }
-                Ok(_) => {
-                    errors.push(IndexError {
-                        lang: lang.clone(),
-                        file: file_str,
-                        error: "expected top-level JSON object".to_string(),
-                    });
-                }
-                Err(e) => {
-                    errors.push(IndexError {
-                        lang: lang.clone(),
-                        file: file_str,
-                        error: e.to_string(),
-                    });
-                }
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some("po") {
+            continue;
+        }
+        let lang = match path.file_stem().and_then(|n| n.to_str()) {
+            Some(n) => n.to_string(),
+            None => continue,
+        };
+        let priority = 40 + root.alias_penalty(&lang);
+        let lang = root.canonical_lang(&lang).to_string();
+        languages.insert(lang.clone());
+        tasks.push(FileTask::new(path, &lang, priority, FileTarget::Namespaces));
+    }
+}
+
+/// Process a Fluent root: locales/{lang}/{ns}.ftl
+fn process_fluent(
+    root: &RootConfig,
+    tasks: &mut Vec<FileTask>,
+    languages: &mut BTreeSet<String>,
+    namespaces: &mut BTreeSet<String>,
+) {
+    let entries = match std::fs::read_dir(&root.path) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        if !dir.is_dir() {
+            continue;
+        }
+        let lang = match dir.file_name().and_then(|n| n.to_str()) {
+            Some(n) => n.to_string(),
+            None => continue,
+        };
+        let Ok(files) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let priority = root.lang_dir_priority(&lang);
+        let lang = root.canonical_lang(&lang);
+        let mut has_ftl_file = false;
+        for file in files.flatten() {
+            let path = file.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("ftl") {
+                continue;
             }
+            let Some(ns) = path.file_stem().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            has_ftl_file = true;
+            namespaces.insert(ns.to_string());
+            let target = FileTarget::Namespace(ns.to_string());
+            tasks.push(FileTask::new(path, lang, priority, target));
+        }
+        if has_ftl_file {
+            languages.insert(lang.to_string());
+        }
+    }
+}
+
+/// Index the `{ns}.json` directories configured per language in `lang_paths`.
+fn process_lang_paths(
+    root: &RootConfig,
+    tasks: &mut Vec<FileTask>,
+    languages: &mut BTreeSet<String>,
+    namespaces: &mut BTreeSet<String>,
+) {
+    for (lang, dir) in root.lang_paths.iter().flatten() {
+        if index_lang_dir(root, Path::new(dir), lang, tasks, namespaces) {
+            languages.insert(root.canonical_lang(lang).to_string());
         }
     }
 }
 
 pub fn build_index(params: BuildIndexParams, cache: &IndexCache) -> Result<Value> {
     let cache_key = serde_json::to_string(&params.roots)?;
+    let _updates = cache.lock_updates();
+    let previous = cache
+        .get(&cache_key)
+        .map(|cached| cached.parsed_files)
+        .unwrap_or_default();
 
-    let mut index: HashMap<String, HashMap<String, ResourceItem>> = HashMap::new();
-    let mut files: HashMap<String, u64> = HashMap::new();
+    let mut result = IndexResult::default();
     let mut languages: BTreeSet<String> = BTreeSet::new();
     let mut namespaces: BTreeSet<String> = BTreeSet::new();
-    let mut errors: Vec<IndexError> = Vec::new();
 
     for root in &params.roots {
-        let root_path = PathBuf::from(&root.path);
+        let mut root_namespaces: BTreeSet<String> = BTreeSet::new();
+        let mut tasks = Vec::new();
         match root.kind.as_str() {
             "i18next" => {
-                process_i18next(
-                    &root_path,
-                    &mut index,
-                    &mut files,
-                    &mut languages,
-                    &mut namespaces,
-                    &mut errors,
-                );
+                process_i18next(root, &mut tasks, &mut languages, &mut root_namespaces);
+                process_lang_paths(root, &mut tasks, &mut languages, &mut root_namespaces);
             }
             "next-intl" => {
-                process_next_intl(
-                    &root_path,
-                    &mut index,
-                    &mut files,
-                    &mut languages,
-                    &mut namespaces,
-                    &mut errors,
-                );
+                process_next_intl(root, &mut tasks, &mut languages, &mut root_namespaces);
+                process_lang_paths(root, &mut tasks, &mut languages, &mut root_namespaces);
+            }
+            "files" => {
+                process_explicit_files(root, &mut tasks);
+            }
+            "gettext" => {
+                process_gettext(root, &mut tasks, &mut languages);
+            }
+            "fluent" => {
+                process_fluent(root, &mut tasks, &mut languages, &mut root_namespaces);
             }
             _ => {
                 // Unknown kind, skip
             }
         }
+        index_files(
+            root,
+            tasks,
+            &previous,
+            None,
+            &mut result,
+            &mut languages,
+            &mut root_namespaces,
+        );
+        for ns in root_namespaces {
+            let root_paths = result.namespace_roots.entry(ns.clone()).or_default();
+            if !root_paths.contains(&root.path) {
+                root_paths.push(root.path.clone());
+            }
+            namespaces.insert(ns);
+        }
     }
 
-    let result = IndexResult {
-        index,
-        files,
-        languages: languages.into_iter().collect(),
-        errors,
-        namespaces: namespaces.into_iter().collect(),
-    };
+    result.languages = languages.into_iter().collect();
+    result.namespaces = namespaces.into_iter().collect();
+    result
+        .value_conflicts
+        .sort_by(|a, b| (&a.lang, &a.key).cmp(&(&b.lang, &b.key)));
 
     cache.set(cache_key.clone(), result.clone());
 
@@ -370,9 +872,40 @@ fn remove_entries_by_file(
     index.retain(|_, lang_map| !lang_map.is_empty());
 }
 
-fn refresh_languages_and_namespaces(result: &mut IndexResult) {
+/// Snapshot of the (lang, key) -> value entries currently owned by `file_path`.
+fn file_values(
+    index: &HashMap<String, HashMap<String, ResourceItem>>,
+    file_path: &str,
+) -> HashMap<(String, String), Option<String>> {
+    let mut values = HashMap::new();
+    for (lang, lang_map) in index {
+        for (key, item) in lang_map {
+            if item.file.as_deref() == Some(file_path) {
+                values.insert((lang.clone(), key.clone()), item.value.clone());
+            }
+        }
+    }
+    values
+}
+
+/// Position of the root whose directory (or one of its `lang_paths`) contains `file`.
+fn root_index_for_file(roots: &[RootConfig], file: &str) -> Option<usize> {
+    let file = Path::new(file);
+    roots.iter().position(|root| {
+        root.explicit_file(file).is_some()
+            || (root.kind != "files" && file.starts_with(&root.path))
+            || root
+                .lang_paths
+                .iter()
+                .flatten()
+                .any(|(_, dir)| file.starts_with(dir))
+    })
+}
+
+fn refresh_languages_and_namespaces(result: &mut IndexResult, roots: &[RootConfig]) {
     let mut languages = BTreeSet::new();
     let mut namespaces = BTreeSet::new();
+    let mut namespace_roots: BTreeMap<String, BTreeSet<usize>> = BTreeMap::new();
 
     result.index.retain(|_, lang_map| !lang_map.is_empty());
 
@@ -380,60 +913,202 @@ fn refresh_languages_and_namespaces(result: &mut IndexResult) {
         if !lang_map.is_empty() {
             languages.insert(lang.clone());
         }
-        for key in lang_map.keys() {
+        for (key, item) in lang_map {
             if let Some((ns, _)) = key.split_once(':') {
                 namespaces.insert(ns.to_string());
+                let root_idx = item
+                    .file
+                    .as_deref()
+                    .and_then(|file| root_index_for_file(roots, file));
+                if let Some(root_idx) = root_idx {
+                    namespace_roots
+                        .entry(ns.to_string())
+                        .or_default()
+                        .insert(root_idx);
+                }
             }
         }
     }
 
     result.languages = languages.into_iter().collect();
     result.namespaces = namespaces.into_iter().collect();
+    // Keep roots in configuration order, as `build_index` does.
+    result.namespace_roots = namespace_roots
+        .into_iter()
+        .map(|(ns, idxs)| {
+            let paths = idxs.into_iter().map(|i| roots[i].path.clone()).collect();
+            (ns, paths)
+        })
+        .collect();
+}
+
+/// Incrementally add or remove a `{lang}/` directory directly under a known root.
+/// Returns false when the change cannot be applied without a full rebuild.
+fn apply_directory_change(
+    updated: &mut IndexResult,
+    roots: &[RootConfig],
+    dir: &Path,
+) -> Option<Vec<ChangedKey>> {
+    let (root, relative) = roots.iter().find_map(|root| {
+        dir.strip_prefix(&root.path)
+            .ok()
+            .map(|relative| (root, relative.to_path_buf()))
+    })?;
+    let lang = match relative.components().collect::<Vec<_>>().as_slice() {
+        [component] => component.as_os_str().to_str()?.to_string(),
+        _ => return None,
+    };
+    if !matches!(root.kind.as_str(), "i18next" | "next-intl") {
+        return None;
+    }
+
+    let old_values = dir_values(&updated.index, dir);
+
+    // Drop everything previously indexed from inside this directory.
+    let stale: Vec<String> = updated
+        .files
+        .keys()
+        .filter(|file| Path::new(file).starts_with(dir))
+        .cloned()
+        .collect();
+    for file in &stale {
+        remove_entries_by_file(&mut updated.index, file);
+        updated.files.remove(file);
+    }
+    updated
+        .errors
+        .retain(|entry| !Path::new(&entry.file).starts_with(dir));
+    updated
+        .warnings
+        .retain(|entry| !Path::new(&entry.file).starts_with(dir));
+    updated.value_conflicts.retain(|conflict| {
+        !Path::new(&conflict.kept.file).starts_with(dir)
+            && !Path::new(&conflict.shadowed.file).starts_with(dir)
+    });
+
+    if dir.is_dir() {
+        let mut namespaces = BTreeSet::new();
+        let mut tasks = Vec::new();
+        index_lang_dir(root, dir, &lang, &mut tasks, &mut namespaces);
+        index_files(
+            root,
+            tasks,
+            &ParsedFiles::new(),
+            None,
+            updated,
+            &mut BTreeSet::new(),
+            &mut namespaces,
+        );
+        updated
+            .value_conflicts
+            .sort_by(|a, b| (&a.lang, &a.key).cmp(&(&b.lang, &b.key)));
+    }
+
+    let new_values = dir_values(&updated.index, dir);
+    let mut touched: BTreeSet<&(String, String)> = old_values.keys().collect();
+    touched.extend(new_values.keys());
+    let changed_keys = touched
+        .into_iter()
+        .filter(|entry| old_values.get(*entry) != new_values.get(*entry))
+        .map(|(lang, key)| ChangedKey {
+            key: key.clone(),
+            lang: lang.clone(),
+            // Entries left in the index are the ones now defined inside `dir`.
+            lnum: updated
+                .index
+                .get(lang)
+                .and_then(|lang_map| lang_map.get(key))
+                .filter(|item| {
+                    item.file
+                        .as_deref()
+                        .is_some_and(|file| Path::new(file).starts_with(dir))
+                })
+                .and_then(|item| item.lnum),
+        })
+        .collect();
+    Some(changed_keys)
+}
+
+/// Like `file_values`, for every file inside `dir`.
+fn dir_values(
+    index: &HashMap<String, HashMap<String, ResourceItem>>,
+    dir: &Path,
+) -> HashMap<(String, String), Option<String>> {
+    let mut values = HashMap::new();
+    for (lang, lang_map) in index {
+        for (key, item) in lang_map {
+            if item
+                .file
+                .as_deref()
+                .is_some_and(|file| Path::new(file).starts_with(dir))
+            {
+                values.insert((lang.clone(), key.clone()), item.value.clone());
+            }
+        }
+    }
+    values
+}
+
+fn needs_rebuild() -> Value {
+    serde_json::json!({
+        "success": false,
+        "needs_rebuild": true
+    })
 }
 
 pub fn apply_changes(params: ApplyChangesParams, cache: &IndexCache) -> Result<Value> {
+    let _updates = cache.lock_updates();
     let cached = match cache.get(&params.cache_key) {
         Some(c) => c,
         None => {
-            return Ok(serde_json::json!({
-                "success": false,
-                "needs_rebuild": true
-            }));
+            return Ok(needs_rebuild());
         }
     };
 
     let mut updated = cached.clone();
+    let mut changed_keys: Vec<ChangedKey> = Vec::new();
     let roots: Vec<RootConfig> = match serde_json::from_str(&params.cache_key) {
         Ok(r) => r,
         Err(_) => {
-            return Ok(serde_json::json!({
-                "success": false,
-                "needs_rebuild": true
-            }));
+            return Ok(needs_rebuild());
         }
     };
 
-    for path_str in &params.paths {
-        let path = PathBuf::from(path_str);
+    for raw_path in &params.paths {
+        let path = PathBuf::from(raw_path);
+        let path_str = &path_to_string(&path);
+
+        // Directories (existing, or deleted paths that were not JSON files) are
+        // handled as a whole language directory being added or removed.
+        let is_json = is_resource_file(&path);
+        if path.is_dir() || (!path.exists() && !is_json) {
+            let Some(keys) = apply_directory_change(&mut updated, &roots, &path) else {
+                return Ok(needs_rebuild());
+            };
+            changed_keys.extend(keys);
+            continue;
+        }
 
-        // Must be a .json file
-        if path.extension().and_then(|e| e.to_str()) != Some("json") {
-            return Ok(serde_json::json!({
-                "success": false,
-                "needs_rebuild": true
-            }));
+        // Must be a resource file
+        if !is_json {
+            return Ok(needs_rebuild());
         }
 
         if path.exists() && !path.is_file() {
-            return Ok(serde_json::json!({
-                "success": false,
-                "needs_rebuild": true
-            }));
+            return Ok(needs_rebuild());
         }
 
-        let mut matched_root_kind: Option<String> = None;
+        let mut matched_root: Option<&RootConfig> = None;
         let mut matched_components: Vec<String> = Vec::new();
         for root in &roots {
+            if let Some(file) = root.explicit_file(&path) {
+                matched_root = Some(root);
+                matched_components = vec![file.lang.clone(), file.namespace.clone()];
+                break;
+            }
+            if root.kind == "files" {
+                continue;
+            }
             let root_path = PathBuf::from(&root.path);
             if let Ok(relative) = path.strip_prefix(&root_path) {
                 let components: Vec<String> = relative
@@ -442,45 +1117,44 @@ pub fn apply_changes(params: ApplyChangesParams, cache: &IndexCache) -> Result<V
                     .map(|s| s.to_string())
                     .collect();
                 if !components.is_empty() {
-                    matched_root_kind = Some(root.kind.clone());
+                    matched_root = Some(root);
                     matched_components = components;
                     break;
                 }
             }
         }
 
-        let root_kind = match matched_root_kind {
-            Some(kind) => kind,
+        let root = match matched_root {
+            Some(root) => root,
             None => {
-                return Ok(serde_json::json!({
-                    "success": false,
-                    "needs_rebuild": true
-                }));
+                return Ok(needs_rebuild());
             }
         };
 
         let is_deleted = !path.is_file();
-        let is_next_intl_root_file = root_kind == "next-intl" && matched_components.len() == 1;
+        let is_next_intl_root_file = root.kind == "next-intl" && matched_components.len() == 1;
         if is_deleted || is_next_intl_root_file {
-            return Ok(serde_json::json!({
-                "success": false,
-                "needs_rebuild": true
-            }));
+            return Ok(needs_rebuild());
         }
 
+        let old_values = file_values(&updated.index, path_str);
+
         // Remove old entries/errors for this file regardless of change type.
         remove_entries_by_file(&mut updated.index, path_str);
         updated.files.remove(path_str);
         updated.errors.retain(|entry| entry.file != *path_str);
+        updated.warnings.retain(|entry| entry.file != *path_str);
 
         // Re-read and re-parse the changed file.
-        let new_value = match read_json_file(&path) {
-            Ok(v) => v,
+        let (new_value, lines) = match read_resource_file(&path, root.json_comments) {
+            Ok(Some(content)) => (content.value, content.lines),
+            Ok(None) => {
+                let lang = root.canonical_lang(matched_components[0].as_str());
+                updated.warnings.push(empty_file_warning(lang, path_str));
+                (Value::Object(Default::default()), HashMap::new())
+            }
             Err(_) => {
-                return Ok(serde_json::json!({
-                    "success": false,
-                    "needs_rebuild": true
-                }));
+                return Ok(needs_rebuild());
             }
         };
 
@@ -490,22 +1164,43 @@ pub fn apply_changes(params: ApplyChangesParams, cache: &IndexCache) -> Result<V
         }
 
         let components: Vec<&str> = matched_components.iter().map(|s| s.as_str()).collect();
-        match root_kind.as_str() {
+        match root.kind.as_str() {
+            "files" => {
+                // Explicit assignment: [lang, ns]
+                let (lang, ns) = (components[0], components[1]);
+                let flat = root.flatten_namespace(ns, &new_value);
+                insert_items(
+                    &mut updated.index,
+                    lang,
+                    ns,
+                    &flat,
+                    LeafLines::file(&lines),
+                    path_str,
+                    EXPLICIT_FILE_PRIORITY,
+                );
+            }
             "i18next" => {
                 // Expected: {lang}/{ns}.json
                 if components.len() != 2 {
-                    return Ok(serde_json::json!({
-                        "success": false,
-                        "needs_rebuild": true
-                    }));
+                    return Ok(needs_rebuild());
                 }
                 let lang = components[0];
                 let ns = Path::new(components[1])
                     .file_stem()
                     .and_then(|n| n.to_str())
                     .unwrap_or("");
-                let flat = flatten_table(&new_value, "");
-                insert_items(&mut updated.index, lang, ns, &flat, path_str, 30);
+                let flat = root.flatten_namespace(ns, &new_value);
+                let priority = root.lang_dir_priority(lang);
+                let lang = root.canonical_lang(lang);
+                insert_items(
+                    &mut updated.index,
+                    lang,
+                    ns,
+                    &flat,
+                    LeafLines::file(&lines),
+                    path_str,
+                    priority,
+                );
             }
             "next-intl" => {
                 if components.len() == 2 {
@@ -515,37 +1210,79 @@ pub fn apply_changes(params: ApplyChangesParams, cache: &IndexCache) -> Result<V
                         .file_stem()
                         .and_then(|n| n.to_str())
                         .unwrap_or("");
-                    let flat = flatten_table(&new_value, "");
-                    insert_items(&mut updated.index, lang, ns, &flat, path_str, 50);
+                    let flat = root.flatten_namespace(ns, &new_value);
+                    let priority = root.lang_dir_priority(lang);
+                    let lang = root.canonical_lang(lang);
+                    insert_items(
+                        &mut updated.index,
+                        lang,
+                        ns,
+                        &flat,
+                        LeafLines::file(&lines),
+                        path_str,
+                        priority,
+                    );
                 } else if components.len() == 1 {
                     // {lang}.json root file
                     let lang = Path::new(components[0])
                         .file_stem()
                         .and_then(|n| n.to_str())
                         .unwrap_or("");
+                    let priority = 40 + root.alias_penalty(lang);
+                    let lang = root.canonical_lang(lang);
                     if let Value::Object(map) = &new_value {
                         for (ns, ns_value) in map {
-                            let flat = flatten_table(ns_value, "");
-                            insert_items(&mut updated.index, lang, ns, &flat, path_str, 40);
+                            let flat = root.flatten_namespace(ns, ns_value);
+                            let prefix = format!("{}.", ns);
+                            insert_items(
+                                &mut updated.index,
+                                lang,
+                                ns,
+                                &flat,
+                                LeafLines::namespace(&lines, &prefix),
+                                path_str,
+                                priority,
+                            );
                         }
                     }
                 } else {
-                    return Ok(serde_json::json!({
-                        "success": false,
-                        "needs_rebuild": true
-                    }));
+                    return Ok(needs_rebuild());
                 }
             }
             _ => {
-                return Ok(serde_json::json!({
-                    "success": false,
-                    "needs_rebuild": true
-                }));
+                return Ok(needs_rebuild());
             }
         }
+
+        let new_values = file_values(&updated.index, path_str);
+        let ns = if root.kind == "files" {
+            components[1]
+        } else {
+            path.file_stem().and_then(|n| n.to_str()).unwrap_or("")
+        };
+        let mut touched: BTreeSet<&(String, String)> = old_values.keys().collect();
+        touched.extend(new_values.keys());
+        for entry in touched {
+            if old_values.get(entry) == new_values.get(entry) {
+                continue;
+            }
+            let (lang, key) = entry;
+            let lnum = if new_values.contains_key(entry) {
+                key.strip_prefix(ns)
+                    .and_then(|rest| rest.strip_prefix(':'))
+                    .and_then(|path| lines.get(path).copied())
+            } else {
+                None
+            };
+            changed_keys.push(ChangedKey {
+                key: key.clone(),
+                lang: lang.clone(),
+                lnum,
+            });
+        }
     }
 
-    refresh_languages_and_namespaces(&mut updated);
+    refresh_languages_and_namespaces(&mut updated, &roots);
 
     // Update cache
     cache.set(params.cache_key, updated.clone());
@@ -553,7 +1290,215 @@ pub fn apply_changes(params: ApplyChangesParams, cache: &IndexCache) -> Result<V
     Ok(serde_json::json!({
         "success": true,
         "needs_rebuild": false,
-        "result": updated
+        "result": updated,
+        "changed_keys": changed_keys
+    }))
+}
+
+/// Re-read only the files contributing to one namespace: those its cached entries
+/// came from, plus any the walk of each root now finds for it (new files, and root
+/// files holding every namespace). Other namespaces are left as cached.
+pub fn rebuild_namespace(params: RebuildNamespaceParams, cache: &IndexCache) -> Result<Value> {
+    let _updates = cache.lock_updates();
+    let Some(cached) = cache.get(&params.cache_key) else {
+        return Ok(needs_rebuild());
+    };
+    let Ok(roots) = serde_json::from_str::<Vec<RootConfig>>(&params.cache_key) else {
+        return Ok(needs_rebuild());
+    };
+    let ns = params.namespace.as_str();
+    let prefix = format!("{}:", ns);
+
+    let mut tasks_per_root: Vec<Vec<FileTask>> = Vec::new();
+    for root in &roots {
+        let mut tasks = Vec::new();
+        let mut languages = BTreeSet::new();
+        let mut namespaces = BTreeSet::new();
+        match root.kind.as_str() {
+            "i18next" => {
+                process_i18next(root, &mut tasks, &mut languages, &mut namespaces);
+                process_lang_paths(root, &mut tasks, &mut languages, &mut namespaces);
+            }
+            "next-intl" => {
+                process_next_intl(root, &mut tasks, &mut languages, &mut namespaces);
+                process_lang_paths(root, &mut tasks, &mut languages, &mut namespaces);
+            }
+            "files" => process_explicit_files(root, &mut tasks),
+            "gettext" => process_gettext(root, &mut tasks, &mut languages),
+            "fluent" => process_fluent(root, &mut tasks, &mut languages, &mut namespaces),
+            _ => {}
+        }
+        tasks.retain(|task| match &task.target {
+            FileTarget::Namespace(task_ns) | FileTarget::Explicit(task_ns) => task_ns == ns,
+            FileTarget::Namespaces => true,
+        });
+        tasks_per_root.push(tasks);
+    }
+
+    let mut contributing: BTreeSet<String> = cached
+        .index
+        .values()
+        .flat_map(|lang_map| lang_map.iter())
+        .filter(|(key, _)| key.starts_with(&prefix))
+        .filter_map(|(_, item)| item.file.clone())
+        .collect();
+    contributing.extend(
+        tasks_per_root
+            .iter()
+            .flatten()
+            .map(|task| task.file_str.clone()),
+    );
+
+    let mut updated = cached;
+    for lang_map in updated.index.values_mut() {
+        lang_map.retain(|key, _| !key.starts_with(&prefix));
+    }
+    updated
+        .value_conflicts
+        .retain(|conflict| !conflict.key.starts_with(&prefix));
+    updated
+        .errors
+        .retain(|entry| !contributing.contains(&entry.file));
+    updated
+        .warnings
+        .retain(|entry| !contributing.contains(&entry.file));
+    for file in &contributing {
+        updated.files.remove(file);
+        updated.parsed_files.remove(file);
+    }
+
+    for (root, tasks) in roots.iter().zip(tasks_per_root) {
+        index_files(
+            root,
+            tasks,
+            &ParsedFiles::new(),
+            Some(ns),
+            &mut updated,
+            &mut BTreeSet::new(),
+            &mut BTreeSet::new(),
+        );
+    }
+    updated
+        .value_conflicts
+        .sort_by(|a, b| (&a.lang, &a.key).cmp(&(&b.lang, &b.key)));
+    refresh_languages_and_namespaces(&mut updated, &roots);
+
+    let entries: BTreeMap<&str, BTreeMap<&str, &ResourceItem>> = updated
+        .index
+        .iter()
+        .filter_map(|(lang, lang_map)| {
+            let keys: BTreeMap<&str, &ResourceItem> = lang_map
+                .iter()
+                .filter(|(key, _)| key.starts_with(&prefix))
+                .map(|(key, item)| (key.as_str(), item))
+                .collect();
+            (!keys.is_empty()).then_some((lang.as_str(), keys))
+        })
+        .collect();
+    let response = serde_json::json!({
+        "success": true,
+        "needs_rebuild": false,
+        "entries": entries
+    });
+
+    cache.set(params.cache_key, updated);
+    Ok(response)
+}
+
+/// Report keys in one language whose value is empty or longer than `long_threshold`
+/// characters, for localization QA (UI overflow risk, forgotten translations).
+pub fn value_outliers(params: ValueOutliersParams, cache: &IndexCache) -> Result<Value> {
+    let Some(cached) = cache.get(&params.cache_key) else {
+        return Ok(needs_rebuild());
+    };
+
+    let mut empty: Vec<String> = Vec::new();
+    let mut too_long: Vec<(String, usize)> = Vec::new();
+    if let Some(lang_map) = cached.index.get(&params.lang) {
+        for (key, item) in lang_map {
+            let value = item.value.as_deref().unwrap_or("");
+            let length = value.chars().count();
+            if value.trim().is_empty() {
+                empty.push(key.clone());
+            } else if length > params.long_threshold {
+                too_long.push((key.clone(), length));
+            }
+        }
+    }
+    empty.sort();
+    too_long.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let too_long: Vec<Value> = too_long
+        .into_iter()
+        .map(|(key, length)| serde_json::json!({ "key": key, "length": length }))
+        .collect();
+
+    Ok(serde_json::json!({
+        "success": true,
+        "empty": empty,
+        "too_long": too_long
+    }))
+}
+
+/// Per language, the file holding most of the keys of `namespace`: where a new key
+/// of that namespace belongs. Languages without any key of it are left out.
+pub(crate) fn namespace_files(index: &IndexResult, namespace: &str) -> BTreeMap<String, String> {
+    let prefix = format!("{}:", namespace);
+    let mut files = BTreeMap::new();
+    for (lang, lang_map) in &index.index {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for (key, item) in lang_map {
+            if let (true, Some(file)) = (key.starts_with(&prefix), item.file.as_deref()) {
+                *counts.entry(file).or_default() += 1;
+            }
+        }
+        // Ties go to the first path in order, keeping the choice stable.
+        let best =
+            counts.into_iter().fold(
+                None,
+                |best: Option<(&str, usize)>, (file, count)| match best {
+                    Some((_, best_count)) if best_count >= count => best,
+                    _ => Some((file, count)),
+                },
+            );
+        if let Some((file, _)) = best {
+            files.insert(lang.clone(), file.to_string());
+        }
+    }
+    files
+}
+
+/// Keys whose value references `placeholder`, grouped by language, so a placeholder
+/// rename can be checked for impact across every translation.
+pub fn keys_using_placeholder(
+    params: KeysUsingPlaceholderParams,
+    cache: &IndexCache,
+) -> Result<Value> {
+    let Some(cached) = cache.get(&params.cache_key) else {
+        return Ok(needs_rebuild());
+    };
+
+    let mut by_lang: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (lang, lang_map) in &cached.index {
+        let mut keys: Vec<&str> = lang_map
+            .iter()
+            .filter(|(_, item)| {
+                item.value
+                    .as_deref()
+                    .is_some_and(|v| extract_placeholders(v).contains(&params.placeholder))
+            })
+            .map(|(key, _)| key.as_str())
+            .collect();
+        if keys.is_empty() {
+            continue;
+        }
+        keys.sort_unstable();
+        by_lang.insert(lang, keys);
+    }
+
+    Ok(serde_json::json!({
+        "success": true,
+        "keys": by_lang
     }))
 }
 
@@ -569,7 +1514,11 @@ mod tests {
             files: HashMap::new(),
             languages: vec![],
             errors: vec![],
+            warnings: vec![],
             namespaces: vec![],
+            namespace_roots: HashMap::new(),
+            value_conflicts: vec![],
+            parsed_files: HashMap::new(),
         };
         cache.set("k".to_string(), sample.clone());
 
@@ -585,4 +1534,110 @@ mod tests {
         cache.set("k2".to_string(), sample);
         assert!(cache.get("k2").is_some());
     }
+
+    #[test]
+    fn index_cache_evicts_least_recently_used_entry() {
+        let cache = IndexCache::with_capacity(2);
+        cache.set("a".to_string(), IndexResult::default());
+        cache.set("b".to_string(), IndexResult::default());
+        // Touch "a" so "b" becomes the least recently used entry.
+        assert!(cache.get("a").is_some());
+        cache.set("c".to_string(), IndexResult::default());
+
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("c").is_some());
+    }
+
+    fn item(value: &str) -> ResourceItem {
+        ResourceItem {
+            value: Some(value.to_string()),
+            file: None,
+            priority: 30,
+            lnum: None,
+        }
+    }
+
+    #[test]
+    fn value_outliers_reports_empty_and_long_values() {
+        let cache = IndexCache::new();
+        let mut en = HashMap::new();
+        en.insert("common:ok".to_string(), item("Save"));
+        en.insert("common:blank".to_string(), item(""));
+        en.insert(
+            "common:banner".to_string(),
+            item("This announcement is far too long"),
+        );
+        let mut index = HashMap::new();
+        index.insert("en".to_string(), en);
+        cache.set(
+            "k".to_string(),
+            IndexResult {
+                index,
+                files: HashMap::new(),
+                languages: vec!["en".to_string()],
+                errors: vec![],
+                warnings: vec![],
+                namespaces: vec!["common".to_string()],
+                namespace_roots: HashMap::new(),
+                value_conflicts: vec![],
+                parsed_files: HashMap::new(),
+            },
+        );
+
+        let result = value_outliers(
+            ValueOutliersParams {
+                cache_key: "k".to_string(),
+                lang: "en".to_string(),
+                long_threshold: 10,
+            },
+            &cache,
+        )
+        .expect("value_outliers should succeed");
+
+        assert_eq!(result["empty"], serde_json::json!(["common:blank"]));
+        assert_eq!(
+            result["too_long"],
+            serde_json::json!([{ "key": "common:banner", "length": 33 }])
+        );
+    }
+
+    #[test]
+    fn keys_using_placeholder_groups_keys_by_language() {
+        let cache = IndexCache::new();
+        let mut en = HashMap::new();
+        en.insert("common:greet".to_string(), item("Hello {{name}}"));
+        en.insert("common:bye".to_string(), item("Bye {{name}}"));
+        en.insert("common:count".to_string(), item("{{count}} items"));
+        let mut de = HashMap::new();
+        de.insert("common:greet".to_string(), item("Hallo {name}"));
+        de.insert("common:bye".to_string(), item("Tschüss"));
+        let mut index = HashMap::new();
+        index.insert("en".to_string(), en);
+        index.insert("de".to_string(), de);
+        cache.set(
+            "k".to_string(),
+            IndexResult {
+                index,
+                ..Default::default()
+            },
+        );
+
+        let result = keys_using_placeholder(
+            KeysUsingPlaceholderParams {
+                cache_key: "k".to_string(),
+                placeholder: "name".to_string(),
+            },
+            &cache,
+        )
+        .expect("keys_using_placeholder should succeed");
+
+        assert_eq!(
+            result["keys"],
+            serde_json::json!({
+                "de": ["common:greet"],
+                "en": ["common:bye", "common:greet"]
+            })
+        );
+    }
 }
@@ -1,14 +1,160 @@
 use anyhow::{Context, Result};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::path::Path;
 
-/// Read and parse a JSON file.
-pub fn read_json_file(path: &Path) -> Result<Value> {
+/// Extensions of resource files. `.jsonc` and `.json5` may contain comments and
+/// trailing commas; `.toml` catalogs are converted to the equivalent JSON value.
+const RESOURCE_EXTENSIONS: [&str; 4] = ["json", "jsonc", "json5", "toml"];
+
+/// Extensions of gettext and Fluent catalogs, read only by their own root kinds.
+const CATALOG_EXTENSIONS: [&str; 2] = ["po", "ftl"];
+
+/// Whether `path` has a resource file extension. Does not touch the filesystem.
+pub fn is_resource_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| RESOURCE_EXTENSIONS.contains(&ext))
+}
+
+/// Whether `read_resource_file` accepts `path`: a resource file or a gettext/Fluent
+/// catalog. Does not touch the filesystem.
+pub fn is_readable_file(path: &Path) -> bool {
+    is_resource_file(path)
+        || path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| CATALOG_EXTENSIONS.contains(&ext))
+}
+
+/// A parsed resource file.
+#[derive(Debug)]
+pub struct ResourceContent {
+    pub value: Value,
+    /// Line of each leaf, keyed by its dot-joined path. Not filled for TOML files.
+    pub lines: HashMap<String, u32>,
+}
+
+/// Read and parse a resource file. An empty or whitespace-only file yields `None`
+/// rather than a parse error, since empty catalogs are common while scaffolding.
+/// Comments and trailing commas are accepted in `.jsonc`/`.json5` files, and in
+/// `.json` files too when `json_comments` is set. `.toml` files are parsed as TOML,
+/// gettext `.po` catalogs as one namespace per `msgctxt`, and Fluent `.ftl` files
+/// as flat message keys.
+pub fn read_resource_file(path: &Path, json_comments: bool) -> Result<Option<ResourceContent>> {
     let content =
         std::fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?;
+    let extension = path.extension().and_then(|e| e.to_str());
+    if extension == Some("po") {
+        if content.trim().is_empty() {
+            return Ok(None);
+        }
+        // Catalogs are named after their language, e.g. `de.po`.
+        let lang = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        let (value, lines) = super::gettext::parse(&content, lang)
+            .with_context(|| format!("failed to parse {:?}", path))?;
+        return Ok(Some(ResourceContent { value, lines }));
+    }
+    if extension == Some("ftl") {
+        if content.trim().is_empty() {
+            return Ok(None);
+        }
+        let (value, lines) = super::fluent::parse(&content);
+        return Ok(Some(ResourceContent { value, lines }));
+    }
+    if extension == Some("toml") {
+        if content.trim().is_empty() {
+            return Ok(None);
+        }
+        let value =
+            super::toml::parse(&content).with_context(|| format!("failed to parse {:?}", path))?;
+        return Ok(Some(ResourceContent {
+            value,
+            lines: HashMap::new(),
+        }));
+    }
+    let lenient = json_comments || matches!(extension, Some("jsonc" | "json5"));
+    let content = if lenient {
+        strip_json_comments(&content)
+    } else {
+        content
+    };
+    if content.trim().is_empty() {
+        return Ok(None);
+    }
     let value: Value =
         serde_json::from_str(&content).with_context(|| format!("failed to parse {:?}", path))?;
-    Ok(value)
+    // Comments were blanked in place, so leaf positions match the file on disk.
+    let lines = crate::scan::leaf_lines(&content).unwrap_or_default();
+    Ok(Some(ResourceContent { value, lines }))
+}
+
+/// Blank out `//` and `/* */` comments and trailing commas outside strings. Removed
+/// text becomes spaces (newlines are kept), so positions still match the file.
+fn strip_json_comments(source: &str) -> String {
+    let mut out: Vec<char> = Vec::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut in_string = false;
+    // Index in `out` of a comma that is trailing if only whitespace follows
+    // before a closing bracket.
+    let mut pending_comma: Option<usize> = None;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => {
+                    if let Some(escaped) = chars.next() {
+                        out.push(escaped);
+                    }
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                while let Some(&next) = chars.peek() {
+                    if next == '\n' {
+                        break;
+                    }
+                    chars.next();
+                    out.push(' ');
+                }
+                out.push(' ');
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                out.extend([' ', ' ']);
+                let mut prev = '\0';
+                for next in chars.by_ref() {
+                    out.push(if next == '\n' { '\n' } else { ' ' });
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+            }
+            _ => {
+                if c == '}' || c == ']' {
+                    if let Some(index) = pending_comma {
+                        out[index] = ' ';
+                    }
+                }
+                if !c.is_whitespace() {
+                    pending_comma = None;
+                }
+                if c == ',' {
+                    pending_comma = Some(out.len());
+                }
+                if c == '"' {
+                    in_string = true;
+                }
+                out.push(c);
+            }
+        }
+    }
+    out.into_iter().collect()
 }
 
 /// Get the modification time of a file as nanoseconds since UNIX epoch.
@@ -25,3 +171,20 @@ pub fn file_mtime(path: &Path) -> Result<u64> {
         .saturating_mul(1_000_000_000)
         .saturating_add(duration.subsec_nanos() as u64))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_json_comments_keeps_strings_and_positions() {
+        let source =
+            "{\n  // note\n  \"url\": \"http://x/*y*/\", /* a\n b */\n  \"list\": [1, 2,],\n}";
+        let stripped = strip_json_comments(source);
+
+        assert_eq!(stripped.lines().count(), source.lines().count());
+        let value: Value = serde_json::from_str(&stripped).expect("stripped source should parse");
+        assert_eq!(value["url"], "http://x/*y*/");
+        assert_eq!(value["list"], serde_json::json!([1, 2]));
+    }
+}
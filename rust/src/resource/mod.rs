@@ -1,3 +1,6 @@
 pub mod discovery;
+mod fluent;
+mod gettext;
 pub mod index;
 pub mod io;
+mod toml;
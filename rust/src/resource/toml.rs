@@ -0,0 +1,571 @@
+//! Reader for a subset of TOML, enough for translation catalogs.
+//!
+//! This is not a full TOML parser; the `toml` crate is not among the crate's
+//! dependencies. Supported: tables (`[a.b]`), bare/quoted/dotted keys, basic and
+//! literal strings (including multi-line forms), inline tables, arrays, integers,
+//! floats and booleans. Anything else is an error rather than a guess: arrays of
+//! tables (`[[a]]`) and offset/local dates and times are rejected, as are
+//! duplicate keys and redefined tables.
+
+use anyhow::{Result, bail};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+/// Parse TOML into the same shape `serde_json` produces for an equivalent JSON
+/// catalog, so it can be flattened like any other resource file.
+pub fn parse(source: &str) -> Result<Value> {
+    let mut parser = Parser {
+        chars: source.chars().collect(),
+        pos: 0,
+        line: 1,
+    };
+    parser.document()
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn starts_with(&self, text: &str) -> bool {
+        text.chars()
+            .enumerate()
+            .all(|(i, c)| self.peek_at(i) == Some(c))
+    }
+
+    fn error<T>(&self, message: &str) -> Result<T> {
+        bail!("line {}: {}", self.line, message)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        if self.peek() == Some(expected) {
+            self.bump();
+            Ok(())
+        } else {
+            self.error(&format!("expected '{}'", expected))
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.bump();
+        }
+    }
+
+    fn skip_comment(&mut self) {
+        if self.peek() == Some('#') {
+            while self.peek().is_some_and(|c| c != '\n') {
+                self.bump();
+            }
+        }
+    }
+
+    /// Skip whitespace, newlines and comments (between statements and inside arrays).
+    fn skip_trivia(&mut self) {
+        loop {
+            self.skip_spaces();
+            self.skip_comment();
+            match self.peek() {
+                Some('\n') | Some('\r') => {
+                    self.bump();
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// After a statement only a comment may follow on the same line.
+    fn end_of_line(&mut self) -> Result<()> {
+        self.skip_spaces();
+        self.skip_comment();
+        match self.peek() {
+            None | Some('\n') => Ok(()),
+            Some('\r') if self.peek_at(1) == Some('\n') => Ok(()),
+            Some(_) => self.error("unexpected text after value"),
+        }
+    }
+
+    fn document(&mut self) -> Result<Value> {
+        let mut root = Map::new();
+        let mut table: Vec<String> = Vec::new();
+        // Tables defined by a header, a dotted key or an inline table. Tables only
+        // created implicitly (`a` for `[a.b]`) may still get their own header.
+        let mut defined: HashSet<Vec<String>> = HashSet::new();
+        loop {
+            self.skip_trivia();
+            match self.peek() {
+                None => break,
+                Some('[') => {
+                    self.bump();
+                    if self.peek() == Some('[') {
+                        return self.error("arrays of tables are not supported");
+                    }
+                    self.skip_spaces();
+                    table = self.key_path()?;
+                    self.skip_spaces();
+                    self.expect(']')?;
+                    self.end_of_line()?;
+                    if !defined.insert(table.clone()) {
+                        return self
+                            .error(&format!("table '{}' is defined twice", table.join(".")));
+                    }
+                    if table_at(&mut root, &table).is_none() {
+                        return self.error(&format!("'{}' is not a table", table.join(".")));
+                    }
+                }
+                Some(_) => {
+                    let (path, value) = self.key_value()?;
+                    self.end_of_line()?;
+                    let Some(target) = table_at(&mut root, &table) else {
+                        return self.error(&format!("'{}' is not a table", table.join(".")));
+                    };
+                    let depth = if value.is_object() {
+                        path.len()
+                    } else {
+                        path.len() - 1
+                    };
+                    for end in 1..=depth {
+                        defined.insert([table.as_slice(), &path[..end]].concat());
+                    }
+                    self.insert(target, &path, value)?;
+                }
+            }
+        }
+        Ok(Value::Object(root))
+    }
+
+    fn key_value(&mut self) -> Result<(Vec<String>, Value)> {
+        let path = self.key_path()?;
+        self.skip_spaces();
+        self.expect('=')?;
+        self.skip_spaces();
+        let value = self.value()?;
+        Ok((path, value))
+    }
+
+    fn insert(&self, table: &mut Map<String, Value>, path: &[String], value: Value) -> Result<()> {
+        let (last, parents) = path.split_last().expect("key path is never empty");
+        let Some(target) = table_at(table, parents) else {
+            return self.error(&format!("'{}' is not a table", parents.join(".")));
+        };
+        if target.contains_key(last) {
+            return self.error(&format!("duplicate key '{}'", path.join(".")));
+        }
+        target.insert(last.clone(), value);
+        Ok(())
+    }
+
+    fn key_path(&mut self) -> Result<Vec<String>> {
+        let mut path = vec![self.key()?];
+        loop {
+            self.skip_spaces();
+            if self.peek() != Some('.') {
+                return Ok(path);
+            }
+            self.bump();
+            self.skip_spaces();
+            path.push(self.key()?);
+        }
+    }
+
+    fn key(&mut self) -> Result<String> {
+        match self.peek() {
+            Some('"') => {
+                self.bump();
+                self.basic_string()
+            }
+            Some('\'') => {
+                self.bump();
+                self.literal_string()
+            }
+            _ => {
+                let start = self.pos;
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                {
+                    self.bump();
+                }
+                if self.pos == start {
+                    return self.error("expected a key");
+                }
+                Ok(self.chars[start..self.pos].iter().collect())
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        if self.starts_with("\"\"\"") {
+            self.pos += 3;
+            return self.multiline_basic_string().map(Value::String);
+        }
+        if self.starts_with("'''") {
+            self.pos += 3;
+            return self.multiline_literal_string().map(Value::String);
+        }
+        match self.peek() {
+            Some('"') => {
+                self.bump();
+                self.basic_string().map(Value::String)
+            }
+            Some('\'') => {
+                self.bump();
+                self.literal_string().map(Value::String)
+            }
+            Some('[') => {
+                self.bump();
+                self.array()
+            }
+            Some('{') => {
+                self.bump();
+                self.inline_table()
+            }
+            Some(_) => self.scalar(),
+            None => self.error("expected a value"),
+        }
+    }
+
+    fn array(&mut self) -> Result<Value> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_trivia();
+            if self.peek() == Some(']') {
+                self.bump();
+                return Ok(Value::Array(items));
+            }
+            items.push(self.value()?);
+            self.skip_trivia();
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                }
+                Some(']') => {}
+                _ => return self.error("expected ',' or ']' in array"),
+            }
+        }
+    }
+
+    fn inline_table(&mut self) -> Result<Value> {
+        let mut table = Map::new();
+        self.skip_spaces();
+        if self.peek() == Some('}') {
+            self.bump();
+            return Ok(Value::Object(table));
+        }
+        loop {
+            self.skip_spaces();
+            let (path, value) = self.key_value()?;
+            self.insert(&mut table, &path, value)?;
+            self.skip_spaces();
+            match self.bump() {
+                Some(',') => {}
+                Some('}') => return Ok(Value::Object(table)),
+                _ => return self.error("expected ',' or '}' in inline table"),
+            }
+        }
+    }
+
+    fn scalar(&mut self) -> Result<Value> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| !matches!(c, ',' | ']' | '}' | '#' | '\n' | '\r'))
+        {
+            self.bump();
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        let text = text.trim_end();
+        self.pos = start + text.chars().count();
+        match text {
+            "true" => return Ok(Value::Bool(true)),
+            "false" => return Ok(Value::Bool(false)),
+            "" => return self.error("expected a value"),
+            _ => {}
+        }
+        let digits = text.replace('_', "");
+        if let Ok(number) = digits.parse::<i64>() {
+            return Ok(Value::from(number));
+        }
+        if let Some(number) = digits
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+        {
+            return Ok(Value::Number(number));
+        }
+        if text.starts_with(|c: char| c.is_ascii_digit()) && text.contains([':', '-']) {
+            return self.error(&format!("dates and times are not supported: '{}'", text));
+        }
+        self.error(&format!("invalid value '{}'", text))
+    }
+
+    /// Body of a `"..."` string; the opening quote is already consumed.
+    fn basic_string(&mut self) -> Result<String> {
+        let mut out = String::new();
+        loop {
+            // Stop before a newline so the error names the string's own line.
+            let Some(c) = self.peek().filter(|&c| c != '\n') else {
+                return self.error("unterminated string");
+            };
+            self.bump();
+            match c {
+                '"' => return Ok(out),
+                '\\' => out.push(self.escape()?),
+                c => out.push(c),
+            }
+        }
+    }
+
+    /// Body of a `'...'` string; the opening quote is already consumed.
+    fn literal_string(&mut self) -> Result<String> {
+        let mut out = String::new();
+        loop {
+            // Stop before a newline so the error names the string's own line.
+            let Some(c) = self.peek().filter(|&c| c != '\n') else {
+                return self.error("unterminated string");
+            };
+            self.bump();
+            match c {
+                '\'' => return Ok(out),
+                c => out.push(c),
+            }
+        }
+    }
+
+    fn multiline_basic_string(&mut self) -> Result<String> {
+        self.trim_leading_newline();
+        let mut out = String::new();
+        loop {
+            if self.starts_with("\"\"\"") {
+                self.pos += 3;
+                return Ok(out);
+            }
+            match self.bump() {
+                Some('\\') => {
+                    // A backslash at the end of a line trims the newline and the
+                    // whitespace that follows it.
+                    let rest = self.pos;
+                    self.skip_spaces();
+                    if matches!(self.peek(), Some('\n' | '\r')) {
+                        self.skip_trivia_whitespace();
+                    } else {
+                        self.pos = rest;
+                        out.push(self.escape()?);
+                    }
+                }
+                Some('\r') if self.peek() == Some('\n') => {}
+                Some(c) => out.push(c),
+                None => return self.error("unterminated multi-line string"),
+            }
+        }
+    }
+
+    fn multiline_literal_string(&mut self) -> Result<String> {
+        self.trim_leading_newline();
+        let mut out = String::new();
+        loop {
+            if self.starts_with("'''") {
+                self.pos += 3;
+                return Ok(out);
+            }
+            match self.bump() {
+                Some('\r') if self.peek() == Some('\n') => {}
+                Some(c) => out.push(c),
+                None => return self.error("unterminated multi-line string"),
+            }
+        }
+    }
+
+    fn trim_leading_newline(&mut self) {
+        if self.starts_with("\r\n") {
+            self.pos += 1;
+        }
+        if self.peek() == Some('\n') {
+            self.bump();
+        }
+    }
+
+    fn skip_trivia_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn escape(&mut self) -> Result<char> {
+        let c = match self.bump() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some('u') => return self.unicode_escape(4),
+            Some('U') => return self.unicode_escape(8),
+            _ => return self.error("invalid escape sequence"),
+        };
+        Ok(c)
+    }
+
+    fn unicode_escape(&mut self, len: usize) -> Result<char> {
+        let digits: String = (0..len).filter_map(|_| self.bump()).collect();
+        match u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+        {
+            Some(c) if digits.len() == len => Ok(c),
+            _ => self.error("invalid unicode escape"),
+        }
+    }
+}
+
+/// Walk (creating as needed) the nested table at `path`. `None` when a segment
+/// already holds a non-table value.
+fn table_at<'a>(
+    root: &'a mut Map<String, Value>,
+    path: &[String],
+) -> Option<&'a mut Map<String, Value>> {
+    let mut current = root;
+    for segment in path {
+        current = current
+            .entry(segment.clone())
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()?;
+    }
+    Some(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_tables_keys_and_strings() {
+        let source = r#"
+# Catalog
+title = "Hello \"world\"" # trailing comment
+nav.home = 'C:\path'
+count = 1_000
+
+[errors."not-found"]
+body = """
+Line one
+Line two"""
+raw = '''a\nb'''
+flags = [true, false,]
+meta = { owner = "team", level = 2 }
+"#;
+        let value = parse(source).expect("toml should parse");
+
+        assert_eq!(
+            value,
+            json!({
+                "title": "Hello \"world\"",
+                "nav": { "home": "C:\\path" },
+                "count": 1000,
+                "errors": {
+                    "not-found": {
+                        "body": "Line one\nLine two",
+                        "raw": "a\\nb",
+                        "flags": [true, false],
+                        "meta": { "owner": "team", "level": 2 }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_duplicates_and_reports_line() {
+        let err = parse("a = \"x\"\na = \"y\"\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{}", err);
+        assert!(err.to_string().contains("duplicate key 'a'"), "{}", err);
+
+        assert!(parse("a = \"x\"\n[a]\n").is_err());
+    }
+
+    #[test]
+    fn rejects_redefined_tables() {
+        let cases = [
+            (
+                "[a]\nx = \"1\"\n[a]\ny = \"2\"\n",
+                "line 3: table 'a' is defined twice",
+            ),
+            (
+                "a.b = \"1\"\n[a]\nc = \"2\"\n",
+                "line 2: table 'a' is defined twice",
+            ),
+            (
+                "a = { b = \"1\" }\n[a]\n",
+                "line 2: table 'a' is defined twice",
+            ),
+            (
+                "[a]\nb.c = \"1\"\n[a.b]\n",
+                "line 3: table 'a.b' is defined twice",
+            ),
+        ];
+        for (source, expected) in cases {
+            let err = parse(source).expect_err(source);
+            assert!(err.to_string().contains(expected), "{}: {}", source, err);
+        }
+
+        // Implicitly created parents and sub-tables of dotted keys can be extended.
+        let value =
+            parse("[a.b]\nx = \"1\"\n[a]\ny = \"2\"\n[a.c]\nd.e = \"3\"\n[a.c.d.f]\ng = \"4\"\n")
+                .expect("toml should parse");
+        assert_eq!(
+            value,
+            json!({ "a": { "b": { "x": "1" }, "y": "2", "c": { "d": { "e": "3", "f": { "g": "4" } } } } })
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_and_malformed_input() {
+        let cases = [
+            (
+                "[[items]]\nname = \"x\"\n",
+                "line 1: arrays of tables are not supported",
+            ),
+            (
+                "a = \"x\"\nday = 1979-05-27\n",
+                "line 2: dates and times are not supported",
+            ),
+            (
+                "at = 07:32:00\n",
+                "line 1: dates and times are not supported",
+            ),
+            ("a = \"unterminated\n", "line 1"),
+            ("a = \"bad \\q escape\"\n", "line 1"),
+            ("a \"x\"\n", "line 1"),
+            (
+                "a = \"x\" b = \"y\"\n",
+                "line 1: unexpected text after value",
+            ),
+            ("a = yes\n", "line 1: invalid value 'yes'"),
+        ];
+        for (source, expected) in cases {
+            let err = parse(source).expect_err(source);
+            assert!(err.to_string().contains(expected), "{}: {}", source, err);
+        }
+    }
+}
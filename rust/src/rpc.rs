@@ -135,24 +135,39 @@ impl Transport {
     }
 
     pub fn send_response(&self, response: &Response) -> Result<()> {
-        let json = serde_json::to_string(response)?;
-        let stdout = io::stdout();
-        let mut handle = stdout.lock();
-        writeln!(handle, "{}", json)?;
-        handle.flush()?;
-        Ok(())
+        self.output().send_response(response)
+    }
+
+    /// Writer half, for handlers that respond from other threads.
+    pub fn output(&self) -> Output {
+        Output
+    }
+}
+
+/// Writes JSON-RPC messages to stdout, one per line. Each message is written under
+/// the stdout lock, so it can be shared freely between threads.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Output;
+
+impl Output {
+    pub fn send_response(&self, response: &Response) -> Result<()> {
+        write_message(response)
     }
 
     pub fn send_notification(&self, notification: &Notification) -> Result<()> {
-        let json = serde_json::to_string(notification)?;
-        let stdout = io::stdout();
-        let mut handle = stdout.lock();
-        writeln!(handle, "{}", json)?;
-        handle.flush()?;
-        Ok(())
+        write_message(notification)
     }
 }
 
+fn write_message<T: Serialize>(message: &T) -> Result<()> {
+    let json = serde_json::to_string(message)?;
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    writeln!(handle, "{}", json)?;
+    handle.flush()?;
+    Ok(())
+}
+
 impl Default for Transport {
     fn default() -> Self {
         Self::new()
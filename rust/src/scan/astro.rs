@@ -0,0 +1,174 @@
+use unicode_width::UnicodeWidthChar;
+
+/// Whitespace occupying the same display width as `c`, so that columns reported by
+/// the parser still match the original file.
+fn blank(c: char) -> char {
+    match c {
+        '\n' | '\r' | '\t' => c,
+        _ if c.is_ascii() => ' ',
+        _ => match c.width().unwrap_or(0) {
+            0 => '\u{feff}',
+            2 => '\u{3000}',
+            _ => '\u{a0}',
+        },
+    }
+}
+
+fn is_fence(line: &str) -> bool {
+    line.trim_end() == "---"
+}
+
+/// Rewrite an Astro component (`---` frontmatter script plus an HTML-like template
+/// with `{...}` expressions) into a TSX module with the same line/column layout:
+/// frontmatter is kept verbatim, each template expression becomes its own `;expr`
+/// statement, and everything else is blanked.
+pub(super) fn to_module_source(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut rest = source;
+
+    // Frontmatter: an opening `---` line (after optional blank lines) up to the
+    // closing `---` line.
+    let leading = source.len() - source.trim_start().len();
+    let first_line_end = source[leading..]
+        .find('\n')
+        .map_or(source.len(), |i| leading + i);
+    if is_fence(&source[leading..first_line_end]) {
+        let mut pos = first_line_end;
+        let mut close = None;
+        while pos < source.len() {
+            let line_start = pos + 1;
+            let line_end = source[line_start..]
+                .find('\n')
+                .map_or(source.len(), |i| line_start + i);
+            if is_fence(&source[line_start.min(source.len())..line_end]) {
+                close = Some((line_start, line_end));
+                break;
+            }
+            pos = line_end;
+        }
+        if let Some((close_start, close_end)) = close {
+            out.extend(source[..first_line_end].chars().map(blank));
+            out.push_str(&source[first_line_end..close_start]);
+            out.extend(source[close_start..close_end].chars().map(blank));
+            rest = &source[close_end..];
+        }
+    }
+
+    blank_template(rest, &mut out);
+    out
+}
+
+/// Copy template expressions and blank everything else. `<script>`/`<style>` bodies
+/// and HTML comments are blanked as a whole, and quoted attribute values are not
+/// searched for expressions.
+fn blank_template(template: &str, out: &mut String) {
+    let mut chars = template.char_indices().peekable();
+    let mut in_tag = false;
+
+    while let Some((i, c)) = chars.next() {
+        let tail = &template[i..];
+        if !in_tag {
+            let skip_until = if tail.starts_with("<!--") {
+                Some("-->")
+            } else if starts_with_tag(tail, "script") {
+                Some("</script>")
+            } else if starts_with_tag(tail, "style") {
+                Some("</style>")
+            } else {
+                None
+            };
+            if let Some(end_marker) = skip_until {
+                // Blank through the end marker (or to the end of the template).
+                let end = tail
+                    .find(end_marker)
+                    .map_or(tail.len(), |p| p + end_marker.len());
+                out.push(blank(c));
+                while let Some(&(j, next)) = chars.peek() {
+                    if j >= i + end {
+                        break;
+                    }
+                    out.push(blank(next));
+                    chars.next();
+                }
+                continue;
+            }
+        }
+
+        match c {
+            '{' => {
+                out.push(';');
+                let mut depth = 1;
+                for (_, next) in chars.by_ref() {
+                    match next {
+                        '{' => depth += 1,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                    if depth == 0 {
+                        out.push(' ');
+                        break;
+                    }
+                    out.push(next);
+                }
+            }
+            '<' if tail[1..].starts_with(|n: char| n.is_ascii_alphabetic() || n == '/') => {
+                in_tag = true;
+                out.push(' ');
+            }
+            '>' if in_tag => {
+                in_tag = false;
+                out.push(' ');
+            }
+            '"' | '\'' if in_tag => {
+                out.push(' ');
+                for (_, next) in chars.by_ref() {
+                    out.push(blank(next));
+                    if next == c {
+                        break;
+                    }
+                }
+            }
+            _ => out.push(blank(c)),
+        }
+    }
+}
+
+fn starts_with_tag(text: &str, name: &str) -> bool {
+    let Some(rest) = text.strip_prefix('<') else {
+        return false;
+    };
+    rest.get(..name.len())
+        .is_some_and(|tag| tag.eq_ignore_ascii_case(name))
+        && rest[name.len()..].starts_with(|c: char| c == '>' || c.is_ascii_whitespace())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_frontmatter_and_template_expressions_in_place() {
+        let source = "---\nconst x = t(\"a\");\n---\n<p title=\"{no}\">{t(\"b\")}</p>\n";
+        let out = to_module_source(source);
+
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0].trim(), "");
+        assert_eq!(lines[1], "const x = t(\"a\");");
+        assert_eq!(lines[2].trim(), "");
+        assert_eq!(
+            lines[3].find("t(\"b\")"),
+            source.lines().nth(3).unwrap().find("t(\"b\")")
+        );
+        assert!(!lines[3].contains("no"));
+    }
+
+    #[test]
+    fn blanks_style_blocks_and_wide_text_by_display_width() {
+        let source = "<style>p { color: red; }</style>\n<p>日本 {t(\"x\")}</p>\n";
+        let out = to_module_source(source);
+
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0].trim(), "");
+        assert_eq!(lines[1], "   \u{3000}\u{3000} ;t(\"x\")     ");
+    }
+}
@@ -1,10 +1,32 @@
-use swc_common::{SourceMap, Spanned};
+use swc_common::{SourceMap, SourceMapper, Spanned};
 use swc_ecma_ast::*;
 
-use super::const_eval::{ConstBinding, eval_string_exprs};
+use super::const_eval::{
+    ConstBinding, eval_string_expr, eval_string_exprs, key_pattern, wtf8_to_string,
+};
 use super::parser::span_to_loc;
 use super::scope::{NamespaceScope, is_translation_hook};
-use super::{Range, ScanItem};
+use super::{CallShape, MessageComponent, Range, ScanItem, UnresolvedCall};
+
+/// Project conventions for recognising translation calls beyond plain `t(...)`.
+#[derive(Default)]
+pub(super) struct CallConfig<'a> {
+    /// JSX components carrying a key in an attribute.
+    pub(super) message_components: &'a [MessageComponent],
+    /// Replaces the built-in hook names when set.
+    pub(super) hook_names: Option<&'a [String]>,
+    /// Replaces `DEFAULT_T_OBJECT_NAMES` when set.
+    pub(super) t_object_names: Option<&'a [String]>,
+    /// Namespace separator in keys; `:` when unset, disabled when empty.
+    pub(super) ns_separator: Option<&'a str>,
+    /// Key path separator; `.` when unset, disabled when empty.
+    pub(super) key_separator: Option<&'a str>,
+    /// Attach the argument shape of each call to its items.
+    pub(super) include_call_shape: bool,
+}
+
+/// Objects whose `.t(...)` method is a translation call.
+const DEFAULT_T_OBJECT_NAMES: &[&str] = &["i18n", "i18next"];
 
 pub(super) fn extract_calls(
     module: &Module,
@@ -13,20 +35,54 @@ pub(super) fn extract_calls(
     scopes: &[NamespaceScope],
     fallback_namespace: &str,
     range: &Option<Range>,
+    config: &CallConfig,
 ) -> Vec<ScanItem> {
+    extract_calls_with_unresolved(
+        module,
+        cm,
+        const_bindings,
+        scopes,
+        fallback_namespace,
+        range,
+        config,
+    )
+    .0
+}
+
+/// Like `extract_calls`, but also returns the translation calls whose key could not
+/// be evaluated statically.
+pub(super) fn extract_calls_with_unresolved(
+    module: &Module,
+    cm: &SourceMap,
+    const_bindings: &[ConstBinding],
+    scopes: &[NamespaceScope],
+    fallback_namespace: &str,
+    range: &Option<Range>,
+    config: &CallConfig,
+) -> (Vec<ScanItem>, Vec<UnresolvedCall>) {
     let mut items = Vec::new();
+    let mut unresolved = Vec::new();
     let mut visitor = CallVisitor {
         cm,
         const_bindings,
         scopes,
         fallback_namespace,
         range,
+        message_components: config.message_components,
+        hook_names: config.hook_names,
+        t_object_names: config.t_object_names,
+        ns_separator: config.ns_separator.unwrap_or(":"),
+        key_separator: config.key_separator.unwrap_or("."),
+        include_call_shape: config.include_call_shape,
         items: &mut items,
+        unresolved: &mut unresolved,
+        loop_bindings: Vec::new(),
+        next_fallback_group: 0,
     };
     for item in &module.body {
         visitor.visit_module_item(item);
     }
-    items
+    (items, unresolved)
 }
 
 struct CallVisitor<'a> {
@@ -35,10 +91,184 @@ struct CallVisitor<'a> {
     scopes: &'a [NamespaceScope],
     fallback_namespace: &'a str,
     range: &'a Option<Range>,
+    message_components: &'a [MessageComponent],
+    hook_names: Option<&'a [String]>,
+    t_object_names: Option<&'a [String]>,
+    ns_separator: &'a str,
+    key_separator: &'a str,
+    include_call_shape: bool,
     items: &'a mut Vec<ScanItem>,
+    unresolved: &'a mut Vec<UnresolvedCall>,
+    /// Loop variables bound to the elements of a static string array, innermost last:
+    /// `["a", "b"].forEach((k) => t(k))` or `for (const k of ["a", "b"]) t(k)`.
+    loop_bindings: Vec<(String, Vec<String>)>,
+    /// Id for the next fallback-key array.
+    next_fallback_group: u32,
+}
+
+/// Names of array methods whose callback receives each element as its first argument.
+fn is_element_callback_method(name: &str) -> bool {
+    matches!(
+        name,
+        "forEach" | "map" | "flatMap" | "filter" | "some" | "every" | "find"
+    )
+}
+
+/// Names of the properties of the first options object among `args`, in source
+/// order: `["count", "ns"]` for `t("k", { count, ns: "x" })`. The flag is false
+/// when the object also has properties that can't be named statically (computed
+/// `[name]: v` other than a string literal, or `...spread`), which are skipped.
+fn option_keys(args: &[ExprOrSpread]) -> (Vec<String>, bool) {
+    let Some(obj) = args.iter().skip(1).find_map(|arg| match arg.expr.as_ref() {
+        Expr::Object(obj) if arg.spread.is_none() => Some(obj),
+        _ => None,
+    }) else {
+        return (Vec::new(), true);
+    };
+    let mut complete = true;
+    let keys = obj
+        .props
+        .iter()
+        .filter_map(|prop| {
+            let PropOrSpread::Prop(prop) = prop else {
+                complete = false;
+                return None;
+            };
+            match prop.as_ref() {
+                Prop::Shorthand(ident) => Some(ident.sym.to_string()),
+                Prop::KeyValue(kv) => match &kv.key {
+                    PropName::Ident(ident) => Some(ident.sym.to_string()),
+                    PropName::Str(s) => Some(wtf8_to_string(&s.value)),
+                    PropName::Computed(computed) => match computed.expr.as_ref() {
+                        Expr::Lit(Lit::Str(s)) => Some(wtf8_to_string(&s.value)),
+                        _ => {
+                            complete = false;
+                            None
+                        }
+                    },
+                    _ => None,
+                },
+                _ => None,
+            }
+        })
+        .collect();
+    (keys, complete)
+}
+
+/// Value of a plain `name: value` property in an options object literal.
+pub(super) fn option_prop<'b>(obj: &'b ObjectLit, name: &str) -> Option<&'b Expr> {
+    obj.props.iter().find_map(|prop| {
+        let PropOrSpread::Prop(prop) = prop else {
+            return None;
+        };
+        let Prop::KeyValue(kv) = prop.as_ref() else {
+            return None;
+        };
+        let matches = match &kv.key {
+            PropName::Ident(ident) => ident.sym.as_ref() == name,
+            PropName::Str(s) => s.value.as_wtf8().as_str() == Some(name),
+            _ => false,
+        };
+        matches.then_some(kv.value.as_ref())
+    })
+}
+
+/// Whether any options object among the trailing arguments sets `returnObjects: true`.
+fn has_return_objects(args: &[ExprOrSpread]) -> bool {
+    args.iter().skip(1).any(|arg| match arg.expr.as_ref() {
+        Expr::Object(obj) => matches!(
+            option_prop(obj, "returnObjects"),
+            Some(Expr::Lit(Lit::Bool(b))) if b.value
+        ),
+        _ => false,
+    })
+}
+
+fn first_param_name(expr: &Expr) -> Option<String> {
+    let pat = match expr {
+        Expr::Arrow(arrow) => arrow.params.first()?,
+        Expr::Fn(fn_expr) => &fn_expr.function.params.first()?.pat,
+        Expr::Paren(paren) => return first_param_name(&paren.expr),
+        _ => return None,
+    };
+    match pat {
+        Pat::Ident(ident) => Some(ident.sym.to_string()),
+        _ => None,
+    }
 }
 
 impl<'a> CallVisitor<'a> {
+    /// Every element of an array literal, when all of them are static strings.
+    fn static_array_values(&self, expr: &Expr) -> Option<Vec<String>> {
+        match expr {
+            Expr::Array(arr) => {
+                let (lnum, _, _) = span_to_loc(self.cm, arr.span);
+                arr.elems
+                    .iter()
+                    .map(|elem| match elem {
+                        Some(elem) if elem.spread.is_none() => {
+                            eval_string_expr(&elem.expr, lnum, self.const_bindings)
+                        }
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .filter(|values| !values.is_empty())
+            }
+            Expr::Paren(paren) => self.static_array_values(&paren.expr),
+            Expr::TsAs(ts_as) => self.static_array_values(&ts_as.expr),
+            Expr::TsSatisfies(ts_sat) => self.static_array_values(&ts_sat.expr),
+            Expr::TsConstAssertion(ts_const) => self.static_array_values(&ts_const.expr),
+            _ => None,
+        }
+    }
+
+    /// Keys of an i18next fallback-key array, `t(["common:a", "common:b"])`, each
+    /// located at its own element. Elements that are not static are skipped.
+    fn key_array_values(&self, expr: &Expr) -> Vec<(String, (u32, u32, u32))> {
+        let Expr::Array(arr) = expr else {
+            return Vec::new();
+        };
+        arr.elems
+            .iter()
+            .filter_map(|elem| {
+                let elem = elem.as_ref().filter(|elem| elem.spread.is_none())?;
+                let loc = span_to_loc(self.cm, elem.expr.span());
+                eval_string_expr(&elem.expr, loc.0, self.const_bindings).map(|value| (value, loc))
+            })
+            .collect()
+    }
+
+    /// Loop binding introduced by `[...].forEach((k) => ...)`-style calls.
+    fn array_callback_binding(&self, call: &CallExpr) -> Option<(String, Vec<String>)> {
+        let Callee::Expr(callee) = &call.callee else {
+            return None;
+        };
+        let Expr::Member(member) = callee.as_ref() else {
+            return None;
+        };
+        let MemberProp::Ident(prop) = &member.prop else {
+            return None;
+        };
+        if !is_element_callback_method(prop.sym.as_ref()) {
+            return None;
+        }
+        let name = first_param_name(&call.args.first()?.expr)?;
+        let values = self.static_array_values(&member.obj)?;
+        Some((name, values))
+    }
+
+    fn loop_binding_values(&self, expr: &Expr) -> Vec<String> {
+        let Expr::Ident(ident) = expr else {
+            return Vec::new();
+        };
+        self.loop_bindings
+            .iter()
+            .rev()
+            .find(|(name, _)| name == ident.sym.as_ref())
+            .map(|(_, values)| values.clone())
+            .unwrap_or_default()
+    }
+
     fn visit_module_item(&mut self, item: &ModuleItem) {
         match item {
             ModuleItem::Stmt(stmt) => self.visit_stmt(stmt),
@@ -97,7 +327,22 @@ impl<'a> CallVisitor<'a> {
             }
             Stmt::For(for_stmt) => self.visit_stmt(&for_stmt.body),
             Stmt::ForIn(for_in) => self.visit_stmt(&for_in.body),
-            Stmt::ForOf(for_of) => self.visit_stmt(&for_of.body),
+            Stmt::ForOf(for_of) => {
+                let name = match &for_of.left {
+                    ForHead::VarDecl(var) if var.decls.len() == 1 => match &var.decls[0].name {
+                        Pat::Ident(ident) => Some(ident.sym.to_string()),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                let binding = name.zip(self.static_array_values(&for_of.right));
+                let has_binding = binding.is_some();
+                self.loop_bindings.extend(binding);
+                self.visit_stmt(&for_of.body);
+                if has_binding {
+                    self.loop_bindings.pop();
+                }
+            }
             Stmt::While(while_stmt) => {
                 self.visit_expr(&while_stmt.test);
                 self.visit_stmt(&while_stmt.body);
@@ -137,17 +382,39 @@ impl<'a> CallVisitor<'a> {
                     }
                 }
             }
+            Decl::TsModule(ts_module) => {
+                if let Some(body) = &ts_module.body {
+                    self.visit_ts_namespace_body(body);
+                }
+            }
             _ => {}
         }
     }
 
+    fn visit_ts_namespace_body(&mut self, body: &TsNamespaceBody) {
+        match body {
+            TsNamespaceBody::TsModuleBlock(block) => {
+                for item in &block.body {
+                    self.visit_module_item(item);
+                }
+            }
+            TsNamespaceBody::TsNamespaceDecl(decl) => self.visit_ts_namespace_body(&decl.body),
+        }
+    }
+
     fn visit_expr(&mut self, expr: &Expr) {
         match expr {
             Expr::Call(call) => {
                 self.check_call(call);
+                let binding = self.array_callback_binding(call);
+                let has_binding = binding.is_some();
+                self.loop_bindings.extend(binding);
                 for arg in &call.args {
                     self.visit_expr(&arg.expr);
                 }
+                if has_binding {
+                    self.loop_bindings.pop();
+                }
                 if let Callee::Expr(callee) = &call.callee {
                     self.visit_expr(callee);
                 }
@@ -233,6 +500,7 @@ impl<'a> CallVisitor<'a> {
     }
 
     fn visit_jsx_element(&mut self, jsx: &JSXElement) {
+        self.check_message_component(&jsx.opening);
         for attr in &jsx.opening.attrs {
             if let JSXAttrOrSpread::JSXAttr(attr) = attr {
                 if let Some(JSXAttrValue::JSXExprContainer(container)) = &attr.value {
@@ -269,14 +537,16 @@ impl<'a> CallVisitor<'a> {
             Callee::Expr(expr) => match expr.as_ref() {
                 Expr::Ident(ident) => (ident.sym.to_string(), false),
                 Expr::Member(member) => {
-                    if let MemberProp::Ident(prop) = &member.prop {
-                        if prop.sym.as_ref() == "t" {
+                    let MemberProp::Ident(prop) = &member.prop else {
+                        return;
+                    };
+                    match (prop.sym.as_ref(), member.obj.as_ref()) {
+                        ("t", Expr::Ident(obj)) if self.is_t_object(obj.sym.as_ref()) => {
                             ("t".to_string(), true)
-                        } else {
-                            return;
                         }
-                    } else {
-                        return;
+                        // next-intl: `t.rich("key", {...})` / `t.markup("key", {...})`
+                        ("rich" | "markup", Expr::Ident(obj)) => (obj.sym.to_string(), false),
+                        _ => return,
                     }
                 }
                 _ => return,
@@ -284,7 +554,7 @@ impl<'a> CallVisitor<'a> {
             _ => return,
         };
 
-        if is_translation_hook(&func_name) {
+        if is_translation_hook(&func_name, self.hook_names) {
             return;
         }
         if !is_member_t && !self.is_translation_call(&func_name, call) {
@@ -302,13 +572,138 @@ impl<'a> CallVisitor<'a> {
             }
         }
 
-        let values = eval_string_exprs(&first_arg.expr, lnum, self.const_bindings);
+        let mut values = eval_string_exprs(&first_arg.expr, lnum, self.const_bindings);
         if values.is_empty() {
+            values = self.loop_binding_values(&first_arg.expr);
+        }
+        let mut keys: Vec<(String, (u32, u32, u32))> = values
+            .into_iter()
+            .map(|value| (value, (lnum, col, end_col)))
+            .collect();
+        let mut fallback_group = None;
+        if keys.is_empty() {
+            keys = self.key_array_values(&first_arg.expr);
+            if !keys.is_empty() {
+                fallback_group = Some(self.next_fallback_group);
+                self.next_fallback_group += 1;
+            }
+        }
+        if keys.is_empty() {
+            self.unresolved.push(UnresolvedCall {
+                lnum,
+                col,
+                end_col,
+                snippet: self
+                    .cm
+                    .span_to_snippet(first_arg.expr.span())
+                    .unwrap_or_default(),
+                pattern: key_pattern(&first_arg.expr, lnum, self.const_bindings),
+            });
             return;
         }
 
+        let default_value = call
+            .args
+            .get(1)
+            .and_then(|arg| self.default_value_arg(&arg.expr, lnum));
+        let return_objects = has_return_objects(&call.args);
+        let context = self.context_values(&call.args, lnum);
+        let has_options = call.args.iter().skip(1).any(|arg| {
+            arg.spread.is_some() || eval_string_expr(&arg.expr, lnum, self.const_bindings).is_none()
+        });
+        let call_shape = self.include_call_shape.then(|| {
+            let (option_keys, option_keys_complete) = option_keys(&call.args);
+            CallShape {
+                arg_count: call.args.len() as u32,
+                has_options,
+                option_keys,
+                option_keys_incomplete: !option_keys_complete,
+            }
+        });
+
+        for (value, (lnum, col, end_col)) in keys {
+            let (key, namespace, fallback, scope_hook_line) = self.resolve_namespace(&value, lnum);
+            self.items.push(ScanItem {
+                key,
+                raw: value,
+                namespace,
+                lnum,
+                col,
+                end_col,
+                value_range: None,
+                fallback,
+                default_value: default_value.clone(),
+                return_objects,
+                has_options,
+                context: context.clone(),
+                scope_hook_line,
+                fallback_group,
+                call_shape: call_shape.clone(),
+            });
+        }
+    }
+
+    /// Default value from the second argument: a string (positional form) or the
+    /// `defaultValue` property of an options object.
+    fn default_value_arg(&self, expr: &Expr, lnum: u32) -> Option<String> {
+        let Expr::Object(obj) = expr else {
+            return eval_string_expr(expr, lnum, self.const_bindings);
+        };
+        option_prop(obj, "defaultValue")
+            .and_then(|value| eval_string_expr(value, lnum, self.const_bindings))
+    }
+
+    /// Statically known values of the `context` option, e.g. both branches of
+    /// `{ context: isMale ? "male" : "female" }`.
+    fn context_values(&self, args: &[ExprOrSpread], lnum: u32) -> Vec<String> {
+        args.iter()
+            .skip(1)
+            .find_map(|arg| match arg.expr.as_ref() {
+                Expr::Object(obj) => option_prop(obj, "context"),
+                _ => None,
+            })
+            .map(|value| eval_string_exprs(value, lnum, self.const_bindings))
+            .unwrap_or_default()
+    }
+
+    /// Key from the configured attribute of a message component, e.g.
+    /// `<FormattedMessage id="greeting" />`, or from react-i18next's `i18nKey`
+    /// prop (`<Trans i18nKey="welcome" />`) on any other element.
+    fn check_message_component(&mut self, opening: &JSXOpeningElement) {
+        let component = match &opening.name {
+            JSXElementName::Ident(name) => self
+                .message_components
+                .iter()
+                .find(|component| component.name == name.sym.as_ref()),
+            _ => None,
+        };
+        let key_attr = component.map_or("i18nKey", |component| component.key_attr.as_str());
+        let Some(value) = opening.attrs.iter().find_map(|attr| match attr {
+            JSXAttrOrSpread::JSXAttr(attr) => match &attr.name {
+                JSXAttrName::Ident(ident) if ident.sym.as_ref() == key_attr => attr.value.as_ref(),
+                _ => None,
+            },
+            _ => None,
+        }) else {
+            return;
+        };
+
+        let (lnum, col, end_col) = span_to_loc(self.cm, value.span());
+        if let Some(range) = self.range {
+            if lnum < range.start_line || lnum > range.end_line {
+                return;
+            }
+        }
+        let values = match value {
+            JSXAttrValue::Str(s) => vec![wtf8_to_string(&s.value)],
+            JSXAttrValue::JSXExprContainer(container) => match &container.expr {
+                JSXExpr::Expr(expr) => eval_string_exprs(expr, lnum, self.const_bindings),
+                JSXExpr::JSXEmptyExpr(_) => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
         for value in values {
-            let (key, namespace, fallback) = self.resolve_namespace(&value, lnum);
+            let (key, namespace, fallback, scope_hook_line) = self.resolve_namespace(&value, lnum);
             self.items.push(ScanItem {
                 key,
                 raw: value,
@@ -316,11 +711,26 @@ impl<'a> CallVisitor<'a> {
                 lnum,
                 col,
                 end_col,
+                value_range: None,
                 fallback,
+                default_value: None,
+                return_objects: false,
+                has_options: false,
+                context: Vec::new(),
+                scope_hook_line,
+                fallback_group: None,
+                call_shape: None,
             });
         }
     }
 
+    fn is_t_object(&self, name: &str) -> bool {
+        match self.t_object_names {
+            Some(names) => names.iter().any(|n| n == name),
+            None => DEFAULT_T_OBJECT_NAMES.contains(&name),
+        }
+    }
+
     fn is_translation_call(&self, func_name: &str, call: &CallExpr) -> bool {
         if func_name == "t" {
             return true;
@@ -338,22 +748,42 @@ impl<'a> CallVisitor<'a> {
         false
     }
 
-    fn resolve_namespace(&self, value: &str, lnum: u32) -> (String, String, bool) {
-        if let Some(colon_pos) = value.find(':') {
-            let namespace = &value[..colon_pos];
-            return (value.to_string(), namespace.to_string(), false);
+    /// Key path with the configured key separator rewritten to the index's `.`.
+    fn key_path(&self, path: &str) -> String {
+        if self.key_separator.is_empty() || self.key_separator == "." {
+            path.to_string()
+        } else {
+            path.replace(self.key_separator, ".")
         }
+    }
 
+    /// Canonical key, namespace, whether the fallback namespace was used, and the
+    /// line of the hook whose scope supplied the namespace.
+    fn resolve_namespace(&self, value: &str, lnum: u32) -> (String, String, bool, Option<u32>) {
+        if !self.ns_separator.is_empty() {
+            if let Some((namespace, path)) = value.split_once(self.ns_separator) {
+                let key = format!("{}:{}", namespace, self.key_path(path));
+                return (key, namespace.to_string(), false, None);
+            }
+        }
+
+        let path = self.key_path(value);
         for scope in self.scopes {
             if lnum >= scope.start_line && lnum <= scope.end_line {
                 if let Some(namespace) = &scope.ns {
-                    return (format!("{}:{}", namespace, value), namespace.clone(), false);
+                    let key = match &scope.key_prefix {
+                        Some(prefix) => {
+                            format!("{}:{}.{}", namespace, self.key_path(prefix), path)
+                        }
+                        None => format!("{}:{}", namespace, path),
+                    };
+                    return (key, namespace.clone(), false, Some(scope.hook_line));
                 }
             }
         }
 
         let namespace = self.fallback_namespace.to_string();
-        (format!("{}:{}", namespace, value), namespace, true)
+        (format!("{}:{}", namespace, path), namespace, true, None)
     }
 }
 
@@ -367,7 +797,7 @@ mod tests {
     fn extract_items(source: &str, fallback_namespace: &str) -> Vec<ScanItem> {
         let (module, cm) = parse_module(source, "tsx").expect("source should parse");
         let const_bindings = collect_consts(&module, &cm);
-        let scopes = collect_scopes_precise(&module, &cm, &const_bindings);
+        let scopes = collect_scopes_precise(&module, &cm, &const_bindings, None);
         extract_calls(
             &module,
             &cm,
@@ -375,6 +805,7 @@ mod tests {
             &scopes,
             fallback_namespace,
             &None,
+            &CallConfig::default(),
         )
     }
 
@@ -396,6 +827,58 @@ function Page() {
         assert!(!items[0].fallback);
     }
 
+    #[test]
+    fn records_the_hook_line_of_the_matched_scope() {
+        let source = r#"
+function Header() {
+  const { t } = useTranslation("header");
+  return t("title");
+}
+function Footer() {
+
+  const { t } = useTranslation("footer");
+  return [t("links"), t("common:copyright")];
+}
+"#;
+        let lines: Vec<(String, Option<u32>)> = extract_items(source, "common")
+            .into_iter()
+            .map(|item| (item.key, item.scope_hook_line))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                ("header:title".to_string(), Some(2)),
+                ("footer:links".to_string(), Some(7)),
+                ("common:copyright".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn prepends_key_prefix_unless_namespace_is_explicit() {
+        let items = extract_items(
+            r#"
+function Page() {
+  const { t } = useTranslation("common", { keyPrefix: "dashboard" });
+  return [t("title"), t("other:title")];
+}
+"#,
+            "translation",
+        );
+
+        let keys: Vec<(&str, &str)> = items
+            .iter()
+            .map(|item| (item.key.as_str(), item.raw.as_str()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("common:dashboard.title", "title"),
+                ("other:title", "other:title")
+            ]
+        );
+    }
+
     #[test]
     fn falls_back_for_member_t_calls_without_scope() {
         let items = extract_items(r#"i18n.t("greeting");"#, "translation");
@@ -405,4 +888,189 @@ function Page() {
         assert_eq!(items[0].namespace, "translation");
         assert!(items[0].fallback);
     }
+
+    #[test]
+    fn ignores_member_t_calls_on_unknown_objects() {
+        let source = r#"i18next.t("a"); table.t("b"); timer.t("c"); t("d");"#;
+        let keys: Vec<String> = extract_items(source, "common")
+            .into_iter()
+            .map(|item| item.key)
+            .collect();
+        assert_eq!(keys, vec!["common:a", "common:d"]);
+
+        let (module, cm) = parse_module(source, "tsx").expect("source should parse");
+        let names = vec!["table".to_string()];
+        let config = CallConfig {
+            t_object_names: Some(&names),
+            ..Default::default()
+        };
+        let keys: Vec<String> = extract_calls(&module, &cm, &[], &[], "common", &None, &config)
+            .into_iter()
+            .map(|item| item.key)
+            .collect();
+        assert_eq!(keys, vec!["common:b", "common:d"]);
+    }
+
+    #[test]
+    fn resolves_keys_from_object_literal_consts() {
+        let source = r#"
+const KEYS = { title: "dashboard.title", nav: { home: "nav.home" } } as const;
+t(KEYS.title);
+t(KEYS["nav"].home);
+t(KEYS[name]);
+"#;
+        let keys: Vec<String> = extract_items(source, "common")
+            .into_iter()
+            .map(|item| item.key)
+            .collect();
+        assert_eq!(keys, vec!["common:dashboard.title", "common:nav.home"]);
+    }
+
+    #[test]
+    fn applies_configured_ns_and_key_separators() {
+        let source = r#"t("auth|login_title"); t("ratio: 16:9");"#;
+        let (module, cm) = parse_module(source, "tsx").expect("source should parse");
+        let extract = |ns_separator, key_separator| -> Vec<(String, bool)> {
+            let config = CallConfig {
+                ns_separator,
+                key_separator,
+                ..Default::default()
+            };
+            extract_calls(&module, &cm, &[], &[], "common", &None, &config)
+                .into_iter()
+                .map(|item| (item.key, item.fallback))
+                .collect()
+        };
+
+        assert_eq!(
+            extract(Some("|"), Some("_")),
+            vec![
+                ("auth:login.title".to_string(), false),
+                ("common:ratio: 16:9".to_string(), true),
+            ]
+        );
+        assert_eq!(
+            extract(Some(""), Some("")),
+            vec![
+                ("common:auth|login_title".to_string(), true),
+                ("common:ratio: 16:9".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_both_branches_of_a_conditional_attribute() {
+        let source = r#"const el = <Button label={cond ? t("a") : t("b")} />;"#;
+        let keys: Vec<String> = extract_items(source, "common")
+            .into_iter()
+            .map(|item| item.key)
+            .collect();
+        assert_eq!(keys, vec!["common:a", "common:b"]);
+    }
+
+    #[test]
+    fn extracts_calls_wrapped_in_logical_defaults() {
+        let source = r#"const a = t("title") ?? "x"; const b = t("label") || other;"#;
+        let keys: Vec<String> = extract_items(source, "common")
+            .into_iter()
+            .map(|item| item.key)
+            .collect();
+        assert_eq!(keys, vec!["common:title", "common:label"]);
+    }
+
+    #[test]
+    fn extracts_keys_from_next_intl_rich_and_markup_calls() {
+        let items = extract_items(
+            r#"
+function Footer() {
+  const t = useTranslations("legal");
+  return [
+    t.rich("terms", { link: (chunks) => <a>{chunks}</a> }),
+    t.markup("privacy", { b: (chunks) => `<b>${chunks}</b>` }),
+    other.rich("ignored"),
+  ];
+}
+"#,
+            "translation",
+        );
+
+        let keys: Vec<&str> = items.iter().map(|item| item.key.as_str()).collect();
+        assert_eq!(keys, vec!["legal:terms", "legal:privacy"]);
+    }
+
+    #[test]
+    fn extracts_trans_i18n_key_within_scope() {
+        let source = r#"
+function Welcome() {
+  const { t } = useTranslation("home");
+  return <Trans i18nKey="welcome" t={t} />;
+}
+const Footer = () => <Trans i18nKey={"common:footer"} />;
+"#;
+        let items = extract_items(source, "common");
+        let keys: Vec<(&str, bool)> = items
+            .iter()
+            .map(|item| (item.key.as_str(), item.fallback))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![("home:welcome", false), ("common:footer", false)]
+        );
+        assert_eq!((items[0].lnum, items[0].col), (3, 24));
+    }
+
+    #[test]
+    fn extracts_each_key_of_a_fallback_key_array() {
+        let items = extract_items(r#"t(["common:a", "b"]);"#, "translation");
+
+        let keys: Vec<(&str, u32)> = items
+            .iter()
+            .map(|item| (item.key.as_str(), item.col))
+            .collect();
+        assert_eq!(keys, vec![("common:a", 3), ("translation:b", 15)]);
+    }
+
+    #[test]
+    fn groups_fallback_key_arrays_and_skips_dynamic_elements() {
+        let source =
+            r#"t(["common:missing", dynamicKey, "common:present"]); t("plain"); t(["x", "y"]);"#;
+        let items: Vec<(String, Option<u32>)> = extract_items(source, "common")
+            .into_iter()
+            .map(|item| (item.key, item.fallback_group))
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                ("common:missing".to_string(), Some(0)),
+                ("common:present".to_string(), Some(0)),
+                ("common:plain".to_string(), None),
+                ("common:x".to_string(), Some(1)),
+                ("common:y".to_string(), Some(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_keys_from_jsx_conditionals_returned_by_switch_cases() {
+        let source = r#"
+function Status({ kind, done }) {
+  const { t } = useTranslation("status");
+  switch (kind) {
+    case "task":
+      return done ? <Done label={t("task.done")} /> : <span>{t("task.pending")}</span>;
+    default: {
+      return <p>{cond ? t("other") : null}</p>;
+    }
+  }
+}
+"#;
+        let keys: Vec<String> = extract_items(source, "common")
+            .into_iter()
+            .map(|item| item.key)
+            .collect();
+        assert_eq!(
+            keys,
+            vec!["status:task.done", "status:task.pending", "status:other"]
+        );
+    }
 }
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use super::project_cache::{FileScan, ProjectScanCache, project_token, scan_file};
+use super::{component, project};
+use crate::util::is_cancelled;
+
+#[derive(Debug, Deserialize)]
+pub struct ComponentCensusParams {
+    pub project_root: String,
+    pub fallback_namespace: String,
+    #[serde(default)]
+    pub cancel_token_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComponentUsage {
+    pub component: String,
+    pub file: String,
+    pub key_count: usize,
+    pub unique_keys: usize,
+}
+
+/// Translation calls of one file grouped by enclosing component.
+/// Calls outside any component are not counted.
+fn census_file(file_scan: &FileScan) -> Vec<ComponentUsage> {
+    let mut by_component: BTreeMap<&str, (usize, BTreeSet<&str>)> = BTreeMap::new();
+    for item in &file_scan.items {
+        if let Some(name) = component::enclosing_component(&file_scan.components, item.lnum) {
+            let entry = by_component.entry(name).or_default();
+            entry.0 += 1;
+            entry.1.insert(item.key.as_str());
+        }
+    }
+
+    by_component
+        .into_iter()
+        .map(|(name, (key_count, keys))| ComponentUsage {
+            component: name.to_string(),
+            file: file_scan.file.clone(),
+            key_count,
+            unique_keys: keys.len(),
+        })
+        .collect()
+}
+
+/// Answered from the project scan cache when `scan/indexProject` has run for the
+/// same root and fallback namespace.
+pub(super) fn component_census(
+    params: ComponentCensusParams,
+    cache: &ProjectScanCache,
+) -> Result<Value> {
+    let is_cancelled_now = || is_cancelled(params.cancel_token_path.as_deref());
+    let mut usages: Vec<ComponentUsage> = Vec::new();
+
+    let token = project_token(&params.project_root, &params.fallback_namespace);
+    if let Some(scan) = cache.get(&token) {
+        usages.extend(scan.files.iter().flat_map(census_file));
+    } else {
+        let Some(source_files) =
+            project::collect_source_files(Path::new(&params.project_root), &is_cancelled_now)
+        else {
+            return Ok(serde_json::json!({ "components": usages, "cancelled": true }));
+        };
+
+        for chunk in source_files.chunks(50) {
+            if is_cancelled_now() {
+                return Ok(serde_json::json!({ "components": usages, "cancelled": true }));
+            }
+            let results: Vec<Vec<ComponentUsage>> = chunk
+                .par_iter()
+                .map(|path| {
+                    scan_file(path, &params.fallback_namespace)
+                        .map(|file_scan| census_file(&file_scan))
+                        .unwrap_or_default()
+                })
+                .collect();
+            usages.extend(results.into_iter().flatten());
+        }
+    }
+
+    usages.sort_by(|a, b| {
+        b.key_count
+            .cmp(&a.key_count)
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.component.cmp(&b.component))
+    });
+
+    Ok(serde_json::json!({ "components": usages, "cancelled": false }))
+}
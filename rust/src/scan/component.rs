@@ -0,0 +1,138 @@
+use swc_common::{SourceMap, Span};
+use swc_ecma_ast::*;
+
+use super::parser::span_to_lines;
+
+/// A top-level function that looks like a component (PascalCase name, or the
+/// anonymous default export) together with the lines it spans.
+#[derive(Debug, Clone)]
+pub(super) struct ComponentSpan {
+    pub(super) name: String,
+    pub(super) start_line: u32,
+    pub(super) end_line: u32,
+}
+
+fn is_component_name(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+}
+
+/// Span of the function body an initializer evaluates to, looking through
+/// wrappers such as `memo(...)` / `forwardRef(...)` and TS type assertions.
+fn function_span(expr: &Expr) -> Option<Span> {
+    match expr {
+        Expr::Arrow(arrow) => Some(arrow.span),
+        Expr::Fn(fn_expr) => Some(fn_expr.function.span),
+        Expr::Paren(paren) => function_span(&paren.expr),
+        Expr::TsAs(ts_as) => function_span(&ts_as.expr),
+        Expr::TsSatisfies(ts_sat) => function_span(&ts_sat.expr),
+        Expr::Call(call) => call
+            .args
+            .first()
+            .filter(|arg| arg.spread.is_none())
+            .and_then(|arg| function_span(&arg.expr)),
+        _ => None,
+    }
+}
+
+pub(super) fn collect_components(module: &Module, cm: &SourceMap) -> Vec<ComponentSpan> {
+    let mut collector = ComponentCollector {
+        cm,
+        components: Vec::new(),
+    };
+    for item in &module.body {
+        collector.visit_module_item(item);
+    }
+    collector.components
+}
+
+/// Innermost component whose span contains `lnum`.
+pub(super) fn enclosing_component(components: &[ComponentSpan], lnum: u32) -> Option<&str> {
+    components
+        .iter()
+        .filter(|c| lnum >= c.start_line && lnum <= c.end_line)
+        .min_by_key(|c| c.end_line - c.start_line)
+        .map(|c| c.name.as_str())
+}
+
+struct ComponentCollector<'a> {
+    cm: &'a SourceMap,
+    components: Vec<ComponentSpan>,
+}
+
+impl<'a> ComponentCollector<'a> {
+    fn push(&mut self, name: String, span: Span) {
+        let (start_line, end_line) = span_to_lines(self.cm, span);
+        self.components.push(ComponentSpan {
+            name,
+            start_line,
+            end_line,
+        });
+    }
+
+    fn visit_module_item(&mut self, item: &ModuleItem) {
+        match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => self.visit_decl(decl),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                self.visit_decl(&export.decl);
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => {
+                if let DefaultDecl::Fn(fn_expr) = &export.decl {
+                    let name = fn_expr
+                        .ident
+                        .as_ref()
+                        .map(|ident| ident.sym.to_string())
+                        .unwrap_or_else(|| "default".to_string());
+                    self.push(name, fn_expr.function.span);
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => {
+                if let Some(span) = function_span(&export.expr) {
+                    self.push("default".to_string(), span);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_decl(&mut self, decl: &Decl) {
+        match decl {
+            Decl::Fn(fn_decl) => {
+                let name = fn_decl.ident.sym.to_string();
+                if is_component_name(&name) {
+                    self.push(name, fn_decl.function.span);
+                }
+            }
+            Decl::Var(var) => {
+                for declarator in &var.decls {
+                    let Pat::Ident(ident) = &declarator.name else {
+                        continue;
+                    };
+                    let name = ident.id.sym.to_string();
+                    if !is_component_name(&name) {
+                        continue;
+                    }
+                    if let Some(span) = declarator.init.as_deref().and_then(function_span) {
+                        self.push(name, span);
+                    }
+                }
+            }
+            Decl::TsModule(ts_module) => {
+                if let Some(body) = &ts_module.body {
+                    self.visit_ts_namespace_body(body);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_ts_namespace_body(&mut self, body: &TsNamespaceBody) {
+        match body {
+            TsNamespaceBody::TsModuleBlock(block) => {
+                for item in &block.body {
+                    self.visit_module_item(item);
+                }
+            }
+            TsNamespaceBody::TsNamespaceDecl(decl) => self.visit_ts_namespace_body(&decl.body),
+        }
+    }
+}
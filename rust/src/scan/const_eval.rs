@@ -13,7 +13,7 @@ pub(super) struct ConstBinding {
     pub(super) order: usize,
 }
 
-fn wtf8_to_string(atom: &swc_atoms::Wtf8Atom) -> String {
+pub(super) fn wtf8_to_string(atom: &swc_atoms::Wtf8Atom) -> String {
     atom.as_wtf8().as_str().unwrap_or_default().to_string()
 }
 
@@ -78,6 +78,14 @@ where
             Some(format!("{}{}", left, right))
         }
         Expr::Ident(ident) => resolve_ident(ident.sym.as_ref()),
+        Expr::Call(call) if call.args.is_empty() => match &call.callee {
+            Callee::Expr(callee) => match callee.as_ref() {
+                Expr::Ident(ident) => resolve_ident(&literal_fn_binding_name(ident.sym.as_ref())),
+                _ => None,
+            },
+            _ => None,
+        },
+        Expr::Member(_) => resolve_ident(&member_path(expr)?),
         Expr::Paren(paren) => eval_string_expr_with_resolver(&paren.expr, resolve_ident),
         Expr::TsAs(ts_as) => eval_string_expr_with_resolver(&ts_as.expr, resolve_ident),
         Expr::TsSatisfies(ts_sat) => eval_string_expr_with_resolver(&ts_sat.expr, resolve_ident),
@@ -89,6 +97,78 @@ where
     }
 }
 
+/// Binding name for a parameterless local function returning a string, so `getKey()`
+/// resolves through the same lookup as consts. The `()` suffix cannot clash with an
+/// identifier.
+fn literal_fn_binding_name(name: &str) -> String {
+    format!("{}()", name)
+}
+
+/// Binding name for a string member such as the enum member `Namespaces.Dashboard`.
+/// The `.` cannot appear in an identifier, so it cannot clash with a plain const.
+fn member_binding_name(object: &str, member: &str) -> String {
+    format!("{}.{}", object, member)
+}
+
+/// Binding name for a static member access: `KEYS.nav.home` for `KEYS.nav.home` or
+/// `KEYS["nav"].home`. Computed access with anything but a string literal is dynamic.
+fn member_path(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Ident(ident) => Some(ident.sym.to_string()),
+        Expr::Member(member) => {
+            let object = member_path(&member.obj)?;
+            match &member.prop {
+                MemberProp::Ident(prop) => Some(member_binding_name(&object, prop.sym.as_ref())),
+                MemberProp::Computed(computed) => match computed.expr.as_ref() {
+                    Expr::Lit(Lit::Str(s)) => {
+                        Some(member_binding_name(&object, &wtf8_to_string(&s.value)))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// The object literal of `{ ... }`, `{ ... } as const` or `{ ... } satisfies T`.
+fn object_literal(expr: &Expr) -> Option<&ObjectLit> {
+    match expr {
+        Expr::Object(obj) => Some(obj),
+        Expr::Paren(paren) => object_literal(&paren.expr),
+        Expr::TsAs(ts_as) => object_literal(&ts_as.expr),
+        Expr::TsSatisfies(ts_sat) => object_literal(&ts_sat.expr),
+        Expr::TsConstAssertion(ts_const) => object_literal(&ts_const.expr),
+        _ => None,
+    }
+}
+
+/// The expression returned by a parameterless function whose body is only a
+/// `return <expr>`. Anything else is left unresolved.
+fn single_return_expr(stmts: &[Stmt]) -> Option<&Expr> {
+    match stmts {
+        [Stmt::Return(ret)] => ret.arg.as_deref(),
+        _ => None,
+    }
+}
+
+fn literal_fn_return(expr: &Expr) -> Option<&Expr> {
+    match expr {
+        Expr::Arrow(arrow) if arrow.params.is_empty() => match arrow.body.as_ref() {
+            BlockStmtOrExpr::Expr(body) => Some(body),
+            BlockStmtOrExpr::BlockStmt(block) => single_return_expr(&block.stmts),
+        },
+        Expr::Fn(fn_expr) if fn_expr.function.params.is_empty() => fn_expr
+            .function
+            .body
+            .as_ref()
+            .and_then(|body| single_return_expr(&body.stmts)),
+        Expr::Paren(paren) => literal_fn_return(&paren.expr),
+        _ => None,
+    }
+}
+
 pub(super) fn eval_string_expr(
     expr: &Expr,
     line: u32,
@@ -171,6 +251,54 @@ pub(super) fn eval_string_exprs(
     })
 }
 
+/// Best-effort glob for a dynamic key, with `*` for each part that cannot be
+/// evaluated: `errors.*` for `` `errors.${code}` ``. `None` when nothing is static.
+pub(super) fn key_pattern(
+    expr: &Expr,
+    line: u32,
+    const_bindings: &[ConstBinding],
+) -> Option<String> {
+    fn push_part(pattern: &mut String, part: &str) {
+        if part == "*" && pattern.ends_with('*') {
+            return;
+        }
+        pattern.push_str(part);
+    }
+
+    fn pattern_of(expr: &Expr, line: u32, const_bindings: &[ConstBinding], pattern: &mut String) {
+        if let Some(value) = eval_string_expr(expr, line, const_bindings) {
+            pattern.push_str(&value);
+            return;
+        }
+        match expr {
+            Expr::Tpl(tpl) => {
+                for (i, quasi) in tpl.quasis.iter().enumerate() {
+                    pattern.push_str(&quasi.raw);
+                    if let Some(expr) = tpl.exprs.get(i) {
+                        pattern_of(expr, line, const_bindings, pattern);
+                    }
+                }
+            }
+            Expr::Bin(bin) if bin.op == BinaryOp::Add => {
+                pattern_of(&bin.left, line, const_bindings, pattern);
+                pattern_of(&bin.right, line, const_bindings, pattern);
+            }
+            Expr::Paren(paren) => pattern_of(&paren.expr, line, const_bindings, pattern),
+            Expr::TsAs(ts_as) => pattern_of(&ts_as.expr, line, const_bindings, pattern),
+            Expr::TsNonNull(ts_nn) => pattern_of(&ts_nn.expr, line, const_bindings, pattern),
+            _ => push_part(pattern, "*"),
+        }
+    }
+
+    let mut pattern = String::new();
+    pattern_of(expr, line, const_bindings, &mut pattern);
+    if pattern.chars().all(|c| c == '*') {
+        None
+    } else {
+        Some(pattern)
+    }
+}
+
 pub(super) fn collect_consts(module: &Module, cm: &SourceMap) -> Vec<ConstBinding> {
     struct ConstCollector<'a> {
         cm: &'a SourceMap,
@@ -329,6 +457,24 @@ pub(super) fn collect_consts(module: &Module, cm: &SourceMap) -> Vec<ConstBindin
             match decl {
                 Decl::Var(var_decl) => self.collect_var_decl(var_decl, scope_start, scope_end),
                 Decl::Fn(fn_decl) => {
+                    // Function declarations are hoisted, so the binding is visible
+                    // from the start of the enclosing scope.
+                    if fn_decl.function.params.is_empty() {
+                        if let Some(ret) = fn_decl
+                            .function
+                            .body
+                            .as_ref()
+                            .and_then(|body| single_return_expr(&body.stmts))
+                        {
+                            self.collect_literal_fn(
+                                fn_decl.ident.sym.as_ref(),
+                                ret,
+                                scope_start,
+                                scope_end,
+                                scope_start,
+                            );
+                        }
+                    }
                     if let Some(body) = &fn_decl.function.body {
                         let (body_start, body_end) = span_to_lines(self.cm, body.span);
                         for stmt in &body.stmts {
@@ -336,10 +482,124 @@ pub(super) fn collect_consts(module: &Module, cm: &SourceMap) -> Vec<ConstBindin
                         }
                     }
                 }
+                Decl::TsModule(ts_module) => {
+                    if let Some(body) = &ts_module.body {
+                        self.visit_ts_namespace_body(body);
+                    }
+                }
+                Decl::TsEnum(ts_enum) => self.collect_enum(ts_enum, scope_start, scope_end),
                 _ => {}
             }
         }
 
+        /// String-valued members of an enum, e.g. `Namespaces.Dashboard` for
+        /// `enum Namespaces { Dashboard = "dashboard" }`.
+        fn collect_enum(&mut self, ts_enum: &TsEnumDecl, scope_start: u32, scope_end: u32) {
+            let (decl_line, _, _) = span_to_loc(self.cm, ts_enum.span);
+            for member in &ts_enum.members {
+                let member_name = match &member.id {
+                    TsEnumMemberId::Ident(ident) => ident.sym.to_string(),
+                    TsEnumMemberId::Str(s) => wtf8_to_string(&s.value),
+                };
+                let Some(value) = member.init.as_ref().and_then(|init| {
+                    eval_string_expr_with_resolver(init, &|name| {
+                        resolve_const_at_line(name, decl_line, &self.const_bindings)
+                    })
+                }) else {
+                    continue;
+                };
+                self.const_bindings.push(ConstBinding {
+                    name: member_binding_name(ts_enum.id.sym.as_ref(), &member_name),
+                    value,
+                    scope_start,
+                    scope_end,
+                    decl_line,
+                    order: self.next_order,
+                });
+                self.next_order += 1;
+            }
+        }
+
+        /// String leaves of an object-literal const, recorded under their member
+        /// path, e.g. `KEYS.nav.home` for `const KEYS = { nav: { home: "nav.home" } }`.
+        fn collect_object(
+            &mut self,
+            path: &str,
+            obj: &ObjectLit,
+            scope_start: u32,
+            scope_end: u32,
+            decl_line: u32,
+        ) {
+            for prop in &obj.props {
+                let PropOrSpread::Prop(prop) = prop else {
+                    continue;
+                };
+                let Prop::KeyValue(kv) = prop.as_ref() else {
+                    continue;
+                };
+                let key = match &kv.key {
+                    PropName::Ident(ident) => ident.sym.to_string(),
+                    PropName::Str(s) => wtf8_to_string(&s.value),
+                    _ => continue,
+                };
+                let member_path = member_binding_name(path, &key);
+                if let Some(nested) = object_literal(&kv.value) {
+                    self.collect_object(&member_path, nested, scope_start, scope_end, decl_line);
+                    continue;
+                }
+                let Some(value) = eval_string_expr_with_resolver(&kv.value, &|name| {
+                    resolve_const_at_line(name, decl_line, &self.const_bindings)
+                }) else {
+                    continue;
+                };
+                self.const_bindings.push(ConstBinding {
+                    name: member_path,
+                    value,
+                    scope_start,
+                    scope_end,
+                    decl_line,
+                    order: self.next_order,
+                });
+                self.next_order += 1;
+            }
+        }
+
+        fn visit_ts_namespace_body(&mut self, body: &TsNamespaceBody) {
+            match body {
+                TsNamespaceBody::TsModuleBlock(block) => {
+                    let (block_start, block_end) = span_to_lines(self.cm, block.span);
+                    for item in &block.body {
+                        self.visit_module_item(item, block_start, block_end);
+                    }
+                }
+                TsNamespaceBody::TsNamespaceDecl(decl) => self.visit_ts_namespace_body(&decl.body),
+            }
+        }
+
+        fn collect_literal_fn(
+            &mut self,
+            name: &str,
+            ret: &Expr,
+            scope_start: u32,
+            scope_end: u32,
+            decl_line: u32,
+        ) {
+            let (ret_line, _, _) = span_to_loc(self.cm, ret.span());
+            if let Some(value) = eval_string_expr_with_resolver(ret, &|name| {
+                resolve_const_at_line(name, ret_line, &self.const_bindings)
+            }) {
+                self.const_bindings.push(ConstBinding {
+                    name: literal_fn_binding_name(name),
+                    value,
+                    scope_start,
+                    scope_end,
+                    decl_line,
+                    order: self.next_order,
+                });
+                self.next_order += 1;
+            }
+        }
+
         fn collect_var_decl(&mut self, var_decl: &VarDecl, scope_start: u32, scope_end: u32) {
             if var_decl.kind == VarDeclKind::Const {
                 for decl in &var_decl.decls {
@@ -358,6 +618,22 @@ pub(super) fn collect_consts(module: &Module, cm: &SourceMap) -> Vec<ConstBindin
                                     order: self.next_order,
                                 });
                                 self.next_order += 1;
+                            } else if let Some(obj) = object_literal(init) {
+                                self.collect_object(
+                                    ident.sym.as_ref(),
+                                    obj,
+                                    scope_start,
+                                    scope_end,
+                                    decl_line,
+                                );
+                            } else if let Some(ret) = literal_fn_return(init) {
+                                self.collect_literal_fn(
+                                    ident.sym.as_ref(),
+                                    ret,
+                                    scope_start,
+                                    scope_end,
+                                    decl_line,
+                                );
                             }
                         }
                     }
@@ -55,3 +55,18 @@ fn eval_string_exprs_expands_conditional_template_branches() {
 
     assert_eq!(values, vec!["a.title".to_string(), "b.title".to_string()]);
 }
+
+#[test]
+fn key_pattern_globs_dynamic_parts() {
+    let (expr, line) = first_call_arg(r#"t(`errors.${code}.${kind}_title`);"#);
+    assert_eq!(
+        key_pattern(&expr, line, &[]),
+        Some("errors.*.*_title".to_string())
+    );
+
+    let (expr, line) = first_call_arg(r#"t("menu." + item.id + suffix);"#);
+    assert_eq!(key_pattern(&expr, line, &[]), Some("menu.*".to_string()));
+
+    let (expr, line) = first_call_arg("t(key);");
+    assert_eq!(key_pattern(&expr, line, &[]), None);
+}
@@ -0,0 +1,89 @@
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+use super::{call_extract, const_eval, parser, project, scope};
+use crate::util::{is_cancelled, lang_from_extension, path_to_string};
+
+#[derive(Debug, Deserialize)]
+pub struct DynamicKeysParams {
+    pub project_root: String,
+    pub fallback_namespace: String,
+    #[serde(default)]
+    pub cancel_token_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DynamicKeySite {
+    pub file: String,
+    pub lnum: u32,
+    pub col: u32,
+    pub snippet: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+}
+
+fn dynamic_sites_in_file(path: &Path, fallback_namespace: &str) -> Vec<DynamicKeySite> {
+    let Ok(source) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let Ok((module, cm)) = parser::parse_module(&source, lang_from_extension(path)) else {
+        return Vec::new();
+    };
+    let const_bindings = const_eval::collect_consts(&module, &cm);
+    let scopes = scope::collect_scopes_precise(&module, &cm, &const_bindings, None);
+    let (_, unresolved) = call_extract::extract_calls_with_unresolved(
+        &module,
+        &cm,
+        &const_bindings,
+        &scopes,
+        fallback_namespace,
+        &None,
+        &call_extract::CallConfig::default(),
+    );
+
+    let file = path_to_string(path);
+    unresolved
+        .into_iter()
+        .map(|call| DynamicKeySite {
+            file: file.clone(),
+            lnum: call.lnum,
+            col: call.col,
+            snippet: call.snippet,
+            pattern: call.pattern,
+        })
+        .collect()
+}
+
+pub(super) fn dynamic_keys(params: DynamicKeysParams) -> Result<Value> {
+    let is_cancelled_now = || is_cancelled(params.cancel_token_path.as_deref());
+    let mut sites: Vec<DynamicKeySite> = Vec::new();
+
+    let Some(source_files) =
+        project::collect_source_files(Path::new(&params.project_root), &is_cancelled_now)
+    else {
+        return Ok(serde_json::json!({ "sites": sites, "cancelled": true }));
+    };
+
+    for chunk in source_files.chunks(50) {
+        if is_cancelled_now() {
+            return Ok(serde_json::json!({ "sites": sites, "cancelled": true }));
+        }
+        let results: Vec<Vec<DynamicKeySite>> = chunk
+            .par_iter()
+            .map(|path| dynamic_sites_in_file(path, &params.fallback_namespace))
+            .collect();
+        sites.extend(results.into_iter().flatten());
+    }
+
+    sites.sort_by(|a, b| {
+        a.file
+            .cmp(&b.file)
+            .then_with(|| a.lnum.cmp(&b.lnum))
+            .then_with(|| a.col.cmp(&b.col))
+    });
+
+    Ok(serde_json::json!({ "sites": sites, "cancelled": false }))
+}
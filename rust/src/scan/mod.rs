@@ -1,13 +1,28 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeSet;
 
+use crate::resource::index::IndexCache;
+
+mod astro;
 mod call_extract;
+mod census;
+mod component;
 mod const_eval;
+mod dynamic;
+mod node_at;
 pub(crate) mod parser;
+mod project;
+mod project_cache;
 mod resource_json;
 mod scope;
 
+pub use census::ComponentCensusParams;
+pub use dynamic::DynamicKeysParams;
+pub use project_cache::{IndexProjectParams, ProjectScanCache, ReferencesForKeyParams};
+pub(crate) use resource_json::leaf_lines;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanItem {
     pub key: String,
@@ -16,15 +31,128 @@ pub struct ScanItem {
     pub lnum: u32,
     pub col: u32,
     pub end_col: u32,
+    /// Byte offsets `[start, end)` of a resource leaf's value in the source, for
+    /// replacing it in place: the text between the quotes for a string value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_range: Option<(u32, u32)>,
     pub fallback: bool,
+    /// Default given at the call site, either positionally (`t("k", "Default")`)
+    /// or as `t("k", { defaultValue: "Default" })`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<String>,
+    /// `returnObjects: true` was passed, so the key may name a subtree, not a leaf.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub return_objects: bool,
+    /// The call passes an options argument (anything beyond the key and a string
+    /// default), so interpolation values may be supplied.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub has_options: bool,
+    /// Values of the i18next `context` option; the call resolves `key_<context>`
+    /// for each of them, falling back to `key`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context: Vec<String>,
+    /// Line of the hook whose scope supplied the namespace, when one did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope_hook_line: Option<u32>,
+    /// Shared by the keys of one fallback-key array (`t(["a", "b"])`), of which
+    /// the first existing one is used. Unique within one extraction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_group: Option<u32>,
+    /// Argument shape of the call, with `ExtractParams::include_call_shape`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call_shape: Option<CallShape>,
 }
 
-#[derive(Debug, Deserialize)]
+/// How a translation call passes its arguments, for codemods rewriting calls.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallShape {
+    pub arg_count: u32,
+    pub has_options: bool,
+    /// Property names of the options object literal, in source order.
+    pub option_keys: Vec<String>,
+    /// The options object also has computed or spread properties, so it may supply
+    /// variables beyond `option_keys`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub option_keys_incomplete: bool,
+}
+
+/// A translation call whose key argument could not be evaluated statically.
+#[derive(Debug, Clone, Serialize)]
+pub struct UnresolvedCall {
+    pub lnum: u32,
+    pub col: u32,
+    pub end_col: u32,
+    /// Source text of the key argument.
+    pub snippet: String,
+    /// Glob of the possible keys, `*` standing for each dynamic part
+    /// (`errors.*` for `` `errors.${code}` ``). Absent when no part is static.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
 pub struct ExtractParams {
     pub source: String,
     pub lang: String,
     pub fallback_namespace: String,
     pub range: Option<Range>,
+    /// Several ranges at once; items within any of them (or within `range`) are
+    /// returned once each.
+    #[serde(default)]
+    pub ranges: Option<Vec<Range>>,
+    /// Also report calls whose key is dynamic, under `unresolved`.
+    #[serde(default)]
+    pub include_unresolved: bool,
+    /// JSX components that carry a translation key in an attribute.
+    #[serde(default)]
+    pub message_components: Vec<MessageComponent>,
+    /// Hooks that establish a namespace scope, replacing the default
+    /// `useTranslation`/`useTranslations`/`getTranslations`.
+    #[serde(default)]
+    pub hook_names: Option<Vec<String>>,
+    /// Objects whose `.t(...)` method is a translation call; defaults to
+    /// `["i18n", "i18next"]`.
+    #[serde(default)]
+    pub t_object_names: Option<Vec<String>>,
+    /// i18next `nsSeparator`; defaults to `:`. `false` (or `""`) disables it, so a
+    /// `:` in a key is literal.
+    #[serde(default, deserialize_with = "deserialize_separator")]
+    pub ns_separator: Option<String>,
+    /// i18next `keySeparator`; defaults to `.`. Paths are rewritten to `.` in
+    /// canonical keys to match the index. `false` (or `""`) keeps keys flat.
+    #[serde(default, deserialize_with = "deserialize_separator")]
+    pub key_separator: Option<String>,
+    /// Attach `call_shape` (argument count and option keys) to each item.
+    #[serde(default)]
+    pub include_call_shape: bool,
+}
+
+/// Reads a separator option: a string, or `false` (as in i18next config) for `""`.
+pub(crate) fn deserialize_separator<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Separator {
+        Str(String),
+        Bool(bool),
+    }
+    Ok(match Option::<Separator>::deserialize(deserializer)? {
+        Some(Separator::Str(sep)) => Some(sep),
+        Some(Separator::Bool(false)) => Some(String::new()),
+        Some(Separator::Bool(true)) | None => None,
+    })
+}
+
+/// A JSX component whose `key_attr` attribute holds a translation key,
+/// e.g. `{ name: "FormattedMessage", key_attr: "id" }`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageComponent {
+    pub name: String,
+    pub key_attr: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -41,6 +169,49 @@ pub struct TranslationContextParams {
     pub lang: String,
     pub row: u32,
     pub fallback_namespace: String,
+    /// See `ExtractParams::hook_names`.
+    #[serde(default)]
+    pub hook_names: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuggestNamespaceParams {
+    pub source: String,
+    pub lang: String,
+    pub row: u32,
+    pub fallback_namespace: String,
+    /// Index to look up the namespace's resource files in.
+    pub cache_key: String,
+    /// See `ExtractParams::hook_names`.
+    #[serde(default)]
+    pub hook_names: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NamespacesInFileParams {
+    pub source: String,
+    pub lang: String,
+    pub fallback_namespace: String,
+    /// See `ExtractParams::hook_names`.
+    #[serde(default)]
+    pub hook_names: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KeyAtParams {
+    pub source: String,
+    pub lang: String,
+    pub row: u32,
+    pub col: u32,
+    pub fallback_namespace: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeAtParams {
+    pub source: String,
+    pub lang: String,
+    pub row: u32,
+    pub col: u32,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -49,6 +220,12 @@ pub struct Range {
     pub end_line: u32,
 }
 
+impl Range {
+    fn contains(&self, lnum: u32) -> bool {
+        lnum >= self.start_line && lnum <= self.end_line
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct TranslationContext {
     pub namespace: String,
@@ -60,16 +237,44 @@ pub struct TranslationContext {
 pub fn extract(params: ExtractParams) -> Result<Value> {
     let (module, cm) = parser::parse_module(&params.source, &params.lang)?;
     let const_bindings = const_eval::collect_consts(&module, &cm);
-    let scopes = scope::collect_scopes_precise(&module, &cm, &const_bindings);
-    let items = call_extract::extract_calls(
+    let hook_names = params.hook_names.as_deref();
+    let scopes = scope::collect_scopes_precise(&module, &cm, &const_bindings, hook_names);
+    // With several ranges, extract everything once and keep what falls in their
+    // union, so overlapping ranges cannot yield duplicates.
+    let ranges: Vec<Range> = match &params.ranges {
+        Some(ranges) if !ranges.is_empty() => ranges.iter().chain(&params.range).cloned().collect(),
+        _ => Vec::new(),
+    };
+    let range = if ranges.is_empty() {
+        params.range.clone()
+    } else {
+        None
+    };
+    let (mut items, mut unresolved) = call_extract::extract_calls_with_unresolved(
         &module,
         &cm,
         &const_bindings,
         &scopes,
         &params.fallback_namespace,
-        &params.range,
+        &range,
+        &call_extract::CallConfig {
+            message_components: &params.message_components,
+            hook_names,
+            t_object_names: params.t_object_names.as_deref(),
+            ns_separator: params.ns_separator.as_deref(),
+            key_separator: params.key_separator.as_deref(),
+            include_call_shape: params.include_call_shape,
+        },
     );
-    Ok(serde_json::json!({ "items": items }))
+    if !ranges.is_empty() {
+        items.retain(|item| ranges.iter().any(|range| range.contains(item.lnum)));
+        unresolved.retain(|call| ranges.iter().any(|range| range.contains(call.lnum)));
+    }
+    if params.include_unresolved {
+        Ok(serde_json::json!({ "items": items, "unresolved": unresolved }))
+    } else {
+        Ok(serde_json::json!({ "items": items }))
+    }
 }
 
 pub fn extract_resource(params: ExtractResourceParams) -> Result<Value> {
@@ -77,9 +282,14 @@ pub fn extract_resource(params: ExtractResourceParams) -> Result<Value> {
 }
 
 pub fn translation_context_at(params: TranslationContextParams) -> Result<Value> {
+    Ok(serde_json::to_value(translation_context(params)?)?)
+}
+
+fn translation_context(params: TranslationContextParams) -> Result<TranslationContext> {
     let (module, cm) = parser::parse_module(&params.source, &params.lang)?;
     let const_bindings = const_eval::collect_consts(&module, &cm);
-    let scopes = scope::collect_scopes_precise(&module, &cm, &const_bindings);
+    let scopes =
+        scope::collect_scopes_precise(&module, &cm, &const_bindings, params.hook_names.as_deref());
 
     let found_scope = scopes
         .iter()
@@ -96,5 +306,122 @@ pub fn translation_context_at(params: TranslationContextParams) -> Result<Value>
         has_any_hook: !scopes.is_empty(),
     };
 
-    Ok(serde_json::to_value(result)?)
+    Ok(result)
+}
+
+/// Namespace for a new key at `row`, from the enclosing hook scope, and per
+/// language the resource file that already holds most of that namespace.
+pub fn suggest_namespace(params: SuggestNamespaceParams, cache: &IndexCache) -> Result<Value> {
+    let context = translation_context(TranslationContextParams {
+        source: params.source,
+        lang: params.lang,
+        row: params.row,
+        fallback_namespace: params.fallback_namespace,
+        hook_names: params.hook_names,
+    })?;
+    let Some(index) = cache.get(&params.cache_key) else {
+        return Ok(serde_json::json!({
+            "namespace": context.namespace,
+            "found_hook": context.found_hook,
+            "files": {},
+            "needs_rebuild": true
+        }));
+    };
+    let files = crate::resource::index::namespace_files(&index, &context.namespace);
+    Ok(serde_json::json!({
+        "namespace": context.namespace,
+        "found_hook": context.found_hook,
+        "files": files
+    }))
+}
+
+pub fn component_census(params: ComponentCensusParams, cache: &ProjectScanCache) -> Result<Value> {
+    census::component_census(params, cache)
+}
+
+/// Scan every source file of a project once and cache the results for later
+/// project-wide queries. Returns the cache token, or in `stream` mode sends
+/// `scan/fileResult` notifications and returns only counts.
+pub fn index_project(
+    params: IndexProjectParams,
+    cache: &ProjectScanCache,
+    notify: &dyn Fn(&str, Value),
+) -> Result<Value> {
+    project_cache::index_project(params, cache, notify)
+}
+
+/// Every call site of a canonical key in the project.
+pub fn references_for_key(
+    params: ReferencesForKeyParams,
+    cache: &ProjectScanCache,
+) -> Result<Value> {
+    project_cache::references_for_key(params, cache)
+}
+
+pub fn dynamic_keys(params: DynamicKeysParams) -> Result<Value> {
+    dynamic::dynamic_keys(params)
+}
+
+/// Distinct namespaces of the keys a file uses, sorted, e.g. to generate the `ns`
+/// list a route loads.
+pub fn namespaces_in_file(params: NamespacesInFileParams) -> Result<Value> {
+    let (module, cm) = parser::parse_module(&params.source, &params.lang)?;
+    let const_bindings = const_eval::collect_consts(&module, &cm);
+    let hook_names = params.hook_names.as_deref();
+    let scopes = scope::collect_scopes_precise(&module, &cm, &const_bindings, hook_names);
+    let items = call_extract::extract_calls(
+        &module,
+        &cm,
+        &const_bindings,
+        &scopes,
+        &params.fallback_namespace,
+        &None,
+        &call_extract::CallConfig {
+            hook_names,
+            ..Default::default()
+        },
+    );
+    let namespaces: BTreeSet<String> = items.into_iter().map(|item| item.namespace).collect();
+    Ok(serde_json::json!({ "namespaces": namespaces }))
+}
+
+/// Resolve the translation key whose argument span contains `(row, col)`.
+/// Returns `null` when the position is not inside a statically resolvable key.
+pub fn key_at(params: KeyAtParams) -> Result<Value> {
+    let (module, cm) = parser::parse_module(&params.source, &params.lang)?;
+    let const_bindings = const_eval::collect_consts(&module, &cm);
+    let scopes = scope::collect_scopes_precise(&module, &cm, &const_bindings, None);
+    let range = Some(Range {
+        start_line: params.row,
+        end_line: params.row,
+    });
+    let items = call_extract::extract_calls(
+        &module,
+        &cm,
+        &const_bindings,
+        &scopes,
+        &params.fallback_namespace,
+        &range,
+        &call_extract::CallConfig::default(),
+    );
+
+    let found = items
+        .into_iter()
+        .find(|item| params.col >= item.col && params.col < item.end_col);
+    Ok(match found {
+        Some(item) => serde_json::json!({
+            "key": item.key,
+            "namespace": item.namespace,
+            "raw": item.raw,
+        }),
+        None => Value::Null,
+    })
+}
+
+/// Debugging aid: kind and span of the innermost AST node containing `(row, col)`,
+/// or `null` when the position is outside the module.
+pub fn node_at(params: NodeAtParams) -> Result<Value> {
+    let (module, cm) = parser::parse_module(&params.source, &params.lang)?;
+    let node = node_at::node_at(&module, &cm, params.row, params.col);
+    Ok(serde_json::to_value(node)?)
 }
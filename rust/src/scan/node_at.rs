@@ -0,0 +1,598 @@
+use serde::Serialize;
+use swc_common::{SourceMap, Span, Spanned};
+use swc_ecma_ast::*;
+
+/// Innermost AST node containing a position, for debugging extraction.
+#[derive(Debug, Serialize)]
+pub(super) struct NodeAt {
+    pub(super) kind: &'static str,
+    pub(super) lnum: u32,
+    pub(super) col: u32,
+    pub(super) end_lnum: u32,
+    pub(super) end_col: u32,
+}
+
+/// Walk the statements, expressions and JSX of `module` and return the innermost
+/// node whose span contains `(row, col)`. Type annotations are not descended into.
+pub(super) fn node_at(module: &Module, cm: &SourceMap, row: u32, col: u32) -> Option<NodeAt> {
+    let mut finder = NodeFinder {
+        cm,
+        pos: (row, col),
+        found: None,
+    };
+    if finder.enter("Module", module.span) {
+        for item in &module.body {
+            finder.visit_module_item(item);
+        }
+    }
+    let (kind, span) = finder.found?;
+    let lo = cm.lookup_char_pos(span.lo);
+    let hi = cm.lookup_char_pos(span.hi);
+    Some(NodeAt {
+        kind,
+        lnum: lo.line as u32 - 1,
+        col: lo.col_display as u32,
+        end_lnum: hi.line as u32 - 1,
+        end_col: hi.col_display as u32,
+    })
+}
+
+struct NodeFinder<'a> {
+    cm: &'a SourceMap,
+    pos: (u32, u32),
+    found: Option<(&'static str, Span)>,
+}
+
+impl<'a> NodeFinder<'a> {
+    /// Record the node if it contains the position. Children are only visited when
+    /// this returns true, so the last recorded node is the innermost one.
+    fn enter(&mut self, kind: &'static str, span: Span) -> bool {
+        let lo = self.cm.lookup_char_pos(span.lo);
+        let hi = self.cm.lookup_char_pos(span.hi);
+        let start = (lo.line as u32 - 1, lo.col_display as u32);
+        let end = (hi.line as u32 - 1, hi.col_display as u32);
+        let contains = start <= self.pos && self.pos < end;
+        if contains {
+            self.found = Some((kind, span));
+        }
+        contains
+    }
+
+    fn visit_module_item(&mut self, item: &ModuleItem) {
+        match item {
+            ModuleItem::Stmt(stmt) => self.visit_stmt(stmt),
+            ModuleItem::ModuleDecl(decl) => match decl {
+                ModuleDecl::Import(import) => {
+                    self.enter("ImportDecl", import.span);
+                }
+                ModuleDecl::ExportDecl(export) => {
+                    if self.enter("ExportDecl", export.span) {
+                        self.visit_decl(&export.decl);
+                    }
+                }
+                ModuleDecl::ExportDefaultDecl(export) => {
+                    if self.enter("ExportDefaultDecl", export.span) {
+                        match &export.decl {
+                            DefaultDecl::Fn(fn_expr) => self.visit_function(&fn_expr.function),
+                            DefaultDecl::Class(class_expr) => self.visit_class(&class_expr.class),
+                            DefaultDecl::TsInterfaceDecl(_) => {}
+                        }
+                    }
+                }
+                ModuleDecl::ExportDefaultExpr(export) => {
+                    if self.enter("ExportDefaultExpr", export.span) {
+                        self.visit_expr(&export.expr);
+                    }
+                }
+                other => {
+                    self.enter("ModuleDecl", other.span());
+                }
+            },
+        }
+    }
+
+    fn visit_stmts(&mut self, stmts: &[Stmt]) {
+        for stmt in stmts {
+            self.visit_stmt(stmt);
+        }
+    }
+
+    fn visit_block(&mut self, block: &BlockStmt) {
+        if self.enter("BlockStmt", block.span) {
+            self.visit_stmts(&block.stmts);
+        }
+    }
+
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Block(block) => self.visit_block(block),
+            Stmt::Expr(expr_stmt) => {
+                if self.enter("ExprStmt", expr_stmt.span) {
+                    self.visit_expr(&expr_stmt.expr);
+                }
+            }
+            Stmt::Decl(decl) => self.visit_decl(decl),
+            Stmt::Return(ret) => {
+                if self.enter("ReturnStmt", ret.span) {
+                    if let Some(arg) = &ret.arg {
+                        self.visit_expr(arg);
+                    }
+                }
+            }
+            Stmt::Throw(throw) => {
+                if self.enter("ThrowStmt", throw.span) {
+                    self.visit_expr(&throw.arg);
+                }
+            }
+            Stmt::If(if_stmt) => {
+                if self.enter("IfStmt", if_stmt.span) {
+                    self.visit_expr(&if_stmt.test);
+                    self.visit_stmt(&if_stmt.cons);
+                    if let Some(alt) = &if_stmt.alt {
+                        self.visit_stmt(alt);
+                    }
+                }
+            }
+            Stmt::Labeled(labeled) => {
+                if self.enter("LabeledStmt", labeled.span) {
+                    self.visit_stmt(&labeled.body);
+                }
+            }
+            Stmt::Switch(switch) => {
+                if self.enter("SwitchStmt", switch.span) {
+                    self.visit_expr(&switch.discriminant);
+                    for case in &switch.cases {
+                        if self.enter("SwitchCase", case.span) {
+                            if let Some(test) = &case.test {
+                                self.visit_expr(test);
+                            }
+                            self.visit_stmts(&case.cons);
+                        }
+                    }
+                }
+            }
+            Stmt::Try(try_stmt) => {
+                if self.enter("TryStmt", try_stmt.span) {
+                    self.visit_block(&try_stmt.block);
+                    if let Some(handler) = &try_stmt.handler {
+                        if self.enter("CatchClause", handler.span) {
+                            self.visit_block(&handler.body);
+                        }
+                    }
+                    if let Some(finalizer) = &try_stmt.finalizer {
+                        self.visit_block(finalizer);
+                    }
+                }
+            }
+            Stmt::While(while_stmt) => {
+                if self.enter("WhileStmt", while_stmt.span) {
+                    self.visit_expr(&while_stmt.test);
+                    self.visit_stmt(&while_stmt.body);
+                }
+            }
+            Stmt::DoWhile(do_while) => {
+                if self.enter("DoWhileStmt", do_while.span) {
+                    self.visit_stmt(&do_while.body);
+                    self.visit_expr(&do_while.test);
+                }
+            }
+            Stmt::For(for_stmt) => {
+                if self.enter("ForStmt", for_stmt.span) {
+                    match &for_stmt.init {
+                        Some(VarDeclOrExpr::VarDecl(var)) => self.visit_var_decl(var),
+                        Some(VarDeclOrExpr::Expr(expr)) => self.visit_expr(expr),
+                        None => {}
+                    }
+                    for expr in [&for_stmt.test, &for_stmt.update].into_iter().flatten() {
+                        self.visit_expr(expr);
+                    }
+                    self.visit_stmt(&for_stmt.body);
+                }
+            }
+            Stmt::ForIn(for_in) => {
+                if self.enter("ForInStmt", for_in.span) {
+                    self.visit_expr(&for_in.right);
+                    self.visit_stmt(&for_in.body);
+                }
+            }
+            Stmt::ForOf(for_of) => {
+                if self.enter("ForOfStmt", for_of.span) {
+                    self.visit_expr(&for_of.right);
+                    self.visit_stmt(&for_of.body);
+                }
+            }
+            other => {
+                self.enter("Stmt", other.span());
+            }
+        }
+    }
+
+    fn visit_decl(&mut self, decl: &Decl) {
+        match decl {
+            Decl::Fn(fn_decl) => {
+                if self.enter("FnDecl", fn_decl.function.span) {
+                    self.visit_ident(&fn_decl.ident);
+                    self.visit_function(&fn_decl.function);
+                }
+            }
+            Decl::Class(class_decl) => {
+                if self.enter("ClassDecl", class_decl.class.span) {
+                    self.visit_ident(&class_decl.ident);
+                    self.visit_class(&class_decl.class);
+                }
+            }
+            Decl::Var(var) => self.visit_var_decl(var),
+            Decl::TsModule(ts_module) => {
+                if self.enter("TsModuleDecl", ts_module.span) {
+                    if let Some(TsNamespaceBody::TsModuleBlock(block)) = &ts_module.body {
+                        for item in &block.body {
+                            self.visit_module_item(item);
+                        }
+                    }
+                }
+            }
+            other => {
+                self.enter("Decl", other.span());
+            }
+        }
+    }
+
+    fn visit_var_decl(&mut self, var: &VarDecl) {
+        if !self.enter("VarDecl", var.span) {
+            return;
+        }
+        for declarator in &var.decls {
+            if self.enter("VarDeclarator", declarator.span) {
+                if let Pat::Ident(ident) = &declarator.name {
+                    self.visit_ident(&ident.id);
+                }
+                if let Some(init) = &declarator.init {
+                    self.visit_expr(init);
+                }
+            }
+        }
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        if let Some(body) = &function.body {
+            self.visit_block(body);
+        }
+    }
+
+    fn visit_class(&mut self, class: &Class) {
+        for member in &class.body {
+            match member {
+                ClassMember::Method(method) => {
+                    if self.enter("ClassMethod", method.span) {
+                        self.visit_function(&method.function);
+                    }
+                }
+                ClassMember::Constructor(constructor) => {
+                    if self.enter("Constructor", constructor.span) {
+                        if let Some(body) = &constructor.body {
+                            self.visit_block(body);
+                        }
+                    }
+                }
+                ClassMember::ClassProp(prop) => {
+                    if self.enter("ClassProp", prop.span) {
+                        if let Some(value) = &prop.value {
+                            self.visit_expr(value);
+                        }
+                    }
+                }
+                other => {
+                    self.enter("ClassMember", other.span());
+                }
+            }
+        }
+    }
+
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.enter("Ident", ident.span);
+    }
+
+    fn visit_args(&mut self, args: &[ExprOrSpread]) {
+        for arg in args {
+            self.visit_expr(&arg.expr);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Ident(ident) => self.visit_ident(ident),
+            Expr::Lit(lit) => {
+                let kind = match lit {
+                    Lit::Str(_) => "Str",
+                    Lit::Num(_) => "Number",
+                    Lit::Bool(_) => "Bool",
+                    Lit::Null(_) => "Null",
+                    Lit::BigInt(_) => "BigInt",
+                    Lit::Regex(_) => "Regex",
+                    Lit::JSXText(_) => "JSXText",
+                };
+                self.enter(kind, lit.span());
+            }
+            Expr::Tpl(tpl) => self.visit_tpl(tpl),
+            Expr::TaggedTpl(tagged) => {
+                if self.enter("TaggedTpl", tagged.span) {
+                    self.visit_expr(&tagged.tag);
+                    self.visit_tpl(&tagged.tpl);
+                }
+            }
+            Expr::Call(call) => {
+                if self.enter("CallExpr", call.span) {
+                    if let Callee::Expr(callee) = &call.callee {
+                        self.visit_expr(callee);
+                    }
+                    self.visit_args(&call.args);
+                }
+            }
+            Expr::New(new) => {
+                if self.enter("NewExpr", new.span) {
+                    self.visit_expr(&new.callee);
+                    if let Some(args) = &new.args {
+                        self.visit_args(args);
+                    }
+                }
+            }
+            Expr::Member(member) => self.visit_member(member),
+            Expr::OptChain(opt) => {
+                if self.enter("OptChainExpr", opt.span) {
+                    match opt.base.as_ref() {
+                        OptChainBase::Member(member) => self.visit_member(member),
+                        OptChainBase::Call(call) => {
+                            if self.enter("OptCall", call.span) {
+                                self.visit_expr(&call.callee);
+                                self.visit_args(&call.args);
+                            }
+                        }
+                    }
+                }
+            }
+            Expr::Array(arr) => {
+                if self.enter("ArrayLit", arr.span) {
+                    for elem in arr.elems.iter().flatten() {
+                        self.visit_expr(&elem.expr);
+                    }
+                }
+            }
+            Expr::Object(obj) => {
+                if self.enter("ObjectLit", obj.span) {
+                    for prop in &obj.props {
+                        self.visit_prop(prop);
+                    }
+                }
+            }
+            Expr::Fn(fn_expr) => {
+                if self.enter("FnExpr", fn_expr.function.span) {
+                    self.visit_function(&fn_expr.function);
+                }
+            }
+            Expr::Arrow(arrow) => {
+                if self.enter("ArrowExpr", arrow.span) {
+                    match arrow.body.as_ref() {
+                        BlockStmtOrExpr::BlockStmt(block) => self.visit_block(block),
+                        BlockStmtOrExpr::Expr(body) => self.visit_expr(body),
+                    }
+                }
+            }
+            Expr::Class(class_expr) => {
+                if self.enter("ClassExpr", class_expr.class.span) {
+                    self.visit_class(&class_expr.class);
+                }
+            }
+            Expr::Bin(bin) => {
+                if self.enter("BinExpr", bin.span) {
+                    self.visit_expr(&bin.left);
+                    self.visit_expr(&bin.right);
+                }
+            }
+            Expr::Unary(unary) => {
+                if self.enter("UnaryExpr", unary.span) {
+                    self.visit_expr(&unary.arg);
+                }
+            }
+            Expr::Update(update) => {
+                if self.enter("UpdateExpr", update.span) {
+                    self.visit_expr(&update.arg);
+                }
+            }
+            Expr::Assign(assign) => {
+                if self.enter("AssignExpr", assign.span) {
+                    self.visit_expr(&assign.right);
+                }
+            }
+            Expr::Cond(cond) => {
+                if self.enter("CondExpr", cond.span) {
+                    self.visit_expr(&cond.test);
+                    self.visit_expr(&cond.cons);
+                    self.visit_expr(&cond.alt);
+                }
+            }
+            Expr::Seq(seq) => {
+                if self.enter("SeqExpr", seq.span) {
+                    for expr in &seq.exprs {
+                        self.visit_expr(expr);
+                    }
+                }
+            }
+            Expr::Paren(paren) => {
+                if self.enter("ParenExpr", paren.span) {
+                    self.visit_expr(&paren.expr);
+                }
+            }
+            Expr::Await(await_expr) => {
+                if self.enter("AwaitExpr", await_expr.span) {
+                    self.visit_expr(&await_expr.arg);
+                }
+            }
+            Expr::Yield(yield_expr) => {
+                if self.enter("YieldExpr", yield_expr.span) {
+                    if let Some(arg) = &yield_expr.arg {
+                        self.visit_expr(arg);
+                    }
+                }
+            }
+            Expr::TsAs(ts_as) => {
+                if self.enter("TsAsExpr", ts_as.span) {
+                    self.visit_expr(&ts_as.expr);
+                }
+            }
+            Expr::TsSatisfies(ts_sat) => {
+                if self.enter("TsSatisfiesExpr", ts_sat.span) {
+                    self.visit_expr(&ts_sat.expr);
+                }
+            }
+            Expr::TsNonNull(ts_nn) => {
+                if self.enter("TsNonNullExpr", ts_nn.span) {
+                    self.visit_expr(&ts_nn.expr);
+                }
+            }
+            Expr::TsConstAssertion(ts_const) => {
+                if self.enter("TsConstAssertion", ts_const.span) {
+                    self.visit_expr(&ts_const.expr);
+                }
+            }
+            Expr::TsTypeAssertion(ts_assert) => {
+                if self.enter("TsTypeAssertion", ts_assert.span) {
+                    self.visit_expr(&ts_assert.expr);
+                }
+            }
+            Expr::JSXElement(element) => self.visit_jsx_element(element),
+            Expr::JSXFragment(fragment) => self.visit_jsx_fragment(fragment),
+            other => {
+                self.enter("Expr", other.span());
+            }
+        }
+    }
+
+    fn visit_tpl(&mut self, tpl: &Tpl) {
+        if self.enter("Tpl", tpl.span) {
+            for expr in &tpl.exprs {
+                self.visit_expr(expr);
+            }
+        }
+    }
+
+    fn visit_member(&mut self, member: &MemberExpr) {
+        if self.enter("MemberExpr", member.span) {
+            self.visit_expr(&member.obj);
+            match &member.prop {
+                MemberProp::Ident(prop) => {
+                    self.enter("IdentName", prop.span);
+                }
+                MemberProp::Computed(computed) => {
+                    if self.enter("ComputedPropName", computed.span) {
+                        self.visit_expr(&computed.expr);
+                    }
+                }
+                MemberProp::PrivateName(name) => {
+                    self.enter("PrivateName", name.span);
+                }
+            }
+        }
+    }
+
+    fn visit_prop(&mut self, prop: &PropOrSpread) {
+        match prop {
+            PropOrSpread::Spread(spread) => {
+                if self.enter("SpreadElement", spread.span()) {
+                    self.visit_expr(&spread.expr);
+                }
+            }
+            PropOrSpread::Prop(prop) => match prop.as_ref() {
+                Prop::Shorthand(ident) => self.visit_ident(ident),
+                Prop::KeyValue(kv) => {
+                    if self.enter("KeyValueProp", kv.span()) {
+                        self.enter("PropName", kv.key.span());
+                        self.visit_expr(&kv.value);
+                    }
+                }
+                Prop::Method(method) => {
+                    if self.enter("MethodProp", method.span()) {
+                        self.visit_function(&method.function);
+                    }
+                }
+                other => {
+                    self.enter("Prop", other.span());
+                }
+            },
+        }
+    }
+
+    fn visit_jsx_element(&mut self, element: &JSXElement) {
+        if !self.enter("JSXElement", element.span) {
+            return;
+        }
+        let opening = &element.opening;
+        if self.enter("JSXOpeningElement", opening.span) {
+            for attr in &opening.attrs {
+                match attr {
+                    JSXAttrOrSpread::JSXAttr(attr) => {
+                        if self.enter("JSXAttr", attr.span) {
+                            match &attr.value {
+                                Some(JSXAttrValue::Str(s)) => {
+                                    self.enter("Str", s.span);
+                                }
+                                Some(JSXAttrValue::JSXExprContainer(container)) => {
+                                    self.visit_jsx_expr_container(container);
+                                }
+                                Some(JSXAttrValue::JSXElement(element)) => {
+                                    self.visit_jsx_element(element);
+                                }
+                                Some(JSXAttrValue::JSXFragment(fragment)) => {
+                                    self.visit_jsx_fragment(fragment);
+                                }
+                                None => {}
+                            }
+                        }
+                    }
+                    JSXAttrOrSpread::SpreadElement(spread) => {
+                        if self.enter("SpreadElement", spread.span()) {
+                            self.visit_expr(&spread.expr);
+                        }
+                    }
+                }
+            }
+        }
+        self.visit_jsx_children(&element.children);
+        if let Some(closing) = &element.closing {
+            self.enter("JSXClosingElement", closing.span);
+        }
+    }
+
+    fn visit_jsx_fragment(&mut self, fragment: &JSXFragment) {
+        if self.enter("JSXFragment", fragment.span) {
+            self.visit_jsx_children(&fragment.children);
+        }
+    }
+
+    fn visit_jsx_expr_container(&mut self, container: &JSXExprContainer) {
+        if self.enter("JSXExprContainer", container.span) {
+            if let JSXExpr::Expr(expr) = &container.expr {
+                self.visit_expr(expr);
+            }
+        }
+    }
+
+    fn visit_jsx_children(&mut self, children: &[JSXElementChild]) {
+        for child in children {
+            match child {
+                JSXElementChild::JSXText(text) => {
+                    self.enter("JSXText", text.span);
+                }
+                JSXElementChild::JSXExprContainer(container) => {
+                    self.visit_jsx_expr_container(container);
+                }
+                JSXElementChild::JSXSpreadChild(spread) => {
+                    if self.enter("JSXSpreadChild", spread.span) {
+                        self.visit_expr(&spread.expr);
+                    }
+                }
+                JSXElementChild::JSXElement(element) => self.visit_jsx_element(element),
+                JSXElementChild::JSXFragment(fragment) => self.visit_jsx_fragment(fragment),
+            }
+        }
+    }
+}
@@ -3,6 +3,21 @@ use swc_common::{FileName, SourceMap, Span, input::SourceFileInput, sync::Lrc};
 use swc_ecma_ast::Module;
 use swc_ecma_parser::{EsSyntax, Parser, Syntax, TsSyntax, lexer::Lexer};
 
+/// Drop a leading BOM and blank out a leading `#!` line so the parser never sees
+/// either. The shebang is replaced with spaces rather than removed, so every
+/// reported line/column still matches the original source.
+fn strip_preamble(source: &str) -> String {
+    let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+    if !source.starts_with("#!") {
+        return source.to_string();
+    }
+    let line_end = source.find('\n').unwrap_or(source.len());
+    let mut out = String::with_capacity(source.len());
+    out.extend(source[..line_end].chars().map(|_| ' '));
+    out.push_str(&source[line_end..]);
+    out
+}
+
 pub(crate) fn parse_module(source: &str, lang: &str) -> Result<(Module, Lrc<SourceMap>)> {
     if source.len() > crate::util::MAX_SOURCE_BYTES {
         return Err(anyhow::anyhow!(
@@ -13,13 +28,15 @@ pub(crate) fn parse_module(source: &str, lang: &str) -> Result<(Module, Lrc<Sour
     }
 
     let cm: Lrc<SourceMap> = Default::default();
-    let fm = cm.new_source_file(
-        Lrc::new(FileName::Custom("input".into())),
-        source.to_string(),
-    );
+    let source = if lang == "astro" {
+        super::astro::to_module_source(source)
+    } else {
+        strip_preamble(source)
+    };
+    let fm = cm.new_source_file(Lrc::new(FileName::Custom("input".into())), source);
 
     let syntax = match lang {
-        "tsx" => Syntax::Typescript(TsSyntax {
+        "tsx" | "astro" => Syntax::Typescript(TsSyntax {
             tsx: true,
             ..Default::default()
         }),
@@ -27,12 +44,15 @@ pub(crate) fn parse_module(source: &str, lang: &str) -> Result<(Module, Lrc<Sour
             tsx: false,
             ..Default::default()
         }),
+        // TypeScript always accepts import attributes; plain JS must opt in.
         "jsx" => Syntax::Es(EsSyntax {
             jsx: true,
+            import_attributes: true,
             ..Default::default()
         }),
         _ => Syntax::Es(EsSyntax {
             jsx: true,
+            import_attributes: true,
             ..Default::default()
         }),
     };
@@ -88,4 +108,19 @@ mod tests {
 
         assert!(worker.join().expect("worker should not overflow"));
     }
+
+    #[test]
+    fn parses_import_attributes_in_every_language() {
+        let source = r#"import data from "./data.json" with { type: "json" };
+import legacy from "./legacy.json" assert { type: "json" };
+t("title");
+"#;
+        for lang in ["javascript", "jsx", "typescript", "tsx"] {
+            assert!(
+                parse_module(source, lang).is_ok(),
+                "{} should accept import attributes",
+                lang
+            );
+        }
+    }
 }
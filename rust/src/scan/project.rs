@@ -0,0 +1,25 @@
+use std::path::{Path, PathBuf};
+
+use crate::util::is_js_ts_file;
+
+/// JS/TS source files under `root`, honoring ignore files.
+/// Returns `None` when `is_cancelled` fires during the walk.
+pub(super) fn collect_source_files(
+    root: &Path,
+    is_cancelled: &dyn Fn() -> bool,
+) -> Option<Vec<PathBuf>> {
+    let mut source_files = Vec::new();
+    for entry in ignore::WalkBuilder::new(root).build() {
+        if is_cancelled() {
+            return None;
+        }
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let path = entry.path();
+        if path.is_file() && is_js_ts_file(path) {
+            source_files.push(path.to_path_buf());
+        }
+    }
+    Some(source_files)
+}
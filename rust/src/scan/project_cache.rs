@@ -0,0 +1,324 @@
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use super::component::{self, ComponentSpan};
+use super::{ScanItem, call_extract, const_eval, parser, project, scope};
+use crate::util::{is_cancelled, lang_from_extension, path_to_string};
+
+#[derive(Debug, Deserialize)]
+pub struct IndexProjectParams {
+    pub project_root: String,
+    pub fallback_namespace: String,
+    #[serde(default)]
+    pub cancel_token_path: Option<String>,
+    /// Send each file's items as a `scan/fileResult` notification instead of
+    /// caching the scan; the response then only carries counts.
+    #[serde(default)]
+    pub stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReferencesForKeyParams {
+    pub project_root: String,
+    pub fallback_namespace: String,
+    /// Canonical key, e.g. `common:title`.
+    pub key: String,
+    #[serde(default)]
+    pub cancel_token_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KeyReference {
+    pub file: String,
+    pub lnum: u32,
+    pub col: u32,
+    pub end_col: u32,
+}
+
+/// Scan results of one source file.
+#[derive(Clone)]
+pub(super) struct FileScan {
+    pub(super) file: String,
+    pub(super) items: Vec<ScanItem>,
+    pub(super) components: Vec<ComponentSpan>,
+}
+
+/// Scan results of every source file of a project.
+pub(super) struct ProjectScan {
+    pub(super) files: Vec<FileScan>,
+}
+
+/// Number of project scans `ProjectScanCache::new` keeps before evicting the least
+/// recently used.
+const DEFAULT_PROJECT_SCAN_CACHE_CAPACITY: usize = 4;
+
+/// In-process cache of project scans built by `scan/indexProject`, keyed by project
+/// root and scan configuration. Project-wide queries with the same configuration
+/// answer from it instead of walking and parsing the tree again. Bounded like
+/// `IndexCache`, and files deleted since the scan are dropped on lookup.
+pub struct ProjectScanCache {
+    entries: Mutex<ScanEntries>,
+    capacity: usize,
+}
+
+#[derive(Default)]
+struct ScanEntries {
+    /// Scan and the tick of its last access.
+    map: HashMap<String, (Arc<ProjectScan>, u64)>,
+    tick: u64,
+}
+
+impl ScanEntries {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
+
+impl ProjectScanCache {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_PROJECT_SCAN_CACHE_CAPACITY)
+    }
+
+    /// Cache holding at most `capacity` project scans (at least one).
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(ScanEntries::default()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    fn lock_entries(&self) -> MutexGuard<'_, ScanEntries> {
+        match self.entries.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                // Same recovery as `IndexCache`: drop possibly half-updated state
+                // and keep serving.
+                self.entries.clear_poison();
+                let mut entries = poisoned.into_inner();
+                entries.map.clear();
+                entries
+            }
+        }
+    }
+
+    pub(super) fn get(&self, token: &str) -> Option<Arc<ProjectScan>> {
+        let mut entries = self.lock_entries();
+        let tick = entries.next_tick();
+        let (scan, last_used) = entries.map.get_mut(token)?;
+        *last_used = tick;
+        if scan.files.iter().any(|f| !Path::new(&f.file).exists()) {
+            let files = scan
+                .files
+                .iter()
+                .filter(|f| Path::new(&f.file).exists())
+                .cloned()
+                .collect();
+            *scan = Arc::new(ProjectScan { files });
+        }
+        Some(Arc::clone(scan))
+    }
+
+    fn set(&self, token: String, scan: ProjectScan) {
+        let mut entries = self.lock_entries();
+        let tick = entries.next_tick();
+        entries.map.insert(token, (Arc::new(scan), tick));
+        while entries.map.len() > self.capacity {
+            let Some(oldest) = entries
+                .map
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(token, _)| token.clone())
+            else {
+                break;
+            };
+            entries.map.remove(&oldest);
+        }
+    }
+}
+
+impl Default for ProjectScanCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cache token for a project root scanned with `fallback_namespace`.
+pub(super) fn project_token(project_root: &str, fallback_namespace: &str) -> String {
+    serde_json::json!([project_root, fallback_namespace]).to_string()
+}
+
+/// Translation calls and components of one file. Unreadable or unparsable files
+/// yield `None`.
+pub(super) fn scan_file(path: &Path, fallback_namespace: &str) -> Option<FileScan> {
+    let source = std::fs::read_to_string(path).ok()?;
+    let (module, cm) = parser::parse_module(&source, lang_from_extension(path)).ok()?;
+    let const_bindings = const_eval::collect_consts(&module, &cm);
+    let scopes = scope::collect_scopes_precise(&module, &cm, &const_bindings, None);
+    let items = call_extract::extract_calls(
+        &module,
+        &cm,
+        &const_bindings,
+        &scopes,
+        fallback_namespace,
+        &None,
+        &call_extract::CallConfig::default(),
+    );
+    Some(FileScan {
+        file: path_to_string(path),
+        items,
+        components: component::collect_components(&module, &cm),
+    })
+}
+
+/// Scan every source file under `project_root` in parallel chunks, handing each
+/// chunk's results to `on_chunk`. Returns false when cancelled.
+fn scan_project_chunks(
+    project_root: &str,
+    fallback_namespace: &str,
+    cancel_token_path: Option<&str>,
+    on_chunk: &mut dyn FnMut(Vec<FileScan>),
+) -> bool {
+    let is_cancelled_now = || is_cancelled(cancel_token_path);
+    let Some(source_files) =
+        project::collect_source_files(Path::new(project_root), &is_cancelled_now)
+    else {
+        return false;
+    };
+
+    for chunk in source_files.chunks(50) {
+        if is_cancelled_now() {
+            return false;
+        }
+        let results: Vec<Option<FileScan>> = chunk
+            .par_iter()
+            .map(|path| scan_file(path, fallback_namespace))
+            .collect();
+        on_chunk(results.into_iter().flatten().collect());
+    }
+    true
+}
+
+/// Scan every source file under `project_root`. Returns `None` when cancelled.
+pub(super) fn scan_project(
+    project_root: &str,
+    fallback_namespace: &str,
+    cancel_token_path: Option<&str>,
+) -> Option<ProjectScan> {
+    let mut files = Vec::new();
+    let completed = scan_project_chunks(
+        project_root,
+        fallback_namespace,
+        cancel_token_path,
+        &mut |chunk| files.extend(chunk),
+    );
+    completed.then_some(ProjectScan { files })
+}
+
+/// Streaming `scan/indexProject`: results are sent per file and not kept.
+fn stream_project(params: &IndexProjectParams, notify: &dyn Fn(&str, Value)) -> Value {
+    let mut file_count = 0usize;
+    let mut item_count = 0usize;
+    let completed = scan_project_chunks(
+        &params.project_root,
+        &params.fallback_namespace,
+        params.cancel_token_path.as_deref(),
+        &mut |chunk| {
+            for file_scan in chunk {
+                file_count += 1;
+                item_count += file_scan.items.len();
+                notify(
+                    "scan/fileResult",
+                    serde_json::json!({
+                        "file": file_scan.file,
+                        "items": file_scan.items
+                    }),
+                );
+            }
+        },
+    );
+    serde_json::json!({
+        "file_count": file_count,
+        "item_count": item_count,
+        "cancelled": !completed
+    })
+}
+
+pub(super) fn index_project(
+    params: IndexProjectParams,
+    cache: &ProjectScanCache,
+    notify: &dyn Fn(&str, Value),
+) -> Result<Value> {
+    if params.stream {
+        return Ok(stream_project(&params, notify));
+    }
+    let token = project_token(&params.project_root, &params.fallback_namespace);
+    let Some(scan) = scan_project(
+        &params.project_root,
+        &params.fallback_namespace,
+        params.cancel_token_path.as_deref(),
+    ) else {
+        return Ok(serde_json::json!({ "cancelled": true }));
+    };
+    let file_count = scan.files.len();
+    cache.set(token.clone(), scan);
+    Ok(serde_json::json!({
+        "cache_token": token,
+        "file_count": file_count,
+        "cancelled": false
+    }))
+}
+
+pub(super) fn references_for_key(
+    params: ReferencesForKeyParams,
+    cache: &ProjectScanCache,
+) -> Result<Value> {
+    let token = project_token(&params.project_root, &params.fallback_namespace);
+    let (scan, cached) = match cache.get(&token) {
+        Some(scan) => (scan, true),
+        None => match scan_project(
+            &params.project_root,
+            &params.fallback_namespace,
+            params.cancel_token_path.as_deref(),
+        ) {
+            Some(scan) => (Arc::new(scan), false),
+            None => {
+                return Ok(serde_json::json!({ "references": [], "cancelled": true }));
+            }
+        },
+    };
+
+    let mut references: Vec<KeyReference> = scan
+        .files
+        .iter()
+        .flat_map(|file_scan| {
+            file_scan
+                .items
+                .iter()
+                .filter(|item| item.key == params.key)
+                .map(|item| KeyReference {
+                    file: file_scan.file.clone(),
+                    lnum: item.lnum,
+                    col: item.col,
+                    end_col: item.end_col,
+                })
+        })
+        .collect();
+    references.sort_by(|a, b| {
+        a.file
+            .cmp(&b.file)
+            .then_with(|| a.lnum.cmp(&b.lnum))
+            .then_with(|| a.col.cmp(&b.col))
+    });
+
+    Ok(serde_json::json!({
+        "references": references,
+        "cached": cached,
+        "cancelled": false
+    }))
+}
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use serde_json::Value;
+use std::collections::HashMap;
 
 use super::{ExtractResourceParams, ScanItem};
 
@@ -9,6 +10,9 @@ struct JsonLeaf {
     lnum: u32,
     col: u32,
     end_col: u32,
+    /// Byte range of the value: the text between the quotes for a string, the
+    /// whole token otherwise.
+    value_range: (u32, u32),
 }
 
 struct JsonLeafScanner<'a> {
@@ -213,12 +217,18 @@ impl<'a> JsonLeafScanner<'a> {
         Ok(())
     }
 
-    fn push_leaf(&mut self, path: &[String], lnum: u32, col: u32, end_col: u32) {
+    fn push_leaf(&mut self, path: &[String], key: (u32, u32, u32), value_start: usize) {
+        let (lnum, col, end_col) = key;
+        let mut value_range = (value_start as u32, self.idx as u32);
+        if self.source[value_start..].starts_with('"') {
+            value_range = (value_range.0 + 1, value_range.1 - 1);
+        }
         self.leaves.push(JsonLeaf {
             path: path.to_vec(),
             lnum,
             col,
             end_col,
+            value_range,
         });
     }
 
@@ -271,41 +281,43 @@ impl<'a> JsonLeafScanner<'a> {
         key_end_col: u32,
         depth: usize,
     ) -> Result<()> {
+        let key = (key_line, key_col, key_end_col);
+        let value_start = self.idx;
         match self.peek_char() {
             Some('{') => self.parse_object(path, skip_emit, depth + 1),
             Some('[') => self.parse_array(path, depth + 1),
             Some('"') => {
                 let _ = self.parse_string()?;
                 if !skip_emit {
-                    self.push_leaf(path, key_line, key_col, key_end_col);
+                    self.push_leaf(path, key, value_start);
                 }
                 Ok(())
             }
             Some('-' | '0'..='9') => {
                 self.parse_number()?;
                 if !skip_emit {
-                    self.push_leaf(path, key_line, key_col, key_end_col);
+                    self.push_leaf(path, key, value_start);
                 }
                 Ok(())
             }
             Some('t') => {
                 self.expect_literal("true")?;
                 if !skip_emit {
-                    self.push_leaf(path, key_line, key_col, key_end_col);
+                    self.push_leaf(path, key, value_start);
                 }
                 Ok(())
             }
             Some('f') => {
                 self.expect_literal("false")?;
                 if !skip_emit {
-                    self.push_leaf(path, key_line, key_col, key_end_col);
+                    self.push_leaf(path, key, value_start);
                 }
                 Ok(())
             }
             Some('n') => {
                 self.expect_literal("null")?;
                 if !skip_emit {
-                    self.push_leaf(path, key_line, key_col, key_end_col);
+                    self.push_leaf(path, key, value_start);
                 }
                 Ok(())
             }
@@ -363,6 +375,15 @@ impl<'a> JsonLeafScanner<'a> {
     }
 }
 
+/// Line of every leaf in a JSON resource, keyed by its dot-joined path.
+pub(crate) fn leaf_lines(source: &str) -> Result<HashMap<String, u32>> {
+    let leaves = JsonLeafScanner::new(source).parse()?;
+    Ok(leaves
+        .into_iter()
+        .map(|leaf| (leaf.path.join("."), leaf.lnum))
+        .collect())
+}
+
 pub(super) fn extract_resource(params: ExtractResourceParams) -> Result<Value> {
     let leaves = JsonLeafScanner::new(&params.source).parse()?;
     let mut items = Vec::new();
@@ -392,7 +413,15 @@ pub(super) fn extract_resource(params: ExtractResourceParams) -> Result<Value> {
                 lnum: leaf.lnum,
                 col: leaf.col,
                 end_col: leaf.end_col,
+                value_range: Some(leaf.value_range),
                 fallback: false,
+                default_value: None,
+                return_objects: false,
+                has_options: false,
+                context: Vec::new(),
+                scope_hook_line: None,
+                fallback_group: None,
+                call_shape: None,
             });
         } else {
             if leaf.path.is_empty() {
@@ -406,7 +435,15 @@ pub(super) fn extract_resource(params: ExtractResourceParams) -> Result<Value> {
                 lnum: leaf.lnum,
                 col: leaf.col,
                 end_col: leaf.end_col,
+                value_range: Some(leaf.value_range),
                 fallback: false,
+                default_value: None,
+                return_objects: false,
+                has_options: false,
+                context: Vec::new(),
+                scope_hook_line: None,
+                fallback_group: None,
+                call_shape: None,
             });
         }
     }
@@ -500,4 +537,15 @@ mod tests {
         assert_eq!(items[0].key, "common:login.title");
         assert_eq!(items[0].lnum, 2);
     }
+
+    #[test]
+    fn records_byte_range_of_leaf_values() {
+        let source = "{\n  \"title\": \"Log in \\\"now\\\"\",\n  \"count\": 42\n}";
+        let items = extract_items(source, "common", false, None);
+
+        let (start, end) = items[0].value_range.expect("string leaf has a range");
+        assert_eq!(&source[start as usize..end as usize], "Log in \\\"now\\\"");
+        let (start, end) = items[1].value_range.expect("number leaf has a range");
+        assert_eq!(&source[start as usize..end as usize], "42");
+    }
 }
@@ -1,6 +1,7 @@
 use swc_common::SourceMap;
 use swc_ecma_ast::*;
 
+use super::call_extract::option_prop;
 use super::const_eval::{ConstBinding, eval_string_expr};
 use super::parser::{span_to_lines, span_to_loc};
 
@@ -8,15 +9,23 @@ use super::parser::{span_to_lines, span_to_loc};
 pub(super) struct NamespaceScope {
     pub(super) ns: Option<String>,
     pub(super) t_func: Option<String>,
+    /// i18next `keyPrefix` option, prepended to keys without an explicit namespace.
+    pub(super) key_prefix: Option<String>,
+    /// Line of the hook call establishing the scope.
+    pub(super) hook_line: u32,
     pub(super) start_line: u32,
     pub(super) end_line: u32,
 }
 
-pub(super) fn is_translation_hook(name: &str) -> bool {
-    matches!(
-        name,
-        "useTranslation" | "useTranslations" | "getTranslations"
-    )
+const DEFAULT_HOOK_NAMES: [&str; 3] = ["useTranslation", "useTranslations", "getTranslations"];
+
+/// Whether `name` is a hook establishing a namespace scope. `hook_names` replaces
+/// the built-in list when set, for projects wrapping the hook (`useAppTranslation`).
+pub(super) fn is_translation_hook(name: &str, hook_names: Option<&[String]>) -> bool {
+    match hook_names {
+        Some(names) => names.iter().any(|hook| hook == name),
+        None => DEFAULT_HOOK_NAMES.contains(&name),
+    }
 }
 
 fn get_callee_name(callee: &Callee) -> Option<String> {
@@ -38,6 +47,19 @@ fn get_first_string_arg(
         .and_then(|arg| eval_string_expr(&arg.expr, line, const_bindings))
 }
 
+/// `keyPrefix` from the hook's options argument:
+/// `useTranslation("common", { keyPrefix: "dashboard" })`.
+fn get_key_prefix(
+    args: &[ExprOrSpread],
+    line: u32,
+    const_bindings: &[ConstBinding],
+) -> Option<String> {
+    let Expr::Object(obj) = args.get(1)?.expr.as_ref() else {
+        return None;
+    };
+    option_prop(obj, "keyPrefix").and_then(|value| eval_string_expr(value, line, const_bindings))
+}
+
 fn extract_hook_call(expr: &Expr) -> Option<&CallExpr> {
     match expr {
         Expr::Call(call) => Some(call),
@@ -62,8 +84,15 @@ fn detect_t_func_name(name: &Pat) -> Option<String> {
                     ObjectPatProp::KeyValue(kv) => {
                         if let PropName::Ident(key) = &kv.key {
                             if key.sym.as_ref() == "t" {
-                                if let Pat::Ident(value) = &*kv.value {
-                                    return Some(value.sym.to_string());
+                                // `{ t: tr }` or, with a default, `{ t: tr = fallback }`
+                                match &*kv.value {
+                                    Pat::Ident(value) => return Some(value.sym.to_string()),
+                                    Pat::Assign(assign) => {
+                                        if let Pat::Ident(value) = &*assign.left {
+                                            return Some(value.sym.to_string());
+                                        }
+                                    }
+                                    _ => {}
                                 }
                             }
                         }
@@ -87,10 +116,12 @@ pub(super) fn collect_scopes_precise(
     module: &Module,
     cm: &SourceMap,
     const_bindings: &[ConstBinding],
+    hook_names: Option<&[String]>,
 ) -> Vec<NamespaceScope> {
     let mut collector = ScopeCollector {
         cm,
         const_bindings,
+        hook_names,
         scopes: Vec::new(),
     };
     for item in &module.body {
@@ -105,6 +136,7 @@ pub(super) fn collect_scopes_precise(
 struct ScopeCollector<'a> {
     cm: &'a SourceMap,
     const_bindings: &'a [ConstBinding],
+    hook_names: Option<&'a [String]>,
     scopes: Vec<NamespaceScope>,
 }
 
@@ -140,13 +172,17 @@ impl<'a> ScopeCollector<'a> {
             Stmt::Expr(expr_stmt) => {
                 if let Some(call) = extract_hook_call(expr_stmt.expr.as_ref()) {
                     if let Some(name) = get_callee_name(&call.callee) {
-                        if is_translation_hook(&name) {
+                        if is_translation_hook(&name, self.hook_names) {
                             let (call_line, _, _) = span_to_loc(self.cm, call.span);
                             let ns =
                                 get_first_string_arg(&call.args, call_line, self.const_bindings);
+                            let key_prefix =
+                                get_key_prefix(&call.args, call_line, self.const_bindings);
                             self.scopes.push(NamespaceScope {
                                 ns,
                                 t_func: Some("t".to_string()),
+                                key_prefix,
+                                hook_line: call_line,
                                 start_line: scope_start,
                                 end_line: scope_end,
                             });
@@ -182,17 +218,21 @@ impl<'a> ScopeCollector<'a> {
                     if let Some(init) = &declarator.init {
                         if let Some(call) = extract_hook_call(init.as_ref()) {
                             if let Some(name) = get_callee_name(&call.callee) {
-                                if is_translation_hook(&name) {
+                                if is_translation_hook(&name, self.hook_names) {
                                     let (call_line, _, _) = span_to_loc(self.cm, call.span);
                                     let ns = get_first_string_arg(
                                         &call.args,
                                         call_line,
                                         self.const_bindings,
                                     );
+                                    let key_prefix =
+                                        get_key_prefix(&call.args, call_line, self.const_bindings);
                                     let t_func = detect_t_func_name(&declarator.name);
                                     self.scopes.push(NamespaceScope {
                                         ns,
                                         t_func,
+                                        key_prefix,
+                                        hook_line: call_line,
                                         start_line: scope_start,
                                         end_line: scope_end,
                                     });
@@ -211,10 +251,33 @@ impl<'a> ScopeCollector<'a> {
                     }
                 }
             }
+            Decl::TsModule(ts_module) => {
+                if let Some(body) = &ts_module.body {
+                    self.visit_ts_namespace_body(body, scope_start, scope_end);
+                }
+            }
             _ => {}
         }
     }
 
+    fn visit_ts_namespace_body(
+        &mut self,
+        body: &TsNamespaceBody,
+        scope_start: u32,
+        scope_end: u32,
+    ) {
+        match body {
+            TsNamespaceBody::TsModuleBlock(block) => {
+                for item in &block.body {
+                    self.visit_module_item(item, scope_start, scope_end);
+                }
+            }
+            TsNamespaceBody::TsNamespaceDecl(decl) => {
+                self.visit_ts_namespace_body(&decl.body, scope_start, scope_end)
+            }
+        }
+    }
+
     fn visit_expr(&mut self, expr: &Expr, _scope_start: u32, _scope_end: u32) {
         match expr {
             Expr::Arrow(arrow) => match &*arrow.body {
@@ -257,7 +320,7 @@ mod tests {
     fn collect_scopes(source: &str) -> Vec<NamespaceScope> {
         let (module, cm) = parse_module(source, "tsx").expect("source should parse");
         let const_bindings = collect_consts(&module, &cm);
-        collect_scopes_precise(&module, &cm, &const_bindings)
+        collect_scopes_precise(&module, &cm, &const_bindings, None)
     }
 
     #[test]
@@ -276,6 +339,42 @@ function Page() {
         assert_eq!(scopes[0].t_func.as_deref(), Some("tt"));
     }
 
+    #[test]
+    fn collects_alias_from_destructured_binding_with_default() {
+        let scopes = collect_scopes(
+            r#"
+function Page() {
+  const { t: tr = fallbackT } = useTranslation("dashboard");
+  return tr("title");
+}
+"#,
+        );
+
+        assert_eq!(scopes.len(), 1);
+        assert_eq!(scopes[0].ns.as_deref(), Some("dashboard"));
+        assert_eq!(scopes[0].t_func.as_deref(), Some("tr"));
+    }
+
+    #[test]
+    fn resolves_hook_namespace_from_string_enum_member() {
+        let scopes = collect_scopes(
+            r#"
+enum Namespaces {
+  Dashboard = "dashboard",
+  Settings = "settings",
+}
+
+function Page() {
+  const { t } = useTranslation(Namespaces.Dashboard);
+  return t("title");
+}
+"#,
+        );
+
+        assert_eq!(scopes.len(), 1);
+        assert_eq!(scopes[0].ns.as_deref(), Some("dashboard"));
+    }
+
     #[test]
     fn registers_default_t_for_bare_hook_call() {
         let scopes = collect_scopes(
@@ -1,5 +1,6 @@
 use serde_json::Value;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 
 /// Maximum nesting depth for our hand-written recursive descent over untrusted input
 /// (the resource JSON scanner). Pathologically deep structures are turned into errors
@@ -20,6 +21,44 @@ pub const SERVER_STACK_SIZE: usize = 256 * 1024 * 1024;
 /// a stack overflow. Hand-written i18n source is far smaller than this.
 pub const MAX_SOURCE_BYTES: usize = 4 * 1024 * 1024;
 
+/// Whether `path` is a JS/TS source file the scanner understands.
+pub fn is_js_ts_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" | "mts" | "cts" | "astro")
+    )
+}
+
+/// Parser language for a source file, derived from its extension.
+pub fn lang_from_extension(path: &Path) -> &str {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("tsx") => "tsx",
+        Some("jsx") => "jsx",
+        Some("astro") => "astro",
+        Some("ts" | "mts" | "cts") => "typescript",
+        _ => "javascript",
+    }
+}
+
+/// Path as emitted in `file` fields. Separators are always `/` so the client can
+/// match buffers the same way on every platform.
+pub fn path_to_string(path: &Path) -> String {
+    let text = path.to_string_lossy();
+    if cfg!(windows) {
+        text.replace('\\', "/")
+    } else {
+        text.into_owned()
+    }
+}
+
+/// Long-running requests are cancelled by the client creating the token file.
+pub fn is_cancelled(token_path: Option<&str>) -> bool {
+    match token_path {
+        Some(path) if !path.is_empty() => Path::new(path).exists(),
+        _ => false,
+    }
+}
+
 /// Flatten a nested JSON object into dot-separated keys.
 /// e.g. {"a": {"b": "c"}} -> {"a.b": "c"}
 pub fn flatten_table(value: &Value, prefix: &str) -> BTreeMap<String, String> {
@@ -28,6 +67,22 @@ pub fn flatten_table(value: &Value, prefix: &str) -> BTreeMap<String, String> {
     result
 }
 
+/// Like `flatten_table`, but only one level deep: top-level keys are kept verbatim even
+/// when they contain dots, and nested values are not expanded into dotted keys.
+pub fn flatten_top_level(value: &Value) -> BTreeMap<String, String> {
+    let mut result = BTreeMap::new();
+    if let Value::Object(map) = value {
+        for (key, val) in map {
+            let text = match val {
+                Value::String(s) => s.clone(),
+                _ => val.to_string(),
+            };
+            result.insert(key.clone(), text);
+        }
+    }
+    result
+}
+
 fn flatten_recursive(value: &Value, prefix: &str, result: &mut BTreeMap<String, String>) {
     match value {
         Value::Object(map) => {
@@ -51,35 +106,158 @@ fn flatten_recursive(value: &Value, prefix: &str, result: &mut BTreeMap<String,
 }
 
 /// Extract placeholder names from a translation value.
-/// Supports both {{name}} (i18next) and {name} (next-intl / ICU) formats.
+/// Supports {{name}} (i18next), {name} (next-intl / ICU) and { $name } (Fluent)
+/// formats. For ICU `{count, plural, one {# item} other {{count} items}}` the
+/// argument name and the arguments referenced inside each branch are taken, not
+/// the `plural`/`select` keyword or the `#` shorthand.
 pub fn extract_placeholders(text: &str) -> Vec<String> {
+    let mut placeholders = extract_placeholders_multiset(text);
+    placeholders.dedup();
+    placeholders
+}
+
+/// Like `extract_placeholders`, but a placeholder used several times is listed once
+/// per use, so `"{{name}} and {{name}}"` differs from `"{{name}}"`.
+pub fn extract_placeholders_multiset(text: &str) -> Vec<String> {
     let mut placeholders = Vec::new();
+    collect_placeholders(text, &mut placeholders);
+    placeholders.sort();
+    placeholders
+}
+
+fn collect_placeholders(text: &str, placeholders: &mut Vec<String>) {
     let mut pos = 0; // byte offset
 
     while pos < text.len() {
-        if text.as_bytes()[pos] == b'{' {
-            let double = pos + 1 < text.len() && text.as_bytes()[pos + 1] == b'{';
-            let start = if double { pos + 2 } else { pos + 1 };
-            let end_marker = if double { "}}" } else { "}" };
-
-            if let Some(end_pos) = text[start..].find(end_marker) {
-                let name = &text[start..start + end_pos];
-                let name = name.trim();
-                if !name.is_empty() && !name.contains(' ') {
-                    placeholders.push(name.to_string());
+        if text.as_bytes()[pos] != b'{' {
+            pos += text[pos..].chars().next().map_or(1, |c| c.len_utf8());
+            continue;
+        }
+        if text[pos..].starts_with("{{") {
+            let start = pos + 2;
+            match text[start..].find("}}") {
+                Some(end_pos) => {
+                    let name = text[start..start + end_pos].trim();
+                    if !name.is_empty() && !name.contains(' ') {
+                        placeholders.push(name.to_string());
+                    }
+                    pos = start + end_pos + 2;
                 }
-                pos = start + end_pos + end_marker.len();
-            } else {
-                pos += 1;
+                None => pos += 1,
             }
-        } else {
-            pos += text[pos..].chars().next().map_or(1, |c| c.len_utf8());
+            continue;
+        }
+
+        let start = pos + 1;
+        let Some(len) = closing_brace(&text[start..]) else {
+            pos += 1;
+            continue;
+        };
+        pos = start + len + 1;
+        let placeable = text[start..start + len].trim();
+        let (head, rest) = placeable
+            .split_once(',')
+            .filter(|(head, _)| !head.contains('{'))
+            .unwrap_or((placeable, ""));
+        if let Some(variable) = fluent_variable(head) {
+            placeholders.push(variable.to_string());
+            continue;
+        }
+        let name = head.trim();
+        if !name.is_empty() && !name.contains(' ') {
+            placeholders.push(name.to_string());
+        }
+        // ICU `{arg, plural|select|selectordinal, key {message} ...}`
+        let (kind, options) = rest.split_once(',').unwrap_or((rest, ""));
+        if matches!(kind.trim(), "plural" | "select" | "selectordinal") {
+            collect_icu_branches(options, placeholders);
         }
     }
+}
 
-    placeholders.sort();
-    placeholders.dedup();
-    placeholders
+/// Placeholders of each `{message}` among ICU branch options like
+/// `one {# item} other {{count} items}`.
+fn collect_icu_branches(options: &str, placeholders: &mut Vec<String>) {
+    let mut rest = options;
+    while let Some(open) = rest.find('{') {
+        let inner = &rest[open + 1..];
+        let Some(len) = closing_brace(inner) else {
+            break;
+        };
+        collect_placeholders(&inner[..len], placeholders);
+        rest = &inner[len + 1..];
+    }
+}
+
+/// Byte offset of the `}` closing a placeable whose content starts `text`.
+fn closing_brace(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, byte) in text.bytes().enumerate() {
+        match byte {
+            b'{' => depth += 1,
+            b'}' if depth == 0 => return Some(index),
+            b'}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// First `$variable` of a Fluent placeable, e.g. `count` in `$count`,
+/// `NUMBER($count)` or the selector of `$count -> ...`.
+fn fluent_variable(placeable: &str) -> Option<&str> {
+    let rest = &placeable[placeable.find('$')? + 1..];
+    let end = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+        .unwrap_or(rest.len());
+    (end > 0).then_some(&rest[..end])
+}
+
+/// Check if a key matches any of the given patterns. A pattern is a substring match,
+/// optionally anchored with a leading `^` and/or trailing `$`.
+pub fn matches_key_pattern(key: &str, patterns: &[String]) -> bool {
+    patterns.iter().any(|pattern| {
+        if pattern.is_empty() {
+            return false;
+        }
+        let anchored_start = pattern.starts_with('^');
+        let anchored_end = pattern.ends_with('$');
+        let start = if anchored_start { 1 } else { 0 };
+        let end = if anchored_end {
+            pattern.len().saturating_sub(1)
+        } else {
+            pattern.len()
+        };
+        let needle = &pattern[start..end];
+        if anchored_start && anchored_end {
+            key == needle
+        } else if anchored_start {
+            key.starts_with(needle)
+        } else if anchored_end {
+            key.ends_with(needle)
+        } else {
+            key.contains(needle)
+        }
+    })
+}
+
+/// Key of `map` naming the same path as `key` under a namespace that differs only in
+/// ASCII case (`Common:title` -> `common:title`). `None` when `key` exists as is.
+pub fn find_key_ignoring_namespace_case<'a, V>(
+    map: &'a HashMap<String, V>,
+    key: &str,
+) -> Option<&'a String> {
+    if map.contains_key(key) {
+        return None;
+    }
+    let (ns, path) = key.split_once(':')?;
+    map.keys().find(|candidate| {
+        candidate
+            .split_once(':')
+            .is_some_and(|(other_ns, other_path)| {
+                other_path == path && other_ns.eq_ignore_ascii_case(ns)
+            })
+    })
 }
 
 /// Check if two values have equivalent placeholders.
@@ -104,6 +282,20 @@ mod tests {
         assert_eq!(result.get("login.button").unwrap(), "Submit");
     }
 
+    #[test]
+    fn test_path_to_string_uses_forward_slashes() {
+        #[cfg(windows)]
+        assert_eq!(
+            path_to_string(Path::new(r"C:\\app\\locales\\en\\common.json")),
+            "C:/app/locales/en/common.json"
+        );
+        #[cfg(not(windows))]
+        assert_eq!(
+            path_to_string(Path::new("/app/locales/en/common.json")),
+            "/app/locales/en/common.json"
+        );
+    }
+
     #[test]
     fn test_extract_placeholders_i18next() {
         let result = extract_placeholders("Hello {{name}}, you have {{count}} items");
@@ -116,6 +308,41 @@ mod tests {
         assert_eq!(result, vec!["count", "name"]);
     }
 
+    #[test]
+    fn test_extract_placeholders_fluent() {
+        let result = extract_placeholders(
+            "Hi { $user-name }, { NUMBER($count, minimumFractionDigits: 2) } new {-brand}",
+        );
+        assert_eq!(result, vec!["-brand", "count", "user-name"]);
+    }
+
+    #[test]
+    fn test_extract_placeholders_icu_plural_and_select() {
+        assert_eq!(
+            extract_placeholders("{count, plural, one {# item} other {# items}}"),
+            vec!["count"]
+        );
+        assert_eq!(
+            extract_placeholders(
+                "{gender, select, male {{name} replied} other {{name} replied {count, plural, one {once} other {# times}}}} at {time, date, short}"
+            ),
+            vec!["count", "gender", "name", "time"]
+        );
+        assert_eq!(
+            extract_placeholders("{count, plural, =0 {No items} other {{count} items}}"),
+            vec!["count"]
+        );
+    }
+
+    #[test]
+    fn test_extract_placeholders_multiset_keeps_repeats() {
+        assert_eq!(
+            extract_placeholders_multiset("{{name}} and {{name}}, {{count}}"),
+            vec!["count", "name", "name"]
+        );
+        assert_eq!(extract_placeholders("{{name}} and {{name}}"), vec!["name"]);
+    }
+
     #[test]
     fn test_placeholder_equal() {
         assert!(placeholder_equal(
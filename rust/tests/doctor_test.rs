@@ -1,5 +1,5 @@
-use i18n_status_core::doctor::{self, DiagnoseParams};
-use i18n_status_core::resource::index::RootConfig;
+use i18n_status_core::doctor::{self, DiagnoseParams, MissingMatrixParams, UntranslatedParams};
+use i18n_status_core::resource::index::{self, BuildIndexParams, IndexCache, RootConfig};
 use std::fs;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -49,6 +49,7 @@ fn diagnose_marks_cancelled_when_token_file_exists() {
         roots: vec![RootConfig {
             kind: "i18next".to_string(),
             path: ".".to_string(),
+            ..Default::default()
         }],
         primary_lang: "en".to_string(),
         languages: vec!["en".to_string()],
@@ -57,6 +58,7 @@ fn diagnose_marks_cancelled_when_token_file_exists() {
         open_buf_paths: vec![],
         open_buffers: vec![],
         cancel_token_path: Some(token_path.to_string_lossy().to_string()),
+        ..Default::default()
     };
 
     let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
@@ -78,6 +80,7 @@ fn diagnose_without_cancel_token_keeps_normal_result() {
         open_buf_paths: vec![],
         open_buffers: vec![],
         cancel_token_path: None,
+        ..Default::default()
     };
 
     let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
@@ -105,6 +108,7 @@ fn diagnose_reports_scan_error_and_skips_unused_when_parse_fails() {
         roots: vec![RootConfig {
             kind: "i18next".to_string(),
             path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
         }],
         primary_lang: "en".to_string(),
         languages: vec!["en".to_string()],
@@ -113,6 +117,7 @@ fn diagnose_reports_scan_error_and_skips_unused_when_parse_fails() {
         open_buf_paths: vec![],
         open_buffers: vec![],
         cancel_token_path: None,
+        ..Default::default()
     };
 
     let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
@@ -160,6 +165,7 @@ fn diagnose_cancels_during_file_collection() {
         roots: vec![RootConfig {
             kind: "i18next".to_string(),
             path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
         }],
         primary_lang: "en".to_string(),
         languages: vec!["en".to_string()],
@@ -168,6 +174,7 @@ fn diagnose_cancels_during_file_collection() {
         open_buf_paths: vec![],
         open_buffers: vec![],
         cancel_token_path: Some(token_path.to_string_lossy().to_string()),
+        ..Default::default()
     };
 
     let wrote_token = AtomicBool::new(false);
@@ -193,3 +200,859 @@ fn diagnose_cancels_during_file_collection() {
     let _ = fs::remove_file(token_path);
     let _ = fs::remove_dir_all(root);
 }
+
+#[test]
+fn diagnose_only_kinds_limits_checks_to_requested_kinds() {
+    let root = unique_temp_dir("doctor-only-kinds");
+    let locales_dir = root.join("locales");
+    write_file(
+        &locales_dir.join("en/common.json"),
+        r#"{"used":"Used","unused":"Unused"}"#,
+    );
+    write_file(&locales_dir.join("ja/common.json"), r#"{"extra":"Extra"}"#);
+    write_file(
+        &root.join("src/app.ts"),
+        r#"t("common:used"); t("common:absent");"#,
+    );
+
+    let params = DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string(), "ja".to_string()],
+        fallback_namespace: "common".to_string(),
+        only_kinds: Some(vec!["missing".to_string()]),
+        ..Default::default()
+    };
+
+    let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
+    let issues = result["issues"]
+        .as_array()
+        .expect("issues should be an array");
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["kind"], "missing");
+    assert_eq!(issues[0]["key"], "common:absent");
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_reports_key_missing_only_from_primary() {
+    let root = unique_temp_dir("doctor-missing-from-primary");
+    let locales_dir = root.join("locales");
+    write_file(&locales_dir.join("en/common.json"), r#"{"hello":"Hello"}"#);
+    write_file(
+        &locales_dir.join("ja/common.json"),
+        r#"{"hello":"こんにちは","bye":"さようなら","stray":"余分"}"#,
+    );
+    write_file(
+        &locales_dir.join("fr/common.json"),
+        r#"{"hello":"Bonjour","bye":"Au revoir"}"#,
+    );
+
+    let params = DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string(), "ja".to_string(), "fr".to_string()],
+        fallback_namespace: "common".to_string(),
+        only_kinds: Some(vec![
+            "drift_extra".to_string(),
+            "missing_from_primary_only".to_string(),
+        ]),
+        ..Default::default()
+    };
+
+    let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
+    let issues = result["issues"]
+        .as_array()
+        .expect("issues should be an array");
+
+    let elevated: Vec<_> = issues
+        .iter()
+        .filter(|issue| issue["kind"] == "missing_from_primary_only")
+        .collect();
+    assert_eq!(elevated.len(), 1);
+    assert_eq!(elevated[0]["key"], "common:bye");
+    assert_eq!(elevated[0]["severity"], 2);
+
+    let extra_count = issues
+        .iter()
+        .filter(|issue| issue["kind"] == "drift_extra")
+        .count();
+    assert_eq!(extra_count, 3);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_min_severity_excludes_less_severe_issues() {
+    let root = unique_temp_dir("doctor-min-severity");
+    let locales_dir = root.join("locales");
+    write_file(
+        &locales_dir.join("en/common.json"),
+        r#"{"used":"Used","unused":"Unused"}"#,
+    );
+    write_file(&locales_dir.join("ja/common.json"), r#"{"extra":"Extra"}"#);
+    write_file(
+        &root.join("src/app.ts"),
+        r#"t("common:used"); t("common:absent");"#,
+    );
+
+    let params = DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string(), "ja".to_string()],
+        fallback_namespace: "common".to_string(),
+        min_severity: Some(2),
+        ..Default::default()
+    };
+
+    let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
+    let issues = result["issues"]
+        .as_array()
+        .expect("issues should be an array");
+
+    assert!(!issues.is_empty());
+    assert!(
+        issues
+            .iter()
+            .all(|issue| issue["severity"].as_u64() <= Some(2))
+    );
+    assert!(issues.iter().any(|issue| issue["kind"] == "missing"));
+    assert!(!issues.iter().any(|issue| issue["kind"] == "unused"));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_return_objects_key_with_subtree_is_not_missing() {
+    let root = unique_temp_dir("doctor-return-objects");
+    let locales_dir = root.join("locales");
+    write_file(
+        &locales_dir.join("en/common.json"),
+        r#"{"menu":{"file":"File","edit":"Edit"}}"#,
+    );
+    write_file(
+        &root.join("src/app.ts"),
+        r#"t("menu", { returnObjects: true }); t("toolbar", { returnObjects: true });"#,
+    );
+
+    let params = DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string()],
+        fallback_namespace: "common".to_string(),
+        only_kinds: Some(vec!["missing".to_string()]),
+        ..Default::default()
+    };
+
+    let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
+    let issues = result["issues"]
+        .as_array()
+        .expect("issues should be an array");
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["key"], "common:toolbar");
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_context_variants_count_as_used() {
+    let root = unique_temp_dir("doctor-context");
+    let locales_dir = root.join("locales");
+    write_file(
+        &locales_dir.join("en/common.json"),
+        r#"{"friend_male":"A boyfriend","friend_female":"A girlfriend"}"#,
+    );
+    write_file(
+        &root.join("src/app.ts"),
+        r#"t("friend", { context: "male" });"#,
+    );
+
+    let params = DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string()],
+        fallback_namespace: "common".to_string(),
+        only_kinds: Some(vec!["missing".to_string(), "unused".to_string()]),
+        ..Default::default()
+    };
+
+    let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
+    let issues = result["issues"]
+        .as_array()
+        .expect("issues should be an array");
+
+    assert_eq!(issues.len(), 1, "unexpected issues: {:?}", issues);
+    assert_eq!(issues[0]["kind"], "unused");
+    assert_eq!(issues[0]["key"], "common:friend_female");
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_flags_interpolation_without_options_argument() {
+    let root = unique_temp_dir("doctor-interpolation");
+    let locales_dir = root.join("locales");
+    write_file(
+        &locales_dir.join("en/common.json"),
+        r#"{"greeting":"Hello {{name}}","plain":"Hi"}"#,
+    );
+    write_file(
+        &root.join("src/app.ts"),
+        "t(\"greeting\");\nt(\"greeting\", { name });\nt(\"plain\");\n",
+    );
+
+    let params = DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string()],
+        fallback_namespace: "common".to_string(),
+        only_kinds: Some(vec!["interpolation_without_args".to_string()]),
+        ..Default::default()
+    };
+
+    let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
+    let issues = result["issues"]
+        .as_array()
+        .expect("issues should be an array");
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["kind"], "interpolation_without_args");
+    assert_eq!(issues[0]["key"], "common:greeting");
+    assert_eq!(issues[0]["severity"], 2);
+    assert_eq!(issues[0]["lnum"], 0);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_groups_values_differing_only_by_trailing_punctuation() {
+    let root = unique_temp_dir("doctor-punctuation-variant");
+    let locales_dir = root.join("locales");
+    write_file(
+        &locales_dir.join("en/common.json"),
+        r#"{"greeting":"Hi","greeting2":"Hi!","save":"Save","save_again":"Save"}"#,
+    );
+
+    let params = |detect_duplicates: bool| DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string()],
+        fallback_namespace: "common".to_string(),
+        only_kinds: Some(vec!["punctuation_variant".to_string()]),
+        detect_duplicates,
+        ..Default::default()
+    };
+
+    let result = doctor::diagnose(params(true), &|_, _| {}).expect("diagnose should succeed");
+    let issues = result["issues"]
+        .as_array()
+        .expect("issues should be an array");
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["kind"], "punctuation_variant");
+    assert_eq!(issues[0]["key"], "common:greeting");
+    assert_eq!(issues[0]["severity"], 3);
+    let message = issues[0]["message"].as_str().unwrap();
+    assert!(message.contains("common:greeting, common:greeting2"));
+
+    let result = doctor::diagnose(params(false), &|_, _| {}).expect("diagnose should succeed");
+    assert_eq!(result["issues"], serde_json::json!([]));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_flags_primary_value_much_shorter_than_translations() {
+    let root = unique_temp_dir("doctor-primary-stub");
+    let locales_dir = root.join("locales");
+    write_file(
+        &locales_dir.join("en/common.json"),
+        r#"{"welcome":"x","save":"Save"}"#,
+    );
+    write_file(
+        &locales_dir.join("de/common.json"),
+        r#"{"welcome":"Willkommen in Ihrem Konto","save":"Speichern"}"#,
+    );
+    write_file(
+        &locales_dir.join("fr/common.json"),
+        r#"{"welcome":"Bienvenue dans votre compte","save":"Enregistrer"}"#,
+    );
+
+    let params = DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string(), "de".to_string(), "fr".to_string()],
+        fallback_namespace: "common".to_string(),
+        only_kinds: Some(vec!["suspicious_primary_stub".to_string()]),
+        detect_primary_stubs: true,
+        ..Default::default()
+    };
+
+    let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
+    let issues = result["issues"]
+        .as_array()
+        .expect("issues should be an array");
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["key"], "common:welcome");
+    assert_eq!(issues[0]["severity"], 3);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_flags_html_entity_where_other_language_uses_literal() {
+    let root = unique_temp_dir("doctor-html-entity");
+    let locales_dir = root.join("locales");
+    write_file(
+        &locales_dir.join("en/common.json"),
+        r#"{"show":"Tom & Jerry","terms":"Terms &amp; Conditions"}"#,
+    );
+    write_file(
+        &locales_dir.join("de/common.json"),
+        r#"{"show":"Tom &amp; Jerry","terms":"AGB &amp; Datenschutz"}"#,
+    );
+
+    let params = DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string(), "de".to_string()],
+        fallback_namespace: "common".to_string(),
+        only_kinds: Some(vec!["html_entity_in_value".to_string()]),
+        detect_html_entities: true,
+        ..Default::default()
+    };
+
+    let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
+    let issues = result["issues"]
+        .as_array()
+        .expect("issues should be an array");
+
+    // `terms` uses the entity in both languages, so only `show` is inconsistent.
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["key"], "common:show");
+    assert_eq!(issues[0]["severity"], 3);
+    assert!(issues[0]["file"].as_str().unwrap().contains("de"));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_reports_namespace_without_resource_file() {
+    let root = unique_temp_dir("doctor-namespace-file");
+    let locales_dir = root.join("locales");
+    write_file(&locales_dir.join("en/common.json"), r#"{"ok":"OK"}"#);
+    write_file(
+        &root.join("src/app.ts"),
+        r#"t("ok"); t("checkout:pay"); t("checkout:cancel");"#,
+    );
+
+    let params = DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string()],
+        fallback_namespace: "common".to_string(),
+        only_kinds: Some(vec!["namespace_file_missing".to_string()]),
+        ..Default::default()
+    };
+
+    let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
+    let issues = result["issues"]
+        .as_array()
+        .expect("issues should be an array");
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["kind"], "namespace_file_missing");
+    assert!(
+        issues[0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("'checkout'")
+    );
+    assert_eq!(
+        result["namespace_coverage"],
+        serde_json::json!({ "checkout": 0, "common": 1 })
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_lenient_fallback_reports_fallback_keys_as_info() {
+    let root = unique_temp_dir("doctor-strict-fallback");
+    let locales_dir = root.join("locales");
+    write_file(&locales_dir.join("en/translation.json"), r#"{"ok":"OK"}"#);
+    write_file(&locales_dir.join("en/home.json"), r#"{"title":"Home"}"#);
+    write_file(
+        &root.join("src/app.ts"),
+        r#"t("bare"); t("home:subtitle");"#,
+    );
+
+    let params = |strict_fallback: Option<bool>| DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string()],
+        fallback_namespace: "translation".to_string(),
+        only_kinds: Some(vec!["missing".to_string()]),
+        strict_fallback,
+        ..Default::default()
+    };
+    let severities = |strict_fallback: Option<bool>| {
+        let result =
+            doctor::diagnose(params(strict_fallback), &|_, _| {}).expect("diagnose should succeed");
+        let mut severities: Vec<(String, u64)> = result["issues"]
+            .as_array()
+            .expect("issues should be an array")
+            .iter()
+            .map(|issue| {
+                (
+                    issue["key"].as_str().unwrap().to_string(),
+                    issue["severity"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        severities.sort();
+        severities
+    };
+
+    assert_eq!(
+        severities(None),
+        vec![
+            ("home:subtitle".to_string(), 2),
+            ("translation:bare".to_string(), 2)
+        ]
+    );
+    assert_eq!(
+        severities(Some(false)),
+        vec![
+            ("home:subtitle".to_string(), 2),
+            ("translation:bare".to_string(), 3)
+        ]
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_reports_missing_keys_with_inline_default_as_info() {
+    let root = unique_temp_dir("doctor-inline-default");
+    let locales_dir = root.join("locales");
+    write_file(&locales_dir.join("en/common.json"), r#"{"ok":"OK"}"#);
+    write_file(
+        &root.join("src/app.ts"),
+        r#"t("greeting", { defaultValue: "Hello" }); t("farewell");"#,
+    );
+
+    let params = DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string()],
+        fallback_namespace: "common".to_string(),
+        only_kinds: Some(vec!["missing".to_string()]),
+        ..Default::default()
+    };
+    let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
+    let mut issues: Vec<(String, u64, String)> = result["issues"]
+        .as_array()
+        .expect("issues should be an array")
+        .iter()
+        .map(|issue| {
+            (
+                issue["key"].as_str().unwrap().to_string(),
+                issue["severity"].as_u64().unwrap(),
+                issue["message"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    issues.sort();
+
+    assert_eq!(issues.len(), 2);
+    assert_eq!((issues[0].0.as_str(), issues[0].1), ("common:farewell", 2));
+    assert_eq!((issues[1].0.as_str(), issues[1].1), ("common:greeting", 3));
+    assert!(issues[1].2.contains("inline default 'Hello'"));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_accepts_plural_and_context_variants_of_used_keys() {
+    let root = unique_temp_dir("doctor-key-variants");
+    let locales_dir = root.join("locales");
+    write_file(
+        &locales_dir.join("en/common.json"),
+        r#"{"item_one":"{{count}} item","item_other":"{{count}} items","greeting_formal":"Good day","stale_one":"Stale","friend_male_one":"A friend","save_draft":"Save draft"}"#,
+    );
+    write_file(
+        &root.join("src/app.ts"),
+        r#"t("item", { count }); t("greeting", { context: tone }); t("friend", { context: "male", count }); t("save_draft"); t("save"); t("absent");"#,
+    );
+
+    let params = DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string()],
+        fallback_namespace: "common".to_string(),
+        only_kinds: Some(vec!["missing".to_string(), "unused".to_string()]),
+        ..Default::default()
+    };
+    let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
+    let mut issues: Vec<(String, String)> = result["issues"]
+        .as_array()
+        .expect("issues should be an array")
+        .iter()
+        .map(|issue| {
+            (
+                issue["kind"].as_str().unwrap().to_string(),
+                issue["key"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    issues.sort();
+
+    assert_eq!(
+        issues,
+        vec![
+            ("missing".to_string(), "common:absent".to_string()),
+            // A context form is not a plural form, so it does not stand in for
+            // the base key; nor does an unrelated `_draft` key.
+            ("missing".to_string(), "common:greeting".to_string()),
+            ("missing".to_string(), "common:save".to_string()),
+            // The context is dynamic, so which context forms are read is unknown.
+            ("unused".to_string(), "common:greeting_formal".to_string()),
+            ("unused".to_string(), "common:stale_one".to_string()),
+        ]
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_flags_values_containing_todo_markers() {
+    let root = unique_temp_dir("doctor-translation-todo");
+    let locales_dir = root.join("locales");
+    write_file(
+        &locales_dir.join("en/common.json"),
+        r#"{"save":"Save","cancel":"Cancel"}"#,
+    );
+    write_file(
+        &locales_dir.join("de/common.json"),
+        "{\n  \"save\": \"Speichern\",\n  \"cancel\": \"TODO: translate\"\n}\n",
+    );
+
+    let params = DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string(), "de".to_string()],
+        fallback_namespace: "common".to_string(),
+        only_kinds: Some(vec!["translation_todo".to_string()]),
+        todo_markers: vec!["TODO".to_string(), "[MACHINE]".to_string()],
+        ..Default::default()
+    };
+
+    let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
+    let issues = result["issues"]
+        .as_array()
+        .expect("issues should be an array");
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["key"], "common:cancel");
+    assert_eq!(issues[0]["severity"], 2);
+    assert_eq!(issues[0]["lnum"], 2);
+    assert!(
+        issues[0]["file"]
+            .as_str()
+            .unwrap()
+            .ends_with("de/common.json")
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn untranslated_for_language_lists_used_keys_missing_in_target() {
+    let root = unique_temp_dir("doctor-untranslated");
+    let locales_dir = root.join("locales");
+    write_file(
+        &locales_dir.join("en/common.json"),
+        r#"{"save":"Save","cancel":"Cancel","close":"Close","unused":"Unused"}"#,
+    );
+    write_file(
+        &locales_dir.join("ja/common.json"),
+        r#"{"save":"保存","close":""}"#,
+    );
+    write_file(
+        &root.join("src/app.ts"),
+        r#"t("save"); t("cancel"); t("close");"#,
+    );
+
+    let params = UntranslatedParams {
+        config: DiagnoseParams {
+            project_root: root.to_string_lossy().to_string(),
+            roots: vec![RootConfig {
+                kind: "i18next".to_string(),
+                path: locales_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            }],
+            primary_lang: "en".to_string(),
+            languages: vec!["en".to_string(), "ja".to_string()],
+            fallback_namespace: "common".to_string(),
+            ..Default::default()
+        },
+        lang: "ja".to_string(),
+    };
+
+    let result = doctor::untranslated_for_language(params, &|_, _| {})
+        .expect("untranslated_for_language should succeed");
+
+    assert_eq!(result["cancelled"], false);
+    assert_eq!(
+        result["keys"],
+        serde_json::json!([
+            { "key": "common:cancel", "primary_value": "Cancel" },
+            { "key": "common:close", "primary_value": "Close" }
+        ])
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn missing_matrix_lists_languages_lacking_each_key() {
+    let root = unique_temp_dir("doctor-missing-matrix");
+    let locales_dir = root.join("locales");
+    write_file(
+        &locales_dir.join("en/common.json"),
+        r#"{"hello":"Hello","bye":"Bye"}"#,
+    );
+    write_file(
+        &locales_dir.join("ja/common.json"),
+        r#"{"hello":"こんにちは","bye":"さようなら"}"#,
+    );
+    write_file(
+        &locales_dir.join("fr/common.json"),
+        r#"{"hello":"Bonjour"}"#,
+    );
+
+    let cache = IndexCache::new();
+    let built = index::build_index(
+        BuildIndexParams {
+            roots: vec![RootConfig {
+                kind: "i18next".to_string(),
+                path: locales_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            }],
+        },
+        &cache,
+    )
+    .expect("build_index should succeed");
+
+    let result = doctor::missing_matrix(
+        MissingMatrixParams {
+            cache_key: built["cache_key"].as_str().unwrap().to_string(),
+            primary_lang: "en".to_string(),
+            languages: vec!["ja".to_string(), "fr".to_string()],
+        },
+        &cache,
+    )
+    .expect("missing_matrix should succeed");
+
+    assert_eq!(
+        result["rows"],
+        serde_json::json!([{ "key": "common:bye", "missing_in": ["fr"], "value": "Bye" }])
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_unused_grace_skips_recently_modified_files() {
+    let root = unique_temp_dir("doctor-unused-grace");
+    let locales_dir = root.join("locales");
+    write_file(&locales_dir.join("en/common.json"), r#"{"fresh":"Fresh"}"#);
+
+    let params = |unused_grace_ms: Option<u64>| DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string()],
+        fallback_namespace: "common".to_string(),
+        only_kinds: Some(vec!["unused".to_string()]),
+        unused_grace_ms,
+        ..Default::default()
+    };
+
+    let result =
+        doctor::diagnose(params(Some(60_000)), &|_, _| {}).expect("diagnose should succeed");
+    assert_eq!(result["issues"], serde_json::json!([]));
+
+    let result = doctor::diagnose(params(None), &|_, _| {}).expect("diagnose should succeed");
+    let issues = result["issues"].as_array().unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["kind"], "unused");
+    assert_eq!(issues[0]["key"], "common:fresh");
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_reports_namespace_case_mismatch_when_case_insensitive() {
+    let root = unique_temp_dir("doctor-namespace-case");
+    let locales_dir = root.join("locales");
+    write_file(&locales_dir.join("en/common.json"), r#"{"title":"Title"}"#);
+    write_file(
+        &root.join("src/page.ts"),
+        r#"const { t } = useTranslation("Common");
+t("title");
+"#,
+    );
+
+    let params = |case_insensitive_namespaces: bool| DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string()],
+        fallback_namespace: "common".to_string(),
+        only_kinds: Some(vec![
+            "missing".to_string(),
+            "unused".to_string(),
+            "namespace_case_mismatch".to_string(),
+        ]),
+        case_insensitive_namespaces,
+        ..Default::default()
+    };
+
+    let result = doctor::diagnose(params(true), &|_, _| {}).expect("diagnose should succeed");
+    let issues = result["issues"].as_array().unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["kind"], "namespace_case_mismatch");
+    assert_eq!(issues[0]["key"], "Common:title");
+    assert_eq!(result["used_keys"]["common:title"], true);
+
+    let result = doctor::diagnose(params(false), &|_, _| {}).expect("diagnose should succeed");
+    let kinds: Vec<&str> = result["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| issue["kind"].as_str().unwrap())
+        .collect();
+    assert!(kinds.contains(&"missing"));
+    assert!(kinds.contains(&"unused"));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn diagnose_unused_issue_points_at_resource_line() {
+    let root = unique_temp_dir("doctor-unused-lnum");
+    let locales_dir = root.join("locales");
+    write_file(
+        &locales_dir.join("en/common.json"),
+        "{\n  \"title\": \"Title\",\n  \"nav\": {\n    \"old\": \"Old\"\n  }\n}\n",
+    );
+    write_file(&root.join("src/app.ts"), r#"t("title");"#);
+
+    let params = DiagnoseParams {
+        project_root: root.to_string_lossy().to_string(),
+        roots: vec![RootConfig {
+            kind: "i18next".to_string(),
+            path: locales_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        }],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string()],
+        fallback_namespace: "common".to_string(),
+        only_kinds: Some(vec!["unused".to_string()]),
+        ..Default::default()
+    };
+
+    let result = doctor::diagnose(params, &|_, _| {}).expect("diagnose should succeed");
+    let issues = result["issues"]
+        .as_array()
+        .expect("issues should be an array");
+
+    assert_eq!(issues.len(), 1, "unexpected issues: {:?}", issues);
+    assert_eq!(issues[0]["key"], "common:nav.old");
+    assert_eq!(issues[0]["lnum"], 3);
+
+    let _ = fs::remove_dir_all(root);
+}
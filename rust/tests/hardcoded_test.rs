@@ -8,6 +8,7 @@ fn extract(source: &str, lang: &str) -> serde_json::Value {
         range: None,
         min_length: 2,
         exclude_components: vec!["Trans".to_string(), "Translation".to_string()],
+        ..Default::default()
     };
     hardcoded::extract(params).expect("extract should succeed")
 }
@@ -19,6 +20,7 @@ fn extract_with_min_length(source: &str, min_length: usize) -> serde_json::Value
         range: None,
         min_length,
         exclude_components: vec!["Trans".to_string()],
+        ..Default::default()
     };
     hardcoded::extract(params).expect("extract should succeed")
 }
@@ -33,6 +35,7 @@ fn extract_with_range(source: &str, start_line: u32, end_line: u32) -> serde_jso
         }),
         min_length: 2,
         exclude_components: vec!["Trans".to_string()],
+        ..Default::default()
     };
     hardcoded::extract(params).expect("extract should succeed")
 }
@@ -65,6 +68,51 @@ fn excluded_inside_trans_component() {
     assert_eq!(items.len(), 0, "text inside <Trans> should be excluded");
 }
 
+#[test]
+fn excluded_inside_element_with_key_attribute() {
+    let source =
+        r#"const App = () => <div><Foo i18nKey="x">Hello World</Foo><p>Other text</p></div>;"#;
+    let result = extract(source, "tsx");
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1, "unexpected items: {:?}", items);
+    assert_eq!(items[0]["text"], "Other text");
+
+    let params = hardcoded::ExtractParams {
+        source: r#"const App = () => <Localized id="greeting">Hello World</Localized>;"#
+            .to_string(),
+        lang: "tsx".to_string(),
+        key_attributes: vec!["i18nKey".to_string(), "id".to_string()],
+        ..Default::default()
+    };
+    let result = hardcoded::extract(params).expect("extract should succeed");
+    assert_eq!(result["items"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn include_elements_limits_detection_to_listed_elements() {
+    let source = r#"const App = () => (
+  <div>
+    Loose text
+    <p>Paragraph <b>bold</b></p>
+    <p>{"Literal"}</p>
+  </div>
+);"#;
+    let params = hardcoded::ExtractParams {
+        source: source.to_string(),
+        lang: "tsx".to_string(),
+        include_elements: Some(vec!["p".to_string()]),
+        ..Default::default()
+    };
+    let result = hardcoded::extract(params).expect("extract should succeed");
+    let texts: Vec<&str> = result["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["text"].as_str().unwrap())
+        .collect();
+    assert_eq!(texts, vec!["Paragraph", "Literal"]);
+}
+
 #[test]
 fn excluded_inside_t_call() {
     // A string literal that is an argument to t() should not be flagged as hardcoded.
@@ -153,3 +201,147 @@ fn nested_excluded_component() {
         .any(|i| i["text"].as_str().unwrap() == "Nested text");
     assert!(!has_nested, "text nested inside <Trans> should be excluded");
 }
+
+#[test]
+fn multiline_jsx_text_reports_per_line_segments() {
+    // Line 0: const App = () => (
+    // Line 1:   <p>
+    // Line 2:     Hello
+    // Line 3:     World
+    // Line 4:   </p>
+    // Line 5: );
+    let source = "const App = () => (\n  <p>\n    Hello\n    World\n  </p>\n);";
+    let result = extract(source, "tsx");
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["text"], "Hello World");
+    assert_eq!(items[0]["raw_text"], "\n    Hello\n    World\n  ");
+
+    let segments = items[0]["segments"].as_array().unwrap();
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[0]["lnum"], 2);
+    assert_eq!(segments[0]["col"], 4);
+    assert_eq!(segments[0]["end_col"], 9);
+    assert_eq!(segments[1]["lnum"], 3);
+    assert_eq!(segments[1]["col"], 4);
+    assert_eq!(segments[1]["end_col"], 9);
+}
+
+#[test]
+fn group_mixed_content_produces_trans_template() {
+    let source = r#"
+function Greeting({ name }) {
+  return (
+    <div>
+      <p>Hello, <strong>{name}</strong>!</p>
+      <span>Plain text</span>
+    </div>
+  );
+}
+"#;
+    let params = hardcoded::ExtractParams {
+        source: source.to_string(),
+        lang: "jsx".to_string(),
+        group_mixed_content: true,
+        ..Default::default()
+    };
+    let result = hardcoded::extract(params).expect("extract should succeed");
+    let items = result["items"].as_array().unwrap();
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["kind"], "jsx_mixed");
+    assert_eq!(items[0]["text"], "Hello, <1>{{name}}</1>!");
+    assert_eq!(items[0]["lnum"], 4);
+    assert_eq!(items[0]["col"], 9);
+    assert_eq!(items[1]["kind"], "jsx_text");
+    assert_eq!(items[1]["text"], "Plain text");
+
+    let ungrouped = extract(source, "jsx");
+    let kinds: Vec<&str> = ungrouped["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["kind"].as_str().unwrap())
+        .collect();
+    assert!(!kinds.contains(&"jsx_mixed"));
+}
+
+#[test]
+fn preserve_newlines_keeps_line_breaks_in_jsx_text() {
+    let source = r#"
+function Notice() {
+  return (
+    <pre>
+      First   line
+      Second	line
+
+      Third line
+    </pre>
+  );
+}
+"#;
+    let normalized = extract(source, "jsx");
+    assert_eq!(
+        normalized["items"][0]["text"],
+        "First line Second line Third line"
+    );
+
+    let params = hardcoded::ExtractParams {
+        source: source.to_string(),
+        lang: "jsx".to_string(),
+        preserve_newlines: true,
+        ..Default::default()
+    };
+    let preserved = hardcoded::extract(params).expect("extract should succeed");
+    assert_eq!(
+        preserved["items"][0]["text"],
+        "First line\nSecond line\nThird line"
+    );
+}
+
+#[test]
+fn segments_use_display_width_for_wide_characters() {
+    // Line 0: const App = () => (
+    // Line 1:   <div>
+    // Line 2:     <p>「日本語」の
+    // Line 3:     テキスト</p>
+    // Line 4:     {`ようこそ
+    // Line 5:     世界`}
+    // Line 6:     {"caf\u00e9 ouvert"}
+    // Line 7:   </div>
+    // Line 8: );
+    let source = "const App = () => (\n  <div>\n    <p>「日本語」の\n    テキスト</p>\n    {`ようこそ\n    世界`}\n    {\"caf\\u00e9 ouvert\"}\n  </div>\n);";
+    let result = extract(source, "tsx");
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 3);
+
+    assert_eq!(items[0]["kind"], "jsx_text");
+    assert_eq!(items[0]["col"], 7);
+    assert_eq!(
+        items[0]["segments"],
+        serde_json::json!([
+            { "lnum": 2, "col": 7, "end_col": 19 },
+            { "lnum": 3, "col": 4, "end_col": 12 }
+        ])
+    );
+
+    assert_eq!(items[1]["kind"], "jsx_literal");
+    assert_eq!(items[1]["end_col"], 9);
+    assert_eq!(
+        items[1]["segments"],
+        serde_json::json!([
+            { "lnum": 4, "col": 5, "end_col": 14 },
+            { "lnum": 5, "col": 4, "end_col": 9 }
+        ])
+    );
+
+    // Segments follow the escaped source, not the evaluated literal.
+    assert_eq!(items[2]["kind"], "jsx_literal");
+    assert_eq!(items[2]["text"], "caf\u{e9} ouvert");
+    assert_eq!(items[2]["raw_text"], "\"caf\\u00e9 ouvert\"");
+    assert_eq!(items[2]["end_col"], 23);
+    assert_eq!(
+        items[2]["segments"],
+        serde_json::json!([{ "lnum": 6, "col": 5, "end_col": 23 }])
+    );
+}
@@ -30,6 +30,7 @@ fn compute(
         languages: languages.into_iter().map(|s| s.to_string()).collect(),
         index,
         current_lang: None,
+        ..Default::default()
     };
     resolve::compute(params).expect("compute should succeed")
 }
@@ -73,6 +74,52 @@ fn status_localized_when_different_value() {
     assert_eq!(resolved[0]["status"], "\u{2260}"); // ≠
 }
 
+#[test]
+fn reason_case_only_difference_when_values_differ_only_in_case() {
+    let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> = HashMap::new();
+
+    let mut en = HashMap::new();
+    en.insert("common:actions.send".to_string(), make_resource("Send"));
+    index.insert("en".to_string(), en);
+
+    let mut fr = HashMap::new();
+    fr.insert("common:actions.send".to_string(), make_resource("send"));
+    index.insert("fr".to_string(), fr);
+
+    let items = vec![make_item("common:actions.send", "common")];
+    let result = compute(items, index, vec!["en", "fr"]);
+
+    let resolved = result["resolved"].as_array().unwrap();
+    assert_eq!(resolved[0]["status"], "\u{2260}");
+    assert_eq!(resolved[0]["hover"]["reason"], "case_only_difference");
+}
+
+#[test]
+fn required_language_missing_escalates_fallback() {
+    let params = |require_complete: Vec<String>| {
+        let mut en = HashMap::new();
+        en.insert("common:hello".to_string(), make_resource("Hello"));
+        let mut index = HashMap::new();
+        index.insert("en".to_string(), en);
+        resolve::ComputeParams {
+            items: vec![make_item("common:hello", "common")],
+            primary_lang: "en".to_string(),
+            languages: vec!["en".to_string(), "ja".to_string(), "fr".to_string()],
+            index,
+            require_complete,
+            ..Default::default()
+        }
+    };
+
+    let soft = resolve::compute(params(vec![])).expect("compute should succeed");
+    assert_eq!(soft["resolved"][0]["status"], "?");
+    assert_eq!(soft["resolved"][0]["hover"]["reason"], "fallback");
+
+    let gated = resolve::compute(params(vec!["fr".to_string()])).expect("compute should succeed");
+    assert_eq!(gated["resolved"][0]["status"], "\u{00d7}");
+    assert_eq!(gated["resolved"][0]["hover"]["reason"], "required_missing");
+}
+
 #[test]
 fn status_fallback_when_lang_missing() {
     let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> = HashMap::new();
@@ -159,3 +206,522 @@ fn status_placeholder_match_is_localized() {
     // Placeholders match and values differ, so it should be localized
     assert_eq!(resolved[0]["status"], "\u{2260}"); // ≠
 }
+
+#[test]
+fn status_must_match_violation_when_translation_differs() {
+    let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> = HashMap::new();
+
+    let mut en = HashMap::new();
+    en.insert("common:brand".to_string(), make_resource("Acme"));
+    index.insert("en".to_string(), en);
+
+    let mut ja = HashMap::new();
+    ja.insert("common:brand".to_string(), make_resource("アクメ"));
+    index.insert("ja".to_string(), ja);
+
+    let params = resolve::ComputeParams {
+        items: vec![make_item("common:brand", "common")],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string(), "ja".to_string()],
+        index,
+        must_match: vec!["^common:brand$".to_string()],
+        ..Default::default()
+    };
+    let result = resolve::compute(params).expect("compute should succeed");
+
+    let resolved = result["resolved"].as_array().unwrap();
+    assert_eq!(resolved[0]["status"], "!");
+    assert_eq!(resolved[0]["hover"]["reason"], "must_match_violation");
+}
+
+#[test]
+fn status_uses_custom_symbols() {
+    let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> = HashMap::new();
+
+    let mut en = HashMap::new();
+    en.insert("common:hello".to_string(), make_resource("Hello"));
+    index.insert("en".to_string(), en);
+
+    let mut ja = HashMap::new();
+    ja.insert("common:hello".to_string(), make_resource("こんにちは"));
+    index.insert("ja".to_string(), ja);
+
+    let params = resolve::ComputeParams {
+        items: vec![make_item("common:hello", "common")],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string(), "ja".to_string()],
+        index,
+        symbols: Some(resolve::StatusSymbols {
+            synced: "=".to_string(),
+            localized: "~".to_string(),
+            missing_primary: "x".to_string(),
+            fallback: "?".to_string(),
+            mismatch: "!".to_string(),
+        }),
+        ..Default::default()
+    };
+    let result = resolve::compute(params).expect("compute should succeed");
+
+    let resolved = result["resolved"].as_array().unwrap();
+    assert_eq!(resolved[0]["status"], "~");
+    assert_eq!(resolved[0]["hover"]["status"], "~");
+    assert_eq!(resolved[0]["hover"]["reason"], "localized");
+}
+
+#[test]
+fn display_langs_limits_hover_values_but_keeps_aggregates() {
+    let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> = HashMap::new();
+
+    let mut en = HashMap::new();
+    en.insert("common:hello".to_string(), make_resource("Hello {{name}}"));
+    index.insert("en".to_string(), en);
+
+    let mut ja = HashMap::new();
+    ja.insert(
+        "common:hello".to_string(),
+        make_resource("こんにちは {{name}}"),
+    );
+    index.insert("ja".to_string(), ja);
+
+    let mut fr = HashMap::new();
+    fr.insert("common:hello".to_string(), make_resource("Bonjour"));
+    index.insert("fr".to_string(), fr);
+
+    let params = resolve::ComputeParams {
+        items: vec![make_item("common:hello", "common")],
+        primary_lang: "en".to_string(),
+        languages: vec![
+            "en".to_string(),
+            "ja".to_string(),
+            "fr".to_string(),
+            "de".to_string(),
+        ],
+        index,
+        display_langs: Some(vec!["ja".to_string()]),
+        ..Default::default()
+    };
+    let result = resolve::compute(params).expect("compute should succeed");
+
+    let hover = &result["resolved"][0]["hover"];
+    let values = hover["values"].as_object().unwrap();
+    assert_eq!(values.len(), 2);
+    assert!(values.contains_key("en"));
+    assert!(values.contains_key("ja"));
+    assert_eq!(hover["reason"], "placeholder_mismatch");
+    assert_eq!(hover["mismatch_langs"], serde_json::json!(["fr"]));
+    assert_eq!(hover["missing_langs"], serde_json::json!(["de"]));
+}
+
+#[test]
+fn hover_reports_fallback_namespace_usage_and_hint() {
+    let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> = HashMap::new();
+
+    let mut en = HashMap::new();
+    en.insert("common:title".to_string(), make_resource("Title"));
+    en.insert("home:title".to_string(), make_resource("Home title"));
+    en.insert("common:save".to_string(), make_resource("Save"));
+    index.insert("en".to_string(), en);
+
+    let mut via_fallback = make_item("common:title", "common");
+    via_fallback.fallback = true;
+    let mut fallback_only = make_item("common:save", "common");
+    fallback_only.fallback = true;
+    let explicit = make_item("home:title", "home");
+
+    let result = compute(
+        vec![via_fallback, fallback_only, explicit],
+        index,
+        vec!["en"],
+    );
+    let resolved = result["resolved"].as_array().unwrap();
+
+    assert_eq!(resolved[0]["hover"]["used_fallback_namespace"], true);
+    assert!(
+        resolved[0]["hover"]["hint"]
+            .as_str()
+            .unwrap()
+            .contains("home:title")
+    );
+    assert_eq!(resolved[1]["hover"]["used_fallback_namespace"], true);
+    assert!(resolved[1]["hover"].get("hint").is_none());
+    assert_eq!(resolved[2]["hover"]["used_fallback_namespace"], false);
+}
+
+#[test]
+fn fallback_hint_uses_configured_ns_separator() {
+    let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> = HashMap::new();
+    let mut en = HashMap::new();
+    en.insert("common.title".to_string(), make_resource("Title"));
+    en.insert("home.title".to_string(), make_resource("Home title"));
+    index.insert("en".to_string(), en);
+
+    let mut via_fallback = make_item("common.title", "common");
+    via_fallback.fallback = true;
+    let params = resolve::ComputeParams {
+        items: vec![via_fallback],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string()],
+        index,
+        ns_separator: Some(".".to_string()),
+        ..Default::default()
+    };
+    let result = resolve::compute(params).expect("compute should succeed");
+
+    let hint = result["resolved"][0]["hover"]["hint"]
+        .as_str()
+        .expect("hint should be set");
+    assert!(hint.contains("home.title"), "{}", hint);
+}
+
+#[test]
+fn icu_preview_renders_plural_for_sample_counts() {
+    let preview_for = |count: i64| {
+        let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> =
+            HashMap::new();
+        let mut en = HashMap::new();
+        en.insert(
+            "common:items".to_string(),
+            make_resource("{count, plural, one {# item} other {# items}}"),
+        );
+        en.insert("common:title".to_string(), make_resource("Title"));
+        index.insert("en".to_string(), en);
+
+        let params = resolve::ComputeParams {
+            items: vec![
+                make_item("common:items", "common"),
+                make_item("common:title", "common"),
+            ],
+            primary_lang: "en".to_string(),
+            languages: vec!["en".to_string()],
+            index,
+            icu_preview: Some(HashMap::from([(
+                "count".to_string(),
+                serde_json::json!(count),
+            )])),
+            ..Default::default()
+        };
+        let result = resolve::compute(params).expect("compute should succeed");
+        assert!(
+            result["resolved"][1]["hover"]["values"]["en"]
+                .get("preview")
+                .is_none()
+        );
+        result["resolved"][0]["hover"]["values"]["en"]["preview"].clone()
+    };
+
+    assert_eq!(preview_for(1), "1 item");
+    assert_eq!(preview_for(2), "2 items");
+}
+
+#[test]
+fn ns_separator_controls_key_path_used_for_missing_check() {
+    let compute_with = |ns_separator: Option<&str>| {
+        let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> =
+            HashMap::new();
+        let mut en = HashMap::new();
+        // An untranslated stub whose value is just the key path.
+        en.insert("common.greeting".to_string(), make_resource("greeting"));
+        index.insert("en".to_string(), en);
+
+        let params = resolve::ComputeParams {
+            items: vec![resolve::ScanItemInput {
+                key: "common.greeting".to_string(),
+                raw: "common.greeting".to_string(),
+                namespace: "common".to_string(),
+                fallback: false,
+            }],
+            primary_lang: "en".to_string(),
+            languages: vec!["en".to_string()],
+            index,
+            ns_separator: ns_separator.map(|s| s.to_string()),
+            ..Default::default()
+        };
+        resolve::compute(params).expect("compute should succeed")
+    };
+
+    let with_dot = compute_with(Some("."));
+    assert_eq!(
+        with_dot["resolved"][0]["hover"]["reason"],
+        "missing_primary"
+    );
+
+    let with_default = compute_with(None);
+    assert!(with_default["resolved"][0]["hover"]["reason"].is_null());
+}
+
+#[test]
+fn render_markdown_includes_key_status_and_values() {
+    let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> = HashMap::new();
+    let mut en = HashMap::new();
+    en.insert(
+        "common:hello".to_string(),
+        resolve::ResourceItemInput {
+            value: Some("Hello".to_string()),
+            file: Some("locales/en/common.json".to_string()),
+            priority: 0,
+        },
+    );
+    index.insert("en".to_string(), en);
+    let mut ja = HashMap::new();
+    ja.insert("common:hello".to_string(), make_resource("こんにちは"));
+    index.insert("ja".to_string(), ja);
+
+    let params = resolve::ComputeParams {
+        items: vec![make_item("common:hello", "common")],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string(), "ja".to_string(), "fr".to_string()],
+        index,
+        render_markdown: true,
+        ..Default::default()
+    };
+    let result = resolve::compute(params).expect("compute should succeed");
+
+    let markdown = result["resolved"][0]["hover_markdown"].as_str().unwrap();
+    assert!(markdown.contains("common:hello"));
+    assert!(markdown.contains("`?`"));
+    assert!(markdown.contains("- **en**: Hello — `locales/en/common.json`"));
+    assert!(markdown.contains("- **ja**: こんにちは"));
+    assert!(markdown.contains("- **fr**: _(missing)_"));
+    assert!(result["resolved"][0]["hover"].is_object());
+
+    let plain = compute(
+        vec![make_item("common:hello", "common")],
+        HashMap::new(),
+        vec!["en"],
+    );
+    assert!(plain["resolved"][0].get("hover_markdown").is_none());
+}
+
+#[test]
+fn case_insensitive_namespaces_resolve_and_warn() {
+    let make_index = || {
+        let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> =
+            HashMap::new();
+        let mut en = HashMap::new();
+        en.insert("common:title".to_string(), make_resource("Title"));
+        index.insert("en".to_string(), en);
+        index
+    };
+    let params = |case_insensitive_namespaces: bool| resolve::ComputeParams {
+        items: vec![make_item("Common:title", "Common")],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string()],
+        index: make_index(),
+        case_insensitive_namespaces,
+        ..Default::default()
+    };
+
+    let result = resolve::compute(params(true)).expect("compute should succeed");
+    let resolved = &result["resolved"][0];
+    assert_eq!(resolved["text"], "Title");
+    assert_eq!(resolved["hover"]["warning"], "namespace_case_mismatch");
+    assert!(resolved["hover"]["reason"].is_null());
+
+    let result = resolve::compute(params(false)).expect("compute should succeed");
+    assert_eq!(result["resolved"][0]["hover"]["reason"], "missing_primary");
+    assert!(result["resolved"][0]["hover"].get("warning").is_none());
+}
+
+#[test]
+fn summary_counts_items_per_status() {
+    let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> = HashMap::new();
+
+    let mut en = HashMap::new();
+    en.insert("common:same".to_string(), make_resource("Same"));
+    en.insert("common:hello".to_string(), make_resource("Hello"));
+    en.insert("common:bye".to_string(), make_resource("Bye"));
+    en.insert("common:greet".to_string(), make_resource("Hi {{name}}"));
+    index.insert("en".to_string(), en);
+
+    let mut ja = HashMap::new();
+    ja.insert("common:same".to_string(), make_resource("Same"));
+    ja.insert("common:hello".to_string(), make_resource("こんにちは"));
+    ja.insert("common:greet".to_string(), make_resource("やあ {{user}}"));
+    index.insert("ja".to_string(), ja);
+
+    let params = resolve::ComputeParams {
+        items: vec![
+            make_item("common:same", "common"),
+            make_item("common:hello", "common"),
+            make_item("common:bye", "common"),
+            make_item("common:greet", "common"),
+            make_item("common:absent", "common"),
+        ],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string(), "ja".to_string()],
+        index,
+        ..Default::default()
+    };
+    let summary = resolve::summary(params).expect("summary should succeed");
+
+    assert_eq!(
+        summary,
+        serde_json::json!({
+            "synced": 1,
+            "localized": 1,
+            "missing": 1,
+            "fallback": 1,
+            "mismatch": 1
+        })
+    );
+}
+
+#[test]
+fn hover_values_flag_multiline_and_markup() {
+    let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> = HashMap::new();
+    let mut en = HashMap::new();
+    en.insert(
+        "common:terms".to_string(),
+        make_resource("Read the <b>terms</b> first"),
+    );
+    en.insert(
+        "common:address".to_string(),
+        make_resource("Line 1\nLine 2"),
+    );
+    en.insert("common:compare".to_string(), make_resource("a < b > c"));
+    index.insert("en".to_string(), en);
+
+    let items = vec![
+        make_item("common:terms", "common"),
+        make_item("common:address", "common"),
+        make_item("common:compare", "common"),
+    ];
+    let result = compute(items, index, vec!["en"]);
+    let flags: Vec<(bool, bool)> = result["resolved"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| {
+            let value = &item["hover"]["values"]["en"];
+            (
+                value["multiline"].as_bool().unwrap(),
+                value["has_markup"].as_bool().unwrap(),
+            )
+        })
+        .collect();
+
+    assert_eq!(flags, vec![(false, true), (true, false), (false, false)]);
+}
+
+#[test]
+fn namespace_stats_attach_namespace_coverage_to_hover() {
+    let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> = HashMap::new();
+    let mut en = HashMap::new();
+    for key in ["common:a", "common:b", "common:c", "common:d", "auth:login"] {
+        en.insert(key.to_string(), make_resource("value"));
+    }
+    index.insert("en".to_string(), en);
+    let mut ja = HashMap::new();
+    for key in ["common:a", "common:b", "common:c"] {
+        ja.insert(key.to_string(), make_resource("値"));
+    }
+    ja.insert("common:d".to_string(), make_resource(""));
+    index.insert("ja".to_string(), ja);
+
+    let params = resolve::ComputeParams {
+        items: vec![
+            make_item("common:a", "common"),
+            make_item("auth:login", "auth"),
+        ],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string(), "ja".to_string()],
+        index,
+        namespace_stats: true,
+        ..Default::default()
+    };
+    let result = resolve::compute(params).expect("compute should succeed");
+    let resolved = result["resolved"].as_array().unwrap();
+
+    let common = &resolved[0]["hover"]["namespace_coverage"]["ja"];
+    assert_eq!(common["translated"], 3);
+    assert_eq!(common["total"], 4);
+    assert_eq!(common["ratio"].as_f64(), Some(3.0 / 4.0));
+    let auth = &resolved[1]["hover"]["namespace_coverage"]["ja"];
+    assert_eq!(auth["translated"], 0);
+    assert_eq!(auth["ratio"].as_f64(), Some(0.0));
+
+    let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> = HashMap::new();
+    let mut en = HashMap::new();
+    en.insert("common:a".to_string(), make_resource("value"));
+    index.insert("en".to_string(), en);
+    let result = compute(vec![make_item("common:a", "common")], index, vec!["en"]);
+    assert!(result["resolved"][0]["hover"]["namespace_coverage"].is_null());
+}
+
+#[test]
+fn fallback_chains_supply_values_for_region_languages() {
+    let make_index = || {
+        let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> =
+            HashMap::new();
+        let mut en = HashMap::new();
+        en.insert("common:hello".to_string(), make_resource("Hello"));
+        index.insert("en".to_string(), en);
+        index.insert("en-US".to_string(), HashMap::new());
+        index.insert("fr".to_string(), HashMap::new());
+        index
+    };
+
+    let params = resolve::ComputeParams {
+        items: vec![make_item("common:hello", "common")],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string(), "en-US".to_string(), "fr".to_string()],
+        index: make_index(),
+        fallback_chains: HashMap::from([("en-US".to_string(), vec!["en".to_string()])]),
+        ..Default::default()
+    };
+    let result = resolve::compute(params).expect("compute should succeed");
+    let hover = &result["resolved"][0]["hover"];
+    assert_eq!(hover["missing_langs"], serde_json::json!(["fr"]));
+    assert_eq!(hover["values"]["en-US"]["value"], "Hello");
+    assert_eq!(hover["values"]["en-US"]["fallback_lang"], "en");
+    assert!(hover["values"]["en"]["fallback_lang"].is_null());
+
+    let result = compute(
+        vec![make_item("common:hello", "common")],
+        make_index(),
+        vec!["en", "en-US", "fr"],
+    );
+    let hover = &result["resolved"][0]["hover"];
+    assert_eq!(hover["missing_langs"], serde_json::json!(["en-US", "fr"]));
+    assert!(hover["values"]["en-US"]["fallback_lang"].is_null());
+}
+
+#[test]
+fn strict_placeholders_compare_placeholder_counts() {
+    let make_index = || {
+        let mut index: HashMap<String, HashMap<String, resolve::ResourceItemInput>> =
+            HashMap::new();
+        let mut en = HashMap::new();
+        en.insert(
+            "common:pair".to_string(),
+            make_resource("{{name}} and {{name}}"),
+        );
+        index.insert("en".to_string(), en);
+        let mut ja = HashMap::new();
+        ja.insert("common:pair".to_string(), make_resource("{{name}}だけ"));
+        index.insert("ja".to_string(), ja);
+        index
+    };
+
+    let lenient = compute(
+        vec![make_item("common:pair", "common")],
+        make_index(),
+        vec!["en", "ja"],
+    );
+    assert_eq!(lenient["resolved"][0]["status"], "\u{2260}"); // ≠
+
+    let params = resolve::ComputeParams {
+        items: vec![make_item("common:pair", "common")],
+        primary_lang: "en".to_string(),
+        languages: vec!["en".to_string(), "ja".to_string()],
+        index: make_index(),
+        strict_placeholders: true,
+        ..Default::default()
+    };
+    let strict = resolve::compute(params).expect("compute should succeed");
+    assert_eq!(strict["resolved"][0]["status"], "!");
+    assert_eq!(
+        strict["resolved"][0]["hover"]["reason"],
+        "placeholder_mismatch"
+    );
+}
@@ -0,0 +1,1062 @@
+use i18n_status_core::resource::discovery::{self, CheckRootsParams};
+use i18n_status_core::resource::index::{
+    self, ApplyChangesParams, BuildIndexParams, IndexCache, KeysUsingPlaceholderParams,
+    RebuildNamespaceParams, RootConfig, ValueOutliersParams,
+};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn unique_temp_dir(prefix: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "i18n-status-core-{}-{}-{}",
+        prefix,
+        std::process::id(),
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock should be after unix epoch")
+            .as_nanos()
+    ));
+    fs::create_dir_all(&dir).expect("failed to create temp directory");
+    dir
+}
+
+fn write_file(path: &PathBuf, content: &str) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("failed to create parent directory");
+    }
+    fs::write(path, content).expect("failed to write file");
+}
+
+#[test]
+fn check_roots_reports_existing_and_missing_roots() {
+    let root = unique_temp_dir("check-roots");
+    let locales_dir = root.join("locales");
+    write_file(&locales_dir.join("en/common.json"), r#"{"a":"A"}"#);
+    write_file(&locales_dir.join("ja/common.json"), r#"{"a":"A"}"#);
+    let missing_dir = root.join("messages");
+
+    let result = discovery::check_roots(CheckRootsParams {
+        roots: vec![
+            RootConfig {
+                kind: "i18next".to_string(),
+                path: locales_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            },
+            RootConfig {
+                kind: "next-intl".to_string(),
+                path: missing_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            },
+        ],
+    })
+    .expect("check_roots should succeed");
+
+    let roots = result["roots"]
+        .as_array()
+        .expect("roots should be an array");
+    assert_eq!(roots.len(), 2);
+    assert_eq!(roots[0]["kind"], "i18next");
+    assert_eq!(roots[0]["exists"], true);
+    assert_eq!(roots[0]["readable"], true);
+    assert_eq!(roots[0]["file_count"], 2);
+    assert_eq!(roots[1]["exists"], false);
+    assert_eq!(roots[1]["readable"], false);
+    assert_eq!(roots[1]["file_count"], 0);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn check_roots_counts_gettext_and_fluent_catalogs() {
+    let root = unique_temp_dir("check-roots-catalogs");
+    let po_dir = root.join("po");
+    write_file(&po_dir.join("de.po"), "msgid \"a\"\nmsgstr \"A\"\n");
+    write_file(&po_dir.join("fr.po"), "msgid \"a\"\nmsgstr \"A\"\n");
+    let ftl_dir = root.join("ftl");
+    write_file(&ftl_dir.join("en/main.ftl"), "a = A\n");
+
+    let result = discovery::check_roots(CheckRootsParams {
+        roots: vec![
+            RootConfig {
+                kind: "gettext".to_string(),
+                path: po_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            },
+            RootConfig {
+                kind: "fluent".to_string(),
+                path: ftl_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            },
+        ],
+    })
+    .expect("check_roots should succeed");
+
+    assert_eq!(result["roots"][0]["file_count"], 2);
+    assert_eq!(result["roots"][1]["file_count"], 1);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn apply_changes_adds_and_removes_language_directory_incrementally() {
+    let root = unique_temp_dir("apply-dir");
+    let locales_dir = root.join("locales");
+    write_file(&locales_dir.join("en/common.json"), r#"{"hello":"Hello"}"#);
+
+    let cache = IndexCache::new();
+    let built = index::build_index(
+        BuildIndexParams {
+            roots: vec![RootConfig {
+                kind: "i18next".to_string(),
+                path: locales_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            }],
+        },
+        &cache,
+    )
+    .expect("build_index should succeed");
+    let cache_key = built["cache_key"].as_str().unwrap().to_string();
+    assert_eq!(built["languages"], serde_json::json!(["en"]));
+
+    let ja_dir = locales_dir.join("ja");
+    write_file(
+        &ja_dir.join("common.json"),
+        "{\n  \"hello\": \"こんにちは\",\n  \"bye\": \"さようなら\"\n}\n",
+    );
+    let added = index::apply_changes(
+        ApplyChangesParams {
+            cache_key: cache_key.clone(),
+            paths: vec![ja_dir.to_string_lossy().to_string()],
+        },
+        &cache,
+    )
+    .expect("apply_changes should succeed");
+    assert_eq!(added["success"], true);
+    assert_eq!(
+        added["result"]["languages"],
+        serde_json::json!(["en", "ja"])
+    );
+    assert_eq!(
+        added["result"]["index"]["ja"]["common:hello"]["value"],
+        "こんにちは"
+    );
+    assert_eq!(
+        added["changed_keys"],
+        serde_json::json!([
+            { "key": "common:bye", "lang": "ja", "lnum": 2 },
+            { "key": "common:hello", "lang": "ja", "lnum": 1 }
+        ])
+    );
+
+    fs::remove_dir_all(&ja_dir).expect("failed to remove ja directory");
+    let removed = index::apply_changes(
+        ApplyChangesParams {
+            cache_key,
+            paths: vec![ja_dir.to_string_lossy().to_string()],
+        },
+        &cache,
+    )
+    .expect("apply_changes should succeed");
+    assert_eq!(removed["success"], true);
+    assert_eq!(removed["result"]["languages"], serde_json::json!(["en"]));
+    assert!(removed["result"]["index"].get("ja").is_none());
+    assert_eq!(
+        removed["changed_keys"],
+        serde_json::json!([
+            { "key": "common:bye", "lang": "ja" },
+            { "key": "common:hello", "lang": "ja" }
+        ])
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn apply_changes_reports_line_of_changed_keys() {
+    let root = unique_temp_dir("apply-changed-keys");
+    let locales_dir = root.join("locales");
+    let en_common = locales_dir.join("en/common.json");
+    write_file(
+        &en_common,
+        "{\n  \"hello\": \"Hello\",\n  \"bye\": \"Bye\"\n}\n",
+    );
+
+    let cache = IndexCache::new();
+    let built = index::build_index(
+        BuildIndexParams {
+            roots: vec![RootConfig {
+                kind: "i18next".to_string(),
+                path: locales_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            }],
+        },
+        &cache,
+    )
+    .expect("build_index should succeed");
+    let cache_key = built["cache_key"].as_str().unwrap().to_string();
+
+    write_file(
+        &en_common,
+        "{\n  \"hello\": \"Hello\",\n  \"bye\": \"Goodbye\"\n}\n",
+    );
+    let result = index::apply_changes(
+        ApplyChangesParams {
+            cache_key,
+            paths: vec![en_common.to_string_lossy().to_string()],
+        },
+        &cache,
+    )
+    .expect("apply_changes should succeed");
+
+    assert_eq!(result["success"], true);
+    assert_eq!(
+        result["changed_keys"],
+        serde_json::json!([{ "key": "common:bye", "lang": "en", "lnum": 2 }])
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn flat_namespace_keeps_dotted_top_level_keys() {
+    let root = unique_temp_dir("flat-namespaces");
+    let locales_dir = root.join("locales");
+    write_file(
+        &locales_dir.join("en/changelog.json"),
+        r#"{"v1.2.3": "First release", "v2": {"beta": "Nested"}}"#,
+    );
+    write_file(
+        &locales_dir.join("en/common.json"),
+        r#"{"menu": {"open": "Open"}}"#,
+    );
+
+    let cache = IndexCache::new();
+    let built = index::build_index(
+        BuildIndexParams {
+            roots: vec![RootConfig {
+                kind: "i18next".to_string(),
+                path: locales_dir.to_string_lossy().to_string(),
+                flat_namespaces: Some(vec!["changelog".to_string()]),
+                ..Default::default()
+            }],
+        },
+        &cache,
+    )
+    .expect("build_index should succeed");
+
+    let en = &built["index"]["en"];
+    assert_eq!(en["changelog:v1.2.3"]["value"], "First release");
+    assert_eq!(en["changelog:v2"]["value"], r#"{"beta":"Nested"}"#);
+    assert!(en.get("changelog:v2.beta").is_none());
+    assert_eq!(en["common:menu.open"]["value"], "Open");
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn empty_resource_file_is_a_warning_not_an_error() {
+    let root = unique_temp_dir("empty-file");
+    let locales_dir = root.join("locales");
+    write_file(&locales_dir.join("en/common.json"), r#"{"hello":"Hello"}"#);
+    write_file(&locales_dir.join("en/draft.json"), "  \n");
+
+    let cache = IndexCache::new();
+    let built = index::build_index(
+        BuildIndexParams {
+            roots: vec![RootConfig {
+                kind: "i18next".to_string(),
+                path: locales_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            }],
+        },
+        &cache,
+    )
+    .expect("build_index should succeed");
+
+    assert_eq!(built["errors"], serde_json::json!([]));
+    let warnings = built["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0]["kind"], "empty_file");
+    assert_eq!(warnings[0]["lang"], "en");
+    assert!(
+        warnings[0]["file"]
+            .as_str()
+            .unwrap()
+            .ends_with("draft.json")
+    );
+    assert_eq!(built["index"]["en"]["common:hello"]["value"], "Hello");
+    assert!(
+        built["namespaces"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!("draft"))
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn lang_paths_index_languages_from_separate_directories() {
+    let root = unique_temp_dir("lang-paths");
+    let locales_dir = root.join("locales");
+    let fetched_ja = root.join("fetched/ja");
+    write_file(&locales_dir.join("en/common.json"), r#"{"hello":"Hello"}"#);
+    write_file(&locales_dir.join("ja/common.json"), r#"{"hello":"stale"}"#);
+    write_file(&fetched_ja.join("common.json"), r#"{"hello":"こんにちは"}"#);
+
+    let cache = IndexCache::new();
+    let built = index::build_index(
+        BuildIndexParams {
+            roots: vec![RootConfig {
+                kind: "i18next".to_string(),
+                path: locales_dir.to_string_lossy().to_string(),
+                lang_paths: Some(
+                    [("ja".to_string(), fetched_ja.to_string_lossy().to_string())]
+                        .into_iter()
+                        .collect(),
+                ),
+                ..Default::default()
+            }],
+        },
+        &cache,
+    )
+    .expect("build_index should succeed");
+
+    assert_eq!(built["languages"], serde_json::json!(["en", "ja"]));
+    assert_eq!(built["index"]["en"]["common:hello"]["value"], "Hello");
+    assert_eq!(built["index"]["ja"]["common:hello"]["value"], "こんにちは");
+    assert!(
+        built["index"]["ja"]["common:hello"]["file"]
+            .as_str()
+            .unwrap()
+            .contains("fetched")
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn files_root_indexes_explicitly_assigned_files() {
+    let root = unique_temp_dir("explicit-files");
+    let english = root.join("strings/english-ui.json");
+    let japanese = root.join("vendor/jp/ui-strings.json");
+    write_file(&english, r#"{"menu":{"open":"Open"}}"#);
+    write_file(&japanese, r#"{"menu":{"open":"開く"}}"#);
+
+    let cache = IndexCache::new();
+    let built = index::build_index(
+        BuildIndexParams {
+            roots: vec![RootConfig {
+                kind: "files".to_string(),
+                path: root.to_string_lossy().to_string(),
+                files: Some(vec![
+                    index::ResourceFile {
+                        path: english.to_string_lossy().to_string(),
+                        lang: "en".to_string(),
+                        namespace: "ui".to_string(),
+                    },
+                    index::ResourceFile {
+                        path: japanese.to_string_lossy().to_string(),
+                        lang: "ja".to_string(),
+                        namespace: "ui".to_string(),
+                    },
+                ]),
+                ..Default::default()
+            }],
+        },
+        &cache,
+    )
+    .expect("build_index should succeed");
+
+    assert_eq!(built["languages"], serde_json::json!(["en", "ja"]));
+    assert_eq!(built["namespaces"], serde_json::json!(["ui"]));
+    assert_eq!(built["index"]["en"]["ui:menu.open"]["value"], "Open");
+    assert_eq!(built["index"]["ja"]["ui:menu.open"]["value"], "開く");
+
+    // Edits to a listed file are applied incrementally.
+    write_file(&japanese, r#"{"menu":{"open":"ひらく"}}"#);
+    let applied = index::apply_changes(
+        ApplyChangesParams {
+            cache_key: built["cache_key"].as_str().unwrap().to_string(),
+            paths: vec![japanese.to_string_lossy().to_string()],
+        },
+        &cache,
+    )
+    .expect("apply_changes should succeed");
+    assert_eq!(applied["success"], true);
+    assert_eq!(
+        applied["result"]["index"]["ja"]["ui:menu.open"]["value"],
+        "ひらく"
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn lang_aliases_merge_aliased_directories_into_canonical_language() {
+    let root = unique_temp_dir("lang-aliases");
+    let locales_dir = root.join("locales");
+    write_file(&locales_dir.join("en/common.json"), r#"{"hello":"Hello"}"#);
+    write_file(
+        &locales_dir.join("en-US/common.json"),
+        r#"{"hello":"Howdy","color":"Color"}"#,
+    );
+
+    let cache = IndexCache::new();
+    let built = index::build_index(
+        BuildIndexParams {
+            roots: vec![RootConfig {
+                kind: "i18next".to_string(),
+                path: locales_dir.to_string_lossy().to_string(),
+                lang_aliases: Some(
+                    [("en-US".to_string(), "en".to_string())]
+                        .into_iter()
+                        .collect(),
+                ),
+                ..Default::default()
+            }],
+        },
+        &cache,
+    )
+    .expect("build_index should succeed");
+
+    assert_eq!(built["languages"], serde_json::json!(["en"]));
+    // The canonical directory wins; keys only in the alias are merged in.
+    assert_eq!(built["index"]["en"]["common:hello"]["value"], "Hello");
+    assert_eq!(built["index"]["en"]["common:color"]["value"], "Color");
+    assert!(built["index"].get("en-US").is_none());
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn namespace_roots_map_namespaces_to_contributing_roots() {
+    let root = unique_temp_dir("namespace-roots");
+    let app_dir = root.join("app/locales");
+    let shared_dir = root.join("shared/locales");
+    write_file(&app_dir.join("en/home.json"), r#"{"title":"Home"}"#);
+    write_file(&app_dir.join("en/common.json"), r#"{"ok":"OK"}"#);
+    write_file(&shared_dir.join("en/common.json"), r#"{"cancel":"Cancel"}"#);
+    write_file(&shared_dir.join("en/errors.json"), r#"{"oops":"Oops"}"#);
+    let app_path = app_dir.to_string_lossy().to_string();
+    let shared_path = shared_dir.to_string_lossy().to_string();
+
+    let cache = IndexCache::new();
+    let built = index::build_index(
+        BuildIndexParams {
+            roots: vec![
+                RootConfig {
+                    kind: "i18next".to_string(),
+                    path: app_path.clone(),
+                    ..Default::default()
+                },
+                RootConfig {
+                    kind: "i18next".to_string(),
+                    path: shared_path.clone(),
+                    ..Default::default()
+                },
+            ],
+        },
+        &cache,
+    )
+    .expect("build_index should succeed");
+
+    let namespace_roots = &built["namespace_roots"];
+    assert_eq!(namespace_roots["home"], serde_json::json!([app_path]));
+    assert_eq!(
+        namespace_roots["common"],
+        serde_json::json!([app_path, shared_path])
+    );
+    assert_eq!(namespace_roots["errors"], serde_json::json!([shared_path]));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn build_index_reports_conflicting_values_across_roots() {
+    let root = unique_temp_dir("value-conflict");
+    let app = root.join("app/locales");
+    let shared = root.join("shared/locales");
+    write_file(
+        &app.join("en/common.json"),
+        r#"{"save":"Save","cancel":"Cancel"}"#,
+    );
+    write_file(
+        &shared.join("en/common.json"),
+        r#"{"save":"Store","cancel":"Cancel","close":"Close"}"#,
+    );
+
+    let root_config = |path: &PathBuf| RootConfig {
+        kind: "i18next".to_string(),
+        path: path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    let result = index::build_index(
+        BuildIndexParams {
+            roots: vec![root_config(&app), root_config(&shared)],
+        },
+        &IndexCache::new(),
+    )
+    .expect("build_index should succeed");
+
+    let conflicts = result["value_conflicts"].as_array().unwrap();
+    assert_eq!(conflicts.len(), 1);
+    let conflict = &conflicts[0];
+    assert_eq!(conflict["kind"], "value_conflict");
+    assert_eq!(conflict["lang"], "en");
+    assert_eq!(conflict["key"], "common:save");
+    assert_eq!(conflict["kept"]["value"], "Save");
+    assert!(
+        conflict["kept"]["file"]
+            .as_str()
+            .unwrap()
+            .contains("app/locales")
+    );
+    assert_eq!(conflict["shadowed"]["value"], "Store");
+    assert!(
+        conflict["shadowed"]["file"]
+            .as_str()
+            .unwrap()
+            .contains("shared/locales")
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn build_index_reads_jsonc_with_comments_and_trailing_commas() {
+    let root = unique_temp_dir("jsonc");
+    let locales = root.join("locales");
+    write_file(
+        &locales.join("en/common.jsonc"),
+        "{\n  // TODO translate\n  \"save\": \"Save\",\n  /* grouped */\n  \"nav\": { \"home\": \"Home\", },\n}\n",
+    );
+    write_file(
+        &locales.join("en/legacy.json"),
+        "{\n  // kept in plain json\n  \"old\": \"Old\",\n}\n",
+    );
+    write_file(&locales.join("en/broken.jsonc"), "{ \"a\": \"A\" \"b\": }");
+
+    let build = |json_comments: bool| {
+        let result = index::build_index(
+            BuildIndexParams {
+                roots: vec![RootConfig {
+                    kind: "i18next".to_string(),
+                    path: locales.to_string_lossy().to_string(),
+                    json_comments,
+                    ..Default::default()
+                }],
+            },
+            &IndexCache::new(),
+        )
+        .expect("build_index should succeed");
+        let mut errors: Vec<String> = result["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| {
+                e["file"]
+                    .as_str()
+                    .unwrap()
+                    .rsplit('/')
+                    .next()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        errors.sort();
+        (result, errors)
+    };
+
+    let (result, errors) = build(false);
+    assert_eq!(result["index"]["en"]["common:save"]["value"], "Save");
+    assert_eq!(result["index"]["en"]["common:nav.home"]["value"], "Home");
+    assert_eq!(errors, vec!["broken.jsonc", "legacy.json"]);
+
+    let (result, errors) = build(true);
+    assert_eq!(result["index"]["en"]["legacy:old"]["value"], "Old");
+    assert_eq!(errors, vec!["broken.jsonc"]);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn build_index_reads_toml_catalogs() {
+    let root = unique_temp_dir("toml");
+    let locales = root.join("locales");
+    write_file(
+        &locales.join("en/common.toml"),
+        "# Common strings\nsave = \"Save\"\n\n[nav]\nhome = \"Home\"\n\n[errors]\nnot_found.title = \"Not found\"\n",
+    );
+    write_file(&locales.join("ja/common.toml"), "save = '保存'\n");
+
+    let result = index::build_index(
+        BuildIndexParams {
+            roots: vec![RootConfig {
+                kind: "i18next".to_string(),
+                path: locales.to_string_lossy().to_string(),
+                ..Default::default()
+            }],
+        },
+        &IndexCache::new(),
+    )
+    .expect("build_index should succeed");
+
+    assert_eq!(result["index"]["en"]["common:save"]["value"], "Save");
+    assert_eq!(result["index"]["en"]["common:nav.home"]["value"], "Home");
+    assert_eq!(
+        result["index"]["en"]["common:errors.not_found.title"]["value"],
+        "Not found"
+    );
+    assert_eq!(result["index"]["ja"]["common:save"]["value"], "保存");
+    assert_eq!(result["errors"], serde_json::json!([]));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn build_index_records_leaf_lines() {
+    let root = unique_temp_dir("leaf-lines");
+    let messages = root.join("messages");
+    write_file(
+        &messages.join("en.json"),
+        "{\n  \"home\": {\n    \"title\": \"Home\"\n  },\n  \"about\": {\n    \"title\": \"About\"\n  }\n}\n",
+    );
+    write_file(
+        &messages.join("ja/home.json"),
+        "{\n  // comment\n  \"title\": \"ホーム\"\n}\n",
+    );
+
+    let result = index::build_index(
+        BuildIndexParams {
+            roots: vec![RootConfig {
+                kind: "next-intl".to_string(),
+                path: messages.to_string_lossy().to_string(),
+                json_comments: true,
+                ..Default::default()
+            }],
+        },
+        &IndexCache::new(),
+    )
+    .expect("build_index should succeed");
+
+    assert_eq!(result["index"]["en"]["home:title"]["lnum"], 2);
+    assert_eq!(result["index"]["en"]["about:title"]["lnum"], 5);
+    assert_eq!(result["index"]["ja"]["home:title"]["lnum"], 2);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn build_index_reparses_only_files_whose_mtime_changed() {
+    let root = unique_temp_dir("incremental");
+    let locales = root.join("locales");
+    let common = locales.join("en/common.json");
+    let auth = locales.join("en/auth.json");
+    write_file(&common, r#"{"title":"Title"}"#);
+    write_file(&auth, r#"{"login":"Log in"}"#);
+
+    let cache = IndexCache::new();
+    let build = || {
+        index::build_index(
+            BuildIndexParams {
+                roots: vec![RootConfig {
+                    kind: "i18next".to_string(),
+                    path: locales.to_string_lossy().to_string(),
+                    ..Default::default()
+                }],
+            },
+            &cache,
+        )
+        .expect("build_index should succeed")
+    };
+    let set_mtime = |path: &PathBuf, mtime: SystemTime| {
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .and_then(|file| file.set_modified(mtime))
+            .expect("mtime should be settable");
+    };
+    let mtime = |path: &PathBuf| fs::metadata(path).unwrap().modified().unwrap();
+
+    build();
+
+    // Same mtime: the cached parse is reused even though the content differs.
+    let common_mtime = mtime(&common);
+    write_file(&common, r#"{"title":"Edited"}"#);
+    set_mtime(&common, common_mtime);
+    let result = build();
+    assert_eq!(result["index"]["en"]["common:title"]["value"], "Title");
+
+    // Newer mtime: re-parsed. Deleted and new files are picked up.
+    set_mtime(&common, common_mtime + std::time::Duration::from_secs(5));
+    fs::remove_file(&auth).unwrap();
+    write_file(&locales.join("en/home.json"), r#"{"hero":"Hero"}"#);
+    let result = build();
+    assert_eq!(result["index"]["en"]["common:title"]["value"], "Edited");
+    assert!(result["index"]["en"]["auth:login"].is_null());
+    assert_eq!(result["index"]["en"]["home:hero"]["value"], "Hero");
+    assert_eq!(result["files"].as_object().unwrap().len(), 2);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn build_index_is_deterministic_across_many_files() {
+    let root = unique_temp_dir("parallel");
+    let locales = root.join("locales");
+    let overrides = root.join("overrides");
+    for lang in 0..12 {
+        for ns in 0..6 {
+            write_file(
+                &locales.join(format!("l{}/ns{}.json", lang, ns)),
+                &format!(r#"{{"title":"Title {}","shared":"base"}}"#, lang),
+            );
+        }
+        write_file(
+            &overrides.join(format!("l{}/ns0.json", lang)),
+            r#"{"shared":"override"}"#,
+        );
+    }
+    write_file(&locales.join("l0/broken.json"), "{");
+
+    let params = || BuildIndexParams {
+        roots: vec![
+            RootConfig {
+                kind: "i18next".to_string(),
+                path: locales.to_string_lossy().to_string(),
+                ..Default::default()
+            },
+            RootConfig {
+                kind: "i18next".to_string(),
+                path: overrides.to_string_lossy().to_string(),
+                ..Default::default()
+            },
+        ],
+    };
+    let first = index::build_index(params(), &IndexCache::new()).expect("build should succeed");
+    let second = index::build_index(params(), &IndexCache::new()).expect("build should succeed");
+
+    assert_eq!(
+        serde_json::to_string(&first).unwrap(),
+        serde_json::to_string(&second).unwrap()
+    );
+    // Equal priority: the first root's file keeps the entry.
+    assert_eq!(first["index"]["l3"]["ns0:shared"]["value"], "base");
+    assert_eq!(first["value_conflicts"].as_array().unwrap().len(), 12);
+    assert_eq!(first["errors"].as_array().unwrap().len(), 1);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn apply_changes_finds_active_project_after_stale_entries_are_evicted() {
+    let root = unique_temp_dir("lru-active");
+    let cache = IndexCache::with_capacity(2);
+    let build = |name: &str| {
+        let locales = root.join(name);
+        write_file(&locales.join("en/common.json"), r#"{"title":"Title"}"#);
+        let built = index::build_index(
+            BuildIndexParams {
+                roots: vec![RootConfig {
+                    kind: "i18next".to_string(),
+                    path: locales.to_string_lossy().to_string(),
+                    ..Default::default()
+                }],
+            },
+            &cache,
+        )
+        .expect("build_index should succeed");
+        (
+            built["cache_key"].as_str().unwrap().to_string(),
+            locales.join("en/common.json"),
+        )
+    };
+    let apply = |cache_key: &str, path: &PathBuf| {
+        index::apply_changes(
+            ApplyChangesParams {
+                cache_key: cache_key.to_string(),
+                paths: vec![path.to_string_lossy().to_string()],
+            },
+            &cache,
+        )
+        .expect("apply_changes should succeed")
+    };
+
+    let (active_key, active_file) = build("active");
+    let (stale_key, stale_file) = build("stale");
+    // Using the active project refreshes it, so the next build evicts the stale one.
+    assert_eq!(apply(&active_key, &active_file)["needs_rebuild"], false);
+    build("other");
+
+    write_file(&active_file, r#"{"title":"Edited"}"#);
+    let result = apply(&active_key, &active_file);
+    assert_eq!(result["needs_rebuild"], false);
+    assert_eq!(
+        result["result"]["index"]["en"]["common:title"]["value"],
+        "Edited"
+    );
+    assert_eq!(apply(&stale_key, &stale_file)["needs_rebuild"], true);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn gettext_root_indexes_po_catalogs() {
+    let root = unique_temp_dir("gettext");
+    let locales = root.join("locales");
+    write_file(
+        &locales.join("de.po"),
+        r#"msgid ""
+msgstr ""
+"Plural-Forms: nplurals=2; plural=(n != 1);\n"
+
+msgid "Save"
+msgstr "Speichern"
+
+msgctxt "menu"
+msgid "Open"
+msgstr "Öffnen"
+
+msgid "file"
+msgid_plural "files"
+msgstr[0] "Datei"
+msgstr[1] "Dateien"
+"#,
+    );
+    write_file(
+        &locales.join("fr.po"),
+        "msgid \"Save\"\nmsgstr \"Enregistrer\"\nbogus\n",
+    );
+
+    let result = index::build_index(
+        BuildIndexParams {
+            roots: vec![RootConfig {
+                kind: "gettext".to_string(),
+                path: locales.to_string_lossy().to_string(),
+                ..Default::default()
+            }],
+        },
+        &IndexCache::new(),
+    )
+    .expect("build_index should succeed");
+
+    let de = &result["index"]["de"];
+    assert_eq!(de["messages:Save"]["value"], "Speichern");
+    assert_eq!(de["messages:Save"]["lnum"], 5);
+    assert_eq!(de["menu:Open"]["value"], "Öffnen");
+    assert_eq!(de["messages:file_one"]["value"], "Datei");
+    assert_eq!(de["messages:file_other"]["value"], "Dateien");
+    assert_eq!(
+        result["namespaces"],
+        serde_json::json!(["menu", "messages"])
+    );
+
+    let errors = result["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0]["lang"], "fr");
+    assert!(errors[0]["error"].as_str().unwrap().contains("fr.po"));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn fluent_root_indexes_ftl_messages() {
+    let root = unique_temp_dir("fluent");
+    let locales = root.join("locales");
+    write_file(
+        &locales.join("en/main.ftl"),
+        "# Main\nhello = Hello, { $name }!\nsave =\n    .label = Save\n",
+    );
+    write_file(
+        &locales.join("fr/main.ftl"),
+        "hello = Bonjour !\n%% junk line\nsave =\n    .label = Enregistrer\n",
+    );
+
+    let cache = IndexCache::new();
+    let result = index::build_index(
+        BuildIndexParams {
+            roots: vec![RootConfig {
+                kind: "fluent".to_string(),
+                path: locales.to_string_lossy().to_string(),
+                ..Default::default()
+            }],
+        },
+        &cache,
+    )
+    .expect("build_index should succeed");
+
+    assert_eq!(result["languages"], serde_json::json!(["en", "fr"]));
+    assert_eq!(result["namespaces"], serde_json::json!(["main"]));
+    assert_eq!(
+        result["index"]["en"]["main:hello"]["value"],
+        "Hello, { $name }!"
+    );
+    assert_eq!(result["index"]["en"]["main:hello"]["lnum"], 1);
+    assert_eq!(
+        result["index"]["fr"]["main:save.label"]["value"],
+        "Enregistrer"
+    );
+    assert_eq!(result["errors"], serde_json::json!([]));
+
+    // Fluent variables count as placeholders, so only English uses `name`.
+    let with_name = index::keys_using_placeholder(
+        KeysUsingPlaceholderParams {
+            cache_key: result["cache_key"].as_str().unwrap().to_string(),
+            placeholder: "name".to_string(),
+        },
+        &cache,
+    )
+    .expect("keys_using_placeholder should succeed");
+    assert_eq!(
+        with_name["keys"],
+        serde_json::json!({ "en": ["main:hello"] })
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn rebuild_namespace_rereads_only_that_namespace() {
+    let root = unique_temp_dir("rebuild-ns");
+    let locales_dir = root.join("locales");
+    write_file(&locales_dir.join("en/common.json"), r#"{"hello":"Hello"}"#);
+    write_file(
+        &locales_dir.join("ja/common.json"),
+        r#"{"hello":"こんにちは"}"#,
+    );
+    write_file(&locales_dir.join("en/auth.json"), r#"{"login":"Log in"}"#);
+
+    let cache = IndexCache::new();
+    let built = index::build_index(
+        BuildIndexParams {
+            roots: vec![RootConfig {
+                kind: "i18next".to_string(),
+                path: locales_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            }],
+        },
+        &cache,
+    )
+    .expect("build_index should succeed");
+    let cache_key = built["cache_key"].as_str().unwrap().to_string();
+
+    write_file(
+        &locales_dir.join("en/common.json"),
+        r#"{"hello":"Hi","bye":"Bye"}"#,
+    );
+    fs::remove_file(locales_dir.join("ja/common.json")).expect("failed to remove ja file");
+    write_file(&locales_dir.join("de/common.json"), r#"{"hello":"Hallo"}"#);
+    write_file(
+        &locales_dir.join("en/auth.json"),
+        r#"{"login":"Sign in now"}"#,
+    );
+
+    let rebuilt = index::rebuild_namespace(
+        RebuildNamespaceParams {
+            cache_key: cache_key.clone(),
+            namespace: "common".to_string(),
+        },
+        &cache,
+    )
+    .expect("rebuild_namespace should succeed");
+    assert_eq!(rebuilt["success"], true);
+    let entries = &rebuilt["entries"];
+    assert_eq!(entries["en"]["common:hello"]["value"], "Hi");
+    assert_eq!(entries["en"]["common:bye"]["value"], "Bye");
+    assert_eq!(entries["de"]["common:hello"]["value"], "Hallo");
+    assert!(entries.get("ja").is_none());
+    assert!(entries["en"].get("auth:login").is_none());
+
+    // The cached index keeps the other namespaces as they were built.
+    let outliers = index::value_outliers(
+        ValueOutliersParams {
+            cache_key,
+            lang: "en".to_string(),
+            long_threshold: 0,
+        },
+        &cache,
+    )
+    .expect("value_outliers should succeed");
+    assert!(
+        outliers["too_long"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!({ "key": "auth:login", "length": 6 }))
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn concurrent_apply_changes_keep_every_update() {
+    let root = unique_temp_dir("apply-concurrent");
+    let locales_dir = root.join("locales");
+    let namespaces: Vec<String> = (0..8).map(|i| format!("ns{}", i)).collect();
+    for ns in &namespaces {
+        write_file(
+            &locales_dir.join(format!("en/{}.json", ns)),
+            r#"{"key":"old"}"#,
+        );
+    }
+
+    let cache = IndexCache::new();
+    let built = index::build_index(
+        BuildIndexParams {
+            roots: vec![RootConfig {
+                kind: "i18next".to_string(),
+                path: locales_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            }],
+        },
+        &cache,
+    )
+    .expect("build_index should succeed");
+    let cache_key = built["cache_key"].as_str().unwrap().to_string();
+
+    // Each round races one update per file; a lost update leaves an old value.
+    let barrier = std::sync::Barrier::new(namespaces.len());
+    for round in 0..20 {
+        let value = format!("v{}", round);
+        std::thread::scope(|scope| {
+            for ns in &namespaces {
+                let (cache, cache_key, barrier) = (&cache, cache_key.clone(), &barrier);
+                let path = locales_dir.join(format!("en/{}.json", ns));
+                let content = format!(r#"{{"key":"{}"}}"#, value);
+                scope.spawn(move || {
+                    write_file(&path, &content);
+                    barrier.wait();
+                    let applied = index::apply_changes(
+                        ApplyChangesParams {
+                            cache_key,
+                            paths: vec![path.to_string_lossy().to_string()],
+                        },
+                        cache,
+                    )
+                    .expect("apply_changes should succeed");
+                    assert_eq!(applied["success"], true);
+                });
+            }
+        });
+
+        let current = index::apply_changes(
+            ApplyChangesParams {
+                cache_key: cache_key.clone(),
+                paths: Vec::new(),
+            },
+            &cache,
+        )
+        .expect("apply_changes should succeed");
+        for ns in &namespaces {
+            assert_eq!(
+                current["result"]["index"]["en"][format!("{}:key", ns)]["value"],
+                value.as_str(),
+                "update of {} was lost",
+                ns
+            );
+        }
+    }
+
+    let _ = fs::remove_dir_all(root);
+}
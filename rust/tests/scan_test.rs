@@ -1,4 +1,22 @@
 use i18n_status_core::scan;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn unique_temp_dir(prefix: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "i18n-status-core-{}-{}-{}",
+        prefix,
+        std::process::id(),
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock should be after unix epoch")
+            .as_nanos()
+    ));
+    fs::create_dir_all(&dir).expect("failed to create temp directory");
+    dir
+}
 
 fn extract(source: &str, lang: &str, fallback_ns: &str) -> serde_json::Value {
     let params = scan::ExtractParams {
@@ -6,6 +24,7 @@ fn extract(source: &str, lang: &str, fallback_ns: &str) -> serde_json::Value {
         lang: lang.to_string(),
         fallback_namespace: fallback_ns.to_string(),
         range: None,
+        ..Default::default()
     };
     scan::extract(params).expect("extract should succeed")
 }
@@ -25,6 +44,7 @@ fn extract_with_range(
             start_line,
             end_line,
         }),
+        ..Default::default()
     };
     scan::extract(params).expect("extract should succeed")
 }
@@ -172,6 +192,78 @@ t("third");
     assert_eq!(items[0]["raw"], "second");
 }
 
+#[test]
+fn multiple_ranges_return_union_without_duplicates() {
+    let source = r#"
+t("first");
+t("second");
+t("third");
+t("fourth");
+"#;
+    let params = scan::ExtractParams {
+        source: source.to_string(),
+        lang: "tsx".to_string(),
+        fallback_namespace: "ns".to_string(),
+        ranges: Some(vec![
+            scan::Range {
+                start_line: 1,
+                end_line: 2,
+            },
+            scan::Range {
+                start_line: 2,
+                end_line: 2,
+            },
+            scan::Range {
+                start_line: 4,
+                end_line: 4,
+            },
+        ]),
+        ..Default::default()
+    };
+    let result = scan::extract(params).expect("extract should succeed");
+    let raws: Vec<&str> = result["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["raw"].as_str().unwrap())
+        .collect();
+    assert_eq!(raws, vec!["first", "second", "fourth"]);
+}
+
+#[test]
+fn custom_hook_names_establish_namespace_scope() {
+    let source = r#"
+function Page() {
+  const { t } = useAppTranslation("billing");
+  return t("invoice");
+}
+"#;
+    let default_hooks = extract(source, "tsx", "common");
+    assert_eq!(default_hooks["items"][0]["key"], "common:invoice");
+
+    let params = scan::ExtractParams {
+        source: source.to_string(),
+        lang: "tsx".to_string(),
+        fallback_namespace: "common".to_string(),
+        hook_names: Some(vec!["useAppTranslation".to_string()]),
+        ..Default::default()
+    };
+    let result = scan::extract(params).expect("extract should succeed");
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["key"], "billing:invoice");
+
+    let context = scan::translation_context_at(scan::TranslationContextParams {
+        source: source.to_string(),
+        lang: "tsx".to_string(),
+        row: 3,
+        fallback_namespace: "common".to_string(),
+        hook_names: Some(vec!["useAppTranslation".to_string()]),
+    })
+    .expect("should succeed");
+    assert_eq!(context["namespace"], "billing");
+}
+
 #[test]
 fn translation_context_at_basic() {
     let source = r#"
@@ -185,6 +277,7 @@ function Page() {
         lang: "tsx".to_string(),
         row: 3,
         fallback_namespace: "translation".to_string(),
+        hook_names: None,
     };
     let result = scan::translation_context_at(params).expect("should succeed");
     assert_eq!(result["namespace"], "home");
@@ -207,6 +300,7 @@ function Page() {
         lang: "tsx".to_string(),
         row: 0,
         fallback_namespace: "default_ns".to_string(),
+        hook_names: None,
     };
     let result = scan::translation_context_at(params).expect("should succeed");
     assert_eq!(result["namespace"], "default_ns");
@@ -460,3 +554,676 @@ fn extract_root_resource_uses_top_level_namespace() {
     assert_eq!(items[1]["key"], "admin:save");
     assert_eq!(items[1]["namespace"], "admin");
 }
+
+#[test]
+fn extracts_calls_inside_ts_namespace_block() {
+    let source = r#"
+namespace App {
+  export function Page() {
+    const { t } = useTranslation("app");
+    return t("title");
+  }
+}
+"#;
+    let result = extract(source, "tsx", "translation");
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["key"], "app:title");
+    assert_eq!(items[0]["namespace"], "app");
+}
+
+#[test]
+fn extracts_calls_inside_ambient_module_block() {
+    let source = r#"
+declare module "widgets" {
+  const label = t("common:label");
+}
+"#;
+    let result = extract(source, "typescript", "translation");
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["key"], "common:label");
+}
+
+#[test]
+fn shebang_prefixed_file_keeps_original_locations() {
+    let source = "#!/usr/bin/env node\nconst { t } = useTranslation(\"cli\");\nt(\"usage\");\n";
+    let result = extract(source, "typescript", "translation");
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["key"], "cli:usage");
+    assert_eq!(items[0]["lnum"], 2);
+    assert_eq!(items[0]["col"], 2);
+}
+
+#[test]
+fn leading_bom_is_ignored() {
+    let source = "\u{feff}t(\"common:title\");\n";
+    let result = extract(source, "typescript", "translation");
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["key"], "common:title");
+    assert_eq!(items[0]["lnum"], 0);
+    assert_eq!(items[0]["col"], 2);
+}
+
+#[test]
+fn component_census_counts_keys_per_component() {
+    let root = unique_temp_dir("census");
+    fs::write(
+        root.join("App.tsx"),
+        r#"
+import { useTranslation } from "react-i18next";
+
+export function Header() {
+  const { t } = useTranslation("common");
+  return <h1>{t("title")}</h1>;
+}
+
+export const Footer = () => {
+  const { t } = useTranslation("common");
+  return (
+    <footer>
+      {t("copyright")}
+      {t("contact")}
+      {t("copyright")}
+    </footer>
+  );
+};
+
+const outside = t("ignored");
+"#,
+    )
+    .expect("failed to write source");
+
+    let result = scan::component_census(
+        scan::ComponentCensusParams {
+            project_root: root.to_string_lossy().to_string(),
+            fallback_namespace: "common".to_string(),
+            cancel_token_path: None,
+        },
+        &scan::ProjectScanCache::new(),
+    )
+    .expect("component_census should succeed");
+
+    assert_eq!(result["cancelled"], false);
+    let components = result["components"].as_array().unwrap();
+    assert_eq!(components.len(), 2);
+    assert_eq!(components[0]["component"], "Footer");
+    assert_eq!(components[0]["key_count"], 3);
+    assert_eq!(components[0]["unique_keys"], 2);
+    assert_eq!(components[1]["component"], "Header");
+    assert_eq!(components[1]["key_count"], 1);
+    assert_eq!(components[1]["unique_keys"], 1);
+    assert!(components[1]["file"].as_str().unwrap().ends_with("App.tsx"));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn references_for_key_answers_from_indexed_project() {
+    let root = unique_temp_dir("index-project");
+    let file = root.join("Page.tsx");
+    fs::write(
+        &file,
+        r#"
+import { useTranslation } from "react-i18next";
+
+export function Page() {
+  const { t } = useTranslation("home");
+  return <h1>{t("title")}</h1>;
+}
+"#,
+    )
+    .expect("failed to write source");
+
+    let cache = scan::ProjectScanCache::new();
+    let project_root = root.to_string_lossy().to_string();
+    let indexed = scan::index_project(
+        scan::IndexProjectParams {
+            project_root: project_root.clone(),
+            fallback_namespace: "common".to_string(),
+            cancel_token_path: None,
+            stream: false,
+        },
+        &cache,
+        &|_, _| {},
+    )
+    .expect("index_project should succeed");
+    assert_eq!(indexed["file_count"], 1);
+    assert!(indexed["cache_token"].is_string());
+
+    let result = scan::references_for_key(
+        scan::ReferencesForKeyParams {
+            project_root,
+            fallback_namespace: "common".to_string(),
+            key: "home:title".to_string(),
+            cancel_token_path: None,
+        },
+        &cache,
+    )
+    .expect("references_for_key should succeed");
+
+    assert_eq!(result["cached"], true);
+    let references = result["references"].as_array().unwrap();
+    assert_eq!(references.len(), 1);
+    assert_eq!(references[0]["lnum"], 5);
+    assert!(
+        references[0]["file"]
+            .as_str()
+            .unwrap()
+            .ends_with("Page.tsx")
+    );
+
+    // Files deleted since the scan are dropped from the cached scan.
+    fs::remove_file(&file).expect("failed to remove source");
+    let result = scan::references_for_key(
+        scan::ReferencesForKeyParams {
+            project_root: root.to_string_lossy().to_string(),
+            fallback_namespace: "common".to_string(),
+            key: "home:title".to_string(),
+            cancel_token_path: None,
+        },
+        &cache,
+    )
+    .expect("references_for_key should succeed");
+    assert_eq!(result["cached"], true);
+    assert_eq!(result["references"], serde_json::json!([]));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn project_scan_cache_evicts_least_recently_used_project() {
+    let roots: Vec<_> = ["scan-lru-a", "scan-lru-b"]
+        .iter()
+        .map(|prefix| {
+            let root = unique_temp_dir(prefix);
+            fs::write(root.join("a.ts"), r#"t("title");"#).expect("failed to write source");
+            root
+        })
+        .collect();
+
+    let cache = scan::ProjectScanCache::with_capacity(1);
+    let references = |root: &std::path::PathBuf| {
+        scan::references_for_key(
+            scan::ReferencesForKeyParams {
+                project_root: root.to_string_lossy().to_string(),
+                fallback_namespace: "common".to_string(),
+                key: "common:title".to_string(),
+                cancel_token_path: None,
+            },
+            &cache,
+        )
+        .expect("references_for_key should succeed")
+    };
+    for root in &roots {
+        scan::index_project(
+            scan::IndexProjectParams {
+                project_root: root.to_string_lossy().to_string(),
+                fallback_namespace: "common".to_string(),
+                cancel_token_path: None,
+                stream: false,
+            },
+            &cache,
+            &|_, _| {},
+        )
+        .expect("index_project should succeed");
+    }
+
+    assert_eq!(references(&roots[0])["cached"], false);
+    assert_eq!(references(&roots[1])["cached"], true);
+
+    for root in roots {
+        let _ = fs::remove_dir_all(root);
+    }
+}
+
+#[test]
+fn index_project_stream_sends_file_results() {
+    let root = unique_temp_dir("index-project-stream");
+    fs::write(root.join("a.ts"), r#"t("first"); t("second");"#).expect("failed to write source");
+    fs::write(root.join("b.ts"), r#"t("third");"#).expect("failed to write source");
+
+    let cache = scan::ProjectScanCache::new();
+    let notifications = Mutex::new(Vec::new());
+    let result = scan::index_project(
+        scan::IndexProjectParams {
+            project_root: root.to_string_lossy().to_string(),
+            fallback_namespace: "common".to_string(),
+            cancel_token_path: None,
+            stream: true,
+        },
+        &cache,
+        &|method, payload| {
+            notifications
+                .lock()
+                .unwrap()
+                .push((method.to_string(), payload));
+        },
+    )
+    .expect("index_project should succeed");
+
+    assert_eq!(result["file_count"], 2);
+    assert_eq!(result["item_count"], 3);
+    assert!(result.get("cache_token").is_none());
+
+    let mut notifications = notifications.into_inner().unwrap();
+    notifications.sort_by_key(|(_, payload)| payload["file"].as_str().unwrap().to_string());
+    assert_eq!(notifications.len(), 2);
+    assert_eq!(notifications[0].0, "scan/fileResult");
+    assert!(
+        notifications[0].1["file"]
+            .as_str()
+            .unwrap()
+            .ends_with("a.ts")
+    );
+    assert_eq!(notifications[0].1["items"][1]["key"], "common:second");
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn default_value_from_positional_and_options_object() {
+    let source = r#"
+t("a.positional", "Positional default");
+t("a.object", { defaultValue: "Object default", count: 2 });
+t("a.none", { count: 2 });
+"#;
+    let result = extract(source, "javascript", "common");
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0]["default_value"], "Positional default");
+    assert_eq!(items[1]["default_value"], "Object default");
+    assert!(items[2].get("default_value").is_none());
+}
+
+#[test]
+fn dynamic_keys_lists_unresolvable_t_calls() {
+    let root = unique_temp_dir("dynamic-keys");
+    fs::write(
+        root.join("page.ts"),
+        "const { t } = useTranslation(\"common\");\nt(\"static.key\");\nconst label = t(keyName);\n",
+    )
+    .expect("failed to write source");
+
+    let result = scan::dynamic_keys(scan::DynamicKeysParams {
+        project_root: root.to_string_lossy().to_string(),
+        fallback_namespace: "common".to_string(),
+        cancel_token_path: None,
+    })
+    .expect("dynamic_keys should succeed");
+
+    assert_eq!(result["cancelled"], false);
+    let sites = result["sites"].as_array().unwrap();
+    assert_eq!(sites.len(), 1);
+    assert!(sites[0]["file"].as_str().unwrap().ends_with("page.ts"));
+    assert_eq!(sites[0]["lnum"], 2);
+    assert_eq!(sites[0]["col"], 16);
+    assert_eq!(sites[0]["snippet"], "keyName");
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn resolves_key_returned_by_local_literal_function() {
+    let source = r#"
+const PREFIX = "pages";
+function getKey() {
+  return "home";
+}
+const getTitleKey = () => `${PREFIX}.title`;
+function withParam(name) {
+  return "ignored";
+}
+t(getKey());
+t(getTitleKey());
+t(withParam("x"));
+"#;
+    let result = extract(source, "javascript", "common");
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["key"], "common:home");
+    assert_eq!(items[1]["key"], "common:pages.title");
+}
+
+#[test]
+fn inline_static_array_iteration_emits_each_element() {
+    let source = r#"
+const { t } = useTranslation("menu");
+["open", "close"].forEach((k) => t(k));
+for (const label of ["save"] as const) {
+  t(label);
+}
+items.forEach((k) => t(k));
+"#;
+    let result = extract(source, "typescript", "common");
+    let items = result["items"].as_array().unwrap();
+    let keys: Vec<&str> = items.iter().map(|i| i["key"].as_str().unwrap()).collect();
+    assert_eq!(keys, vec!["menu:open", "menu:close", "menu:save"]);
+    assert_eq!(items[0]["lnum"], 2);
+    assert_eq!(items[1]["lnum"], 2);
+}
+
+#[test]
+fn key_at_returns_canonical_key_under_cursor() {
+    let source = r#"const { t } = useTranslation("home");
+const title = t("hero.title");
+const plain = "hero.title";
+"#;
+    let key_at = |row, col| {
+        scan::key_at(scan::KeyAtParams {
+            source: source.to_string(),
+            lang: "typescript".to_string(),
+            row,
+            col,
+            fallback_namespace: "common".to_string(),
+        })
+        .expect("key_at should succeed")
+    };
+
+    let found = key_at(1, 20);
+    assert_eq!(found["key"], "home:hero.title");
+    assert_eq!(found["namespace"], "home");
+    assert_eq!(found["raw"], "hero.title");
+
+    assert!(key_at(1, 2).is_null());
+    assert!(key_at(2, 20).is_null());
+}
+
+#[test]
+fn node_at_reports_innermost_node_kind_and_span() {
+    let source = r#"const title = t(key);
+const el = <p>Hello</p>;
+"#;
+    let node_at = |row, col| {
+        scan::node_at(scan::NodeAtParams {
+            source: source.to_string(),
+            lang: "tsx".to_string(),
+            row,
+            col,
+        })
+        .expect("node_at should succeed")
+    };
+
+    let call = node_at(0, 15);
+    assert_eq!(call["kind"], "CallExpr");
+    assert_eq!(call["lnum"], 0);
+    assert_eq!(call["col"], 14);
+    assert_eq!(call["end_lnum"], 0);
+    assert_eq!(call["end_col"], 20);
+
+    assert_eq!(node_at(0, 16)["kind"], "Ident");
+    assert_eq!(node_at(1, 15)["kind"], "JSXText");
+}
+
+#[test]
+fn const_arrow_component_exported_later_uses_hook_namespace() {
+    let source = r#"import { useTranslation } from "react-i18next";
+
+const Page = () => {
+  const { t } = useTranslation("home");
+  return <h1>{t("title")}</h1>;
+};
+
+const Other = () => {
+  const { t } = useTranslation("about");
+  return <p>{t("title")}</p>;
+};
+
+export default Page;
+export { Other };
+"#;
+    let result = extract(source, "tsx", "common");
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["key"], "home:title");
+    assert_eq!(items[0]["fallback"], false);
+    assert_eq!(items[1]["key"], "about:title");
+    assert_eq!(items[1]["fallback"], false);
+}
+
+#[test]
+fn extracts_key_from_configured_message_component() {
+    let source = r#"
+const { t } = useTranslation("home");
+const el = <FormattedMessage id="greeting" />;
+const other = <Unrelated id="ignored" />;
+"#;
+    let params = scan::ExtractParams {
+        source: source.to_string(),
+        lang: "tsx".to_string(),
+        fallback_namespace: "common".to_string(),
+        message_components: vec![scan::MessageComponent {
+            name: "FormattedMessage".to_string(),
+            key_attr: "id".to_string(),
+        }],
+        ..Default::default()
+    };
+    let result = scan::extract(params).expect("extract should succeed");
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["key"], "home:greeting");
+    assert_eq!(items[0]["raw"], "greeting");
+    assert_eq!(items[0]["lnum"], 2);
+    assert_eq!(items[0]["col"], 32);
+}
+
+#[test]
+fn astro_frontmatter_and_template_expressions() {
+    let source = r#"---
+import { useTranslations } from "../i18n";
+const t = useTranslations("home");
+const title = t("title");
+---
+<h1 class="hero">{title}</h1>
+<p>{t("intro")}</p>
+"#;
+    let result = extract(source, "astro", "translation");
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["key"], "home:title");
+    assert_eq!(items[0]["lnum"], 3);
+    assert_eq!(items[1]["key"], "home:intro");
+    assert_eq!(items[1]["lnum"], 6);
+    assert_eq!(items[1]["col"], 6);
+}
+
+#[test]
+fn aria_attribute_expression_is_extracted() {
+    let source = r#"
+const { t } = useTranslation("common");
+const button = <button aria-label={t("close")} onClick={close}>×</button>;
+"#;
+    let result = extract(source, "tsx", "translation");
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["key"], "common:close");
+    assert_eq!(items[0]["lnum"], 2);
+}
+
+#[test]
+fn ns_separator_false_treats_colon_as_literal() {
+    let params: scan::ExtractParams = serde_json::from_value(serde_json::json!({
+        "source": r#"t("time: 10:30");"#,
+        "lang": "ts",
+        "fallback_namespace": "common",
+        "ns_separator": false,
+        "key_separator": false
+    }))
+    .expect("params should deserialize");
+    let result = scan::extract(params).expect("extract should succeed");
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["key"], "common:time: 10:30");
+    assert_eq!(items[0]["namespace"], "common");
+}
+
+#[test]
+fn unresolved_calls_carry_a_key_pattern() {
+    let params = scan::ExtractParams {
+        source: "t(\"static\");\nt(`errors.${code}`);\nt(keyName);\n".to_string(),
+        lang: "ts".to_string(),
+        fallback_namespace: "common".to_string(),
+        include_unresolved: true,
+        ..Default::default()
+    };
+    let result = scan::extract(params).expect("extract should succeed");
+
+    assert_eq!(result["items"].as_array().unwrap().len(), 1);
+    let unresolved = result["unresolved"].as_array().unwrap();
+    assert_eq!(unresolved.len(), 2);
+    assert_eq!(unresolved[0]["lnum"], 1);
+    assert_eq!(unresolved[0]["pattern"], "errors.*");
+    assert!(unresolved[1].get("pattern").is_none());
+}
+
+#[test]
+fn extracts_from_source_with_import_attributes() {
+    let source = r#"import messages from "./messages.json" with { type: "json" };
+const { t } = useTranslation("common");
+t("greeting");
+"#;
+    for lang in ["javascript", "typescript"] {
+        let result = extract(source, lang, "translation");
+        let items = result["items"].as_array().unwrap();
+        assert_eq!(items.len(), 1, "{}", lang);
+        assert_eq!(items[0]["key"], "common:greeting");
+    }
+}
+
+#[test]
+fn suggest_namespace_returns_scope_namespace_and_its_files() {
+    use i18n_status_core::resource::index::{self, BuildIndexParams, IndexCache, RootConfig};
+
+    let root = unique_temp_dir("suggest-namespace");
+    let locales = root.join("locales");
+    for (lang, ns, body) in [
+        ("en", "settings", r#"{"title":"Settings"}"#),
+        ("en", "common", r#"{"ok":"OK"}"#),
+        ("ja", "settings", r#"{"title":"設定"}"#),
+    ] {
+        let dir = locales.join(lang);
+        fs::create_dir_all(&dir).expect("failed to create locale dir");
+        fs::write(dir.join(format!("{}.json", ns)), body).expect("failed to write resource");
+    }
+    let cache = IndexCache::new();
+    let built = index::build_index(
+        BuildIndexParams {
+            roots: vec![RootConfig {
+                kind: "i18next".to_string(),
+                path: locales.to_string_lossy().to_string(),
+                ..Default::default()
+            }],
+        },
+        &cache,
+    )
+    .expect("build_index should succeed");
+    let cache_key = built["cache_key"].as_str().unwrap().to_string();
+
+    let source = r#"
+function SettingsPage() {
+  const { t } = useTranslation("settings");
+  return null;
+}
+"#;
+    let result = scan::suggest_namespace(
+        scan::SuggestNamespaceParams {
+            source: source.to_string(),
+            lang: "tsx".to_string(),
+            row: 3,
+            fallback_namespace: "common".to_string(),
+            cache_key,
+            hook_names: None,
+        },
+        &cache,
+    )
+    .expect("suggest_namespace should succeed");
+
+    assert_eq!(result["namespace"], "settings");
+    assert_eq!(result["found_hook"], true);
+    let files = result["files"].as_object().unwrap();
+    assert_eq!(files.len(), 2);
+    assert!(
+        files["en"]
+            .as_str()
+            .unwrap()
+            .ends_with("locales/en/settings.json")
+    );
+    assert!(
+        files["ja"]
+            .as_str()
+            .unwrap()
+            .ends_with("locales/ja/settings.json")
+    );
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn include_call_shape_describes_call_arguments() {
+    let source = "t(\"k\", { count, ns: \"x\" });\nt(\"plain\");\n";
+    let params = scan::ExtractParams {
+        source: source.to_string(),
+        lang: "ts".to_string(),
+        fallback_namespace: "common".to_string(),
+        include_call_shape: true,
+        ..Default::default()
+    };
+    let result = scan::extract(params).expect("extract should succeed");
+    let items = result["items"].as_array().unwrap();
+
+    assert_eq!(
+        items[0]["call_shape"],
+        serde_json::json!({ "arg_count": 2, "has_options": true, "option_keys": ["count", "ns"] })
+    );
+    assert_eq!(
+        items[1]["call_shape"],
+        serde_json::json!({ "arg_count": 1, "has_options": false, "option_keys": [] })
+    );
+
+    let without_shape = extract(source, "ts", "common");
+    assert!(without_shape["items"][0].get("call_shape").is_none());
+}
+
+#[test]
+fn include_call_shape_marks_computed_option_keys_incomplete() {
+    let source = "t(\"k\", { [dynamicKey]: v, [\"literal\"]: 1, count });\n";
+    let params = scan::ExtractParams {
+        source: source.to_string(),
+        lang: "ts".to_string(),
+        fallback_namespace: "common".to_string(),
+        include_call_shape: true,
+        ..Default::default()
+    };
+    let result = scan::extract(params).expect("extract should succeed");
+
+    assert_eq!(
+        result["items"][0]["call_shape"],
+        serde_json::json!({
+            "arg_count": 2,
+            "has_options": true,
+            "option_keys": ["literal", "count"],
+            "option_keys_incomplete": true
+        })
+    );
+}
+
+#[test]
+fn namespaces_in_file_lists_distinct_namespaces() {
+    let source = r#"
+const Page = () => {
+  const { t } = useTranslation("home");
+  return <h1>{t("title")}{t("common:save")}{t("hero.subtitle")}</h1>;
+};
+"#;
+    let result = scan::namespaces_in_file(scan::NamespacesInFileParams {
+        source: source.to_string(),
+        lang: "tsx".to_string(),
+        fallback_namespace: "common".to_string(),
+        hook_names: None,
+    })
+    .expect("namespaces_in_file should succeed");
+
+    assert_eq!(result["namespaces"], serde_json::json!(["common", "home"]));
+}